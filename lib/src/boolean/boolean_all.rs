@@ -0,0 +1,104 @@
+use super::compute_fields::{FieldsRecord, FieldsRecorder};
+use super::connect_edges::connect_edges;
+use super::fill_queue::fill_queue;
+use super::helper::Float;
+use super::subdivide_segments::subdivide_observing;
+use super::sweep_event::{EventArena, EventId};
+use super::{
+    all_coords_finite, bounding_boxes_disjoint, contours_to_multi_polygon, empty_bounding_box, trivial_result,
+    BooleanOpError, FillRules, Operation,
+};
+use geo_types::{CoordNum, MultiPolygon, Polygon};
+
+/// Results of all four boolean operations on the same pair of operands.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BooleanResults<F>
+where
+    F: CoordNum,
+{
+    pub intersection: MultiPolygon<F>,
+    pub union: MultiPolygon<F>,
+    pub difference: MultiPolygon<F>,
+    pub xor: MultiPolygon<F>,
+}
+
+/// A sweep over both operands whose events can be classified for several results.
+pub(crate) struct SharedSweep<F>
+where
+    F: Float,
+{
+    events: EventArena<F>,
+    sorted_events: Vec<EventId>,
+    records: Vec<FieldsRecord>,
+    fill_rules: FillRules,
+}
+
+impl<F> SharedSweep<F>
+where
+    F: Float,
+{
+    /// Performs the sweep, or returns `None` if the operands are trivially disjoint, i.e.,
+    /// all results can be assembled from the input polygons directly.
+    pub(crate) fn new(
+        subject: &[Polygon<F>],
+        clipping: &[Polygon<F>],
+        fill_rules: FillRules,
+    ) -> Result<Option<SharedSweep<F>>, BooleanOpError> {
+        if !all_coords_finite(subject) || !all_coords_finite(clipping) {
+            return Err(BooleanOpError::NonFiniteCoordinate);
+        }
+
+        let mut sbbox = empty_bounding_box();
+        let mut cbbox = sbbox;
+
+        let mut events = EventArena::new();
+        let mut event_queue = fill_queue(subject, clipping, &mut sbbox, &mut cbbox, Operation::Union, &mut events);
+
+        if fill_rules.is_default() && bounding_boxes_disjoint(&sbbox, &cbbox) {
+            return Ok(None);
+        }
+
+        let mut recorder = FieldsRecorder::default();
+        let sorted_events =
+            subdivide_observing(&mut events, &mut event_queue, &sbbox, &cbbox, fill_rules, &mut recorder)?;
+        Ok(Some(SharedSweep {
+            events,
+            sorted_events,
+            records: recorder.records,
+            fill_rules,
+        }))
+    }
+
+    /// Classifies the events for the operation and connects the edges of the result.
+    pub(crate) fn result_of(&mut self, operation: Operation) -> Result<MultiPolygon<F>, BooleanOpError> {
+        for record in &self.records {
+            record.replay(&mut self.events, operation, self.fill_rules);
+        }
+        let contours = connect_edges(&mut self.events, &self.sorted_events)?;
+        Ok(contours_to_multi_polygon(&contours))
+    }
+}
+
+pub(crate) fn boolean_all_operation<F>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    fill_rules: FillRules,
+) -> Result<BooleanResults<F>, BooleanOpError>
+where
+    F: Float,
+{
+    match SharedSweep::new(subject, clipping, fill_rules)? {
+        Some(mut sweep) => Ok(BooleanResults {
+            intersection: sweep.result_of(Operation::Intersection)?,
+            union: sweep.result_of(Operation::Union)?,
+            difference: sweep.result_of(Operation::Difference)?,
+            xor: sweep.result_of(Operation::Xor)?,
+        }),
+        None => Ok(BooleanResults {
+            intersection: trivial_result(subject, clipping, Operation::Intersection),
+            union: trivial_result(subject, clipping, Operation::Union),
+            difference: trivial_result(subject, clipping, Operation::Difference),
+            xor: trivial_result(subject, clipping, Operation::Xor),
+        }),
+    }
+}
@@ -1,34 +1,43 @@
-use super::helper::Float;
-use super::segment_intersection::{intersection, LineIntersection};
-use super::signed_area::signed_area;
-use super::sweep_event::SweepEvent;
+use super::error::BooleanOpError;
+use super::helper::OrderedField;
+use super::kernel::Kernel;
+use super::segment_intersection::LineIntersection;
+use super::sweep_event::{EventArena, EventId};
 use std::cmp::Ordering;
-use std::rc::Rc;
 
 use super::helper;
 
-pub fn compare_segments<F>(se1_l: &Rc<SweepEvent<F>>, se2_l: &Rc<SweepEvent<F>>) -> Ordering
+/// Compares the segments of the left events `se1_l` and `se2_l` by their position on the
+/// sweep line. Right events and events without a right event are reported as errors.
+pub fn compare_segments<F, K, P>(
+    events: &EventArena<F, K, P>,
+    se1_l: EventId,
+    se2_l: EventId,
+) -> Result<Ordering, BooleanOpError>
 where
-    F: Float,
+    F: OrderedField,
+    K: Kernel<F>,
 {
-    debug_assert!(
-        se1_l.is_left(),
-        "compare_segments requires left-events, got a right-event."
-    );
-    debug_assert!(
-        se2_l.is_left(),
-        "compare_segments requires left-events, got a right-event."
-    );
-    debug_assert!(
-        se1_l.get_other_event().is_some(),
-        "missing right-event in compare_segments"
-    );
-    debug_assert!(
-        se2_l.get_other_event().is_some(),
-        "missing right-event in compare_segments"
-    );
-
-    if Rc::ptr_eq(se1_l, se2_l) {
+    for se_l in [se1_l, se2_l] {
+        if !events[se_l].is_left() {
+            return Err(BooleanOpError::UnexpectedRightEvent);
+        }
+        if events[se_l].get_other_event().is_none() {
+            return Err(BooleanOpError::MissingOtherEvent);
+        }
+    }
+    Ok(segment_order(events, se1_l, se2_l))
+}
+
+/// Unchecked variant of `compare_segments`, which is the comparator of the sweep line. The
+/// sweep only inserts left events, and checks for their right event before, so that
+/// comparisons of the segments on the sweep line cannot fail.
+pub(crate) fn segment_order<F, K, P>(events: &EventArena<F, K, P>, se1_l: EventId, se2_l: EventId) -> Ordering
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    if se1_l == se2_l {
         return Ordering::Equal;
     }
 
@@ -36,21 +45,22 @@ where
     // SweepEvent w.r.t. the segment of the earlier/newer one. The logic is easier to
     // express by swapping them here according to their temporal order. In case we have
     // to swap, the result function must be inverted accordingly.
-    let (se_old_l, se_new_l, less_if) = if se1_l.is_before(se2_l) {
+    let (se_old_l_id, se_new_l_id, less_if) = if events.is_before(se1_l, se2_l) {
         (se1_l, se2_l, helper::less_if as fn(bool) -> Ordering)
     } else {
         (se2_l, se1_l, helper::less_if_inversed as fn(bool) -> Ordering)
     };
+    let (se_old_l, se_new_l) = (&events[se_old_l_id], &events[se_new_l_id]);
 
-    if let (Some(se_old_r), Some(se_new_r)) = (se_old_l.get_other_event(), se_new_l.get_other_event()) {
-        let sa_l = signed_area(se_old_l.point, se_old_r.point, se_new_l.point);
-        let sa_r = signed_area(se_old_l.point, se_old_r.point, se_new_r.point);
-        if sa_l != 0. || sa_r != 0. {
+    if let (Some(se_old_r), Some(se_new_r)) = (events.other(se_old_l_id), events.other(se_new_l_id)) {
+        let sa_l = K::orientation(se_old_l.point, se_old_r.point, se_new_l.point);
+        let sa_r = K::orientation(se_old_l.point, se_old_r.point, se_new_r.point);
+        if (sa_l != Ordering::Equal || sa_r != Ordering::Equal) && !events.are_on_same_line(se_old_l_id, se_new_l_id) {
             // Segments are not collinear
 
             // Left endpoints exactly identical? Use the right endpoint to sort
             if se_old_l.point == se_new_l.point {
-                return less_if(se_old_l.is_below(se_new_r.point));
+                return less_if(events.is_below(se_old_l_id, se_new_r.point));
             }
 
             // Left endpoints identical in x, but different in y? Sort by y
@@ -58,27 +68,37 @@ where
                 return less_if(se_old_l.point.y < se_new_l.point.y);
             }
 
+            // If `l` lies on the input edge of the reference segment, it is the intersection
+            // point used by `possible_intersection`, even if it is not exactly on the segment
+            // because the segment has been divided at a rounded point.
+            if sa_l != Ordering::Equal
+                && se_old_l.get_source() != se_new_l.get_source()
+                && events.is_on_edge(se_old_l_id, se_new_l.point)
+            {
+                return less_if(sa_r == Ordering::Greater);
+            }
+
             // If `l` and `r` lie on the same side of the reference segment,
             // no intersection check is necessary.
-            if (sa_l > 0.) == (sa_r > 0.) {
-                return less_if(sa_l > 0.);
+            if (sa_l == Ordering::Greater) == (sa_r == Ordering::Greater) {
+                return less_if(sa_l == Ordering::Greater);
             }
 
             // If `l` lies on the reference segment, compare based on `r`.
-            if sa_l == 0. {
-                return less_if(sa_r > 0.);
+            if sa_l == Ordering::Equal {
+                return less_if(sa_r == Ordering::Greater);
             }
 
             // According to the signed-area values the segments cross. Verify if
             // we can get an intersection point whic is truely different from `l`.
-            let inter = intersection(se_old_l.point, se_old_r.point, se_new_l.point, se_new_r.point);
+            let inter = K::intersection(se_old_l.point, se_old_r.point, se_new_l.point, se_new_r.point);
             match inter {
-                LineIntersection::None => return less_if(sa_l > 0.),
+                LineIntersection::None => return less_if(sa_l == Ordering::Greater),
                 LineIntersection::Point(p) => {
                     if p == se_new_l.point {
-                        return less_if(sa_r > 0.);
+                        return less_if(sa_r == Ordering::Greater);
                     } else {
-                        return less_if(sa_l > 0.);
+                        return less_if(sa_l == Ordering::Greater);
                     }
                 }
                 _ => {} // go into collinear logic below
@@ -91,7 +111,13 @@ where
                 // Previously this was returning Ordering::Equal if the segments had identical
                 // left and right endpoints. I think in order to properly support self-overlapping
                 // segments we must return Ordering::Equal if and only if segments are the same
-                // by identity (the Rc::ptr_eq above).
+                // by identity (the id comparison above).
+                if se_old_l.contour_id == se_new_l.contour_id {
+                    // Overlapping segments of the same contour are equal in all properties,
+                    // so their temporal order is undefined. Only their identity is left to
+                    // order them consistently.
+                    return se1_l.cmp(&se2_l);
+                }
                 less_if(se_old_l.contour_id < se_new_l.contour_id)
             } else {
                 // Fallback to purely temporal-based comparison. Since `less_if` already
@@ -102,36 +128,39 @@ where
             less_if(se_old_l.is_subject)
         }
     } else {
-        debug_assert!(false, "Other events should always be defined in compare_segment.");
-        less_if(true)
+        // Only unchecked segments lack a right event, see `compare_segments`.
+        se1_l.cmp(&se2_l)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::super::sweep_event::SweepEvent;
+    use super::super::error::BooleanOpError;
+    use super::super::provenance::EdgeSource;
+    use super::super::sweep_event::{EventArena, EventId, SweepEvent};
     use super::compare_segments;
     use crate::splay::SplaySet;
     use geo_types::Coord;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use std::cmp::Ordering;
-    use std::rc::{Rc, Weak};
 
     macro_rules! assert_ordering {
-        ($se1:expr, $se2:expr, $ordering:expr) => {
+        ($events:expr, $se1:expr, $se2:expr, $ordering:expr) => {
             let inverse_ordering = match $ordering {
                 Ordering::Less => Ordering::Greater,
                 Ordering::Greater => Ordering::Less,
                 _ => Ordering::Equal,
             };
             assert_eq!(
-                compare_segments(&$se1, &$se2),
-                $ordering,
+                compare_segments(&$events, $se1, $se2),
+                Ok($ordering),
                 "Comparing se1/se2 with expected value {:?}",
                 $ordering
             );
             assert_eq!(
-                compare_segments(&$se2, &$se1),
-                inverse_ordering,
+                compare_segments(&$events, $se2, $se1),
+                Ok(inverse_ordering),
                 "Comparing se2/se1 with expected value {:?}",
                 inverse_ordering
             );
@@ -139,47 +168,64 @@ mod test {
     }
 
     fn make_simple(
+        events: &mut EventArena<f64>,
         contour_id: u32,
         x: f64,
         y: f64,
         other_x: f64,
         other_y: f64,
         is_subject: bool,
-    ) -> (Rc<SweepEvent<f64>>, Rc<SweepEvent<f64>>) {
-        let other = SweepEvent::new_rc(
-            contour_id,
-            Coord { x: other_x, y: other_y },
-            false,
-            Weak::new(),
-            is_subject,
-            true,
-        );
-        let event = SweepEvent::new_rc(
-            contour_id,
-            Coord { x, y },
-            true,
-            Rc::downgrade(&other),
-            is_subject,
-            true,
+    ) -> (EventId, EventId) {
+        let (event, other) = events.push_pair(
+            SweepEvent::new(contour_id, Coord { x, y }, true, None, is_subject, true),
+            SweepEvent::new(
+                contour_id,
+                Coord { x: other_x, y: other_y },
+                false,
+                None,
+                is_subject,
+                true,
+            ),
         );
         // Make sure test cases fulfill the invariant of left/right relationship.
-        assert!(event.is_before(&other));
+        assert!(events.is_before(event, other));
 
         (event, other)
     }
 
+    /// Part of the input edge `line` from `left` to `right`, whose endpoints may be rounded
+    /// intersection points off the edge.
+    fn make_part(
+        events: &mut EventArena<f64>,
+        source: EdgeSource,
+        line: [Coord<f64>; 2],
+        left: Coord<f64>,
+        right: Coord<f64>,
+    ) -> EventId {
+        let contour_id = source.edge as u32;
+        let is_subject = source.operand == 0;
+        let (se, other) = make_simple(events, contour_id, left.x, left.y, right.x, right.y, is_subject);
+        for event in [se, other] {
+            events[event].set_source(source);
+            events[event].set_line(line);
+        }
+        se
+    }
+
     #[test]
     fn not_collinear_shared_left_right_first() {
-        let (se1, _other1) = make_simple(0, 0.0, 0.0, 1.0, 1.0, false);
-        let (se2, _other2) = make_simple(0, 0.0, 0.0, 2.0, 3.0, false);
+        let mut events = EventArena::new();
+        let (se1, _other1) = make_simple(&mut events, 0, 0.0, 0.0, 1.0, 1.0, false);
+        let (se2, _other2) = make_simple(&mut events, 0, 0.0, 0.0, 2.0, 3.0, false);
 
-        let mut tree = SplaySet::new(compare_segments);
+        let comparator = |a: &EventId, b: &EventId| compare_segments(&events, *a, *b).unwrap();
+        let mut tree = SplaySet::new(comparator);
 
         tree.insert(se1);
         tree.insert(se2);
 
-        let min_other = tree.min().unwrap().get_other_event().unwrap();
-        let max_other = tree.max().unwrap().get_other_event().unwrap();
+        let min_other = events.other(*tree.min().unwrap()).unwrap();
+        let max_other = events.other(*tree.max().unwrap()).unwrap();
 
         assert_eq!(max_other.point, Coord { x: 2.0, y: 3.0 });
         assert_eq!(min_other.point, Coord { x: 1.0, y: 1.0 });
@@ -187,16 +233,18 @@ mod test {
 
     #[test]
     fn not_collinear_different_left_point_right_sort_y() {
-        let (se1, _other1) = make_simple(0, 0.0, 1.0, 1.0, 1.0, false);
-        let (se2, _other2) = make_simple(0, 0.0, 2.0, 2.0, 3.0, false);
+        let mut events = EventArena::new();
+        let (se1, _other1) = make_simple(&mut events, 0, 0.0, 1.0, 1.0, 1.0, false);
+        let (se2, _other2) = make_simple(&mut events, 0, 0.0, 2.0, 2.0, 3.0, false);
 
-        let mut tree = SplaySet::new(compare_segments);
+        let comparator = |a: &EventId, b: &EventId| compare_segments(&events, *a, *b).unwrap();
+        let mut tree = SplaySet::new(comparator);
 
         tree.insert(se1);
         tree.insert(se2);
 
-        let min_other = tree.min().unwrap().get_other_event().unwrap();
-        let max_other = tree.max().unwrap().get_other_event().unwrap();
+        let min_other = events.other(*tree.min().unwrap()).unwrap();
+        let max_other = events.other(*tree.max().unwrap()).unwrap();
 
         assert_eq!(min_other.point, Coord { x: 1.0, y: 1.0 });
         assert_eq!(max_other.point, Coord { x: 2.0, y: 3.0 });
@@ -204,128 +252,278 @@ mod test {
 
     #[test]
     fn not_collinear_order_in_sweep_line() {
-        let (se1, _other1) = make_simple(0, 0.0, 1.0, 2.0, 1.0, false);
-        let (se2, _other2) = make_simple(0, -1.0, 0.0, 2.0, 3.0, false);
-        let (se3, _other3) = make_simple(0, 0.0, 1.0, 3.0, 4.0, false);
-        let (se4, _other4) = make_simple(0, -1.0, 0.0, 3.0, 1.0, false);
+        let mut events = EventArena::new();
+        let (se1, _other1) = make_simple(&mut events, 0, 0.0, 1.0, 2.0, 1.0, false);
+        let (se2, _other2) = make_simple(&mut events, 0, -1.0, 0.0, 2.0, 3.0, false);
+        let (se3, _other3) = make_simple(&mut events, 0, 0.0, 1.0, 3.0, 4.0, false);
+        let (se4, _other4) = make_simple(&mut events, 0, -1.0, 0.0, 3.0, 1.0, false);
 
-        assert_eq!(se1.cmp(&se2), Ordering::Less);
-        assert!(!se2.is_below(se1.point));
-        assert!(se2.is_above(se1.point));
+        assert_eq!(events.cmp_events(se1, se2), Ordering::Less);
+        assert!(!events.is_below(se2, events[se1].point));
+        assert!(events.is_above(se2, events[se1].point));
 
-        assert_ordering!(se1, se2, Ordering::Less);
+        assert_ordering!(events, se1, se2, Ordering::Less);
 
-        assert_eq!(se3.cmp(&se4), Ordering::Less);
-        assert!(!se4.is_above(se3.point));
+        assert_eq!(events.cmp_events(se3, se4), Ordering::Less);
+        assert!(!events.is_above(se4, events[se3].point));
     }
 
     #[test]
     fn not_collinear_first_point_is_below() {
-        let (se2, _other2) = make_simple(0, 1.0, 1.0, 5.0, 1.0, false);
-        let (se1, _other1) = make_simple(0, -1.0, 0.0, 2.0, 3.0, false);
+        let mut events = EventArena::new();
+        let (se2, _other2) = make_simple(&mut events, 0, 1.0, 1.0, 5.0, 1.0, false);
+        let (se1, _other1) = make_simple(&mut events, 0, -1.0, 0.0, 2.0, 3.0, false);
 
-        assert!(!se1.is_below(se2.point));
-        assert_ordering!(se1, se2, Ordering::Greater);
+        assert!(!events.is_below(se1, events[se2].point));
+        assert_ordering!(events, se1, se2, Ordering::Greater);
     }
 
     #[test]
     fn collinear_segments() {
-        let (se1, _other1) = make_simple(0, 1.0, 1.0, 5.0, 1.0, true);
-        let (se2, _other2) = make_simple(0, 2.0, 01.0, 3.0, 1.0, false);
+        let mut events = EventArena::new();
+        let (se1, _other1) = make_simple(&mut events, 0, 1.0, 1.0, 5.0, 1.0, true);
+        let (se2, _other2) = make_simple(&mut events, 0, 2.0, 01.0, 3.0, 1.0, false);
 
-        assert_ne!(se1.is_subject, se2.is_subject);
-        assert_ordering!(se1, se2, Ordering::Less);
+        assert_ne!(events[se1].is_subject, events[se2].is_subject);
+        assert_ordering!(events, se1, se2, Ordering::Less);
     }
 
     #[test]
     fn collinear_shared_left_point() {
         {
-            let (se1, _other2) = make_simple(1, 0.0, 1.0, 5.0, 1.0, false);
-            let (se2, _other1) = make_simple(2, 0.0, 1.0, 3.0, 1.0, false);
+            let mut events = EventArena::new();
+            let (se1, _other2) = make_simple(&mut events, 1, 0.0, 1.0, 5.0, 1.0, false);
+            let (se2, _other1) = make_simple(&mut events, 2, 0.0, 1.0, 3.0, 1.0, false);
 
-            assert_eq!(se1.is_subject, se2.is_subject);
-            assert_eq!(se1.point, se2.point);
+            assert_eq!(events[se1].is_subject, events[se2].is_subject);
+            assert_eq!(events[se1].point, events[se2].point);
 
-            assert_ordering!(se1, se2, Ordering::Less);
+            assert_ordering!(events, se1, se2, Ordering::Less);
         }
         {
-            let (se1, _other2) = make_simple(2, 0.0, 1.0, 5.0, 1.0, false);
-            let (se2, _other1) = make_simple(1, 0.0, 1.0, 3.0, 1.0, false);
+            let mut events = EventArena::new();
+            let (se1, _other2) = make_simple(&mut events, 2, 0.0, 1.0, 5.0, 1.0, false);
+            let (se2, _other1) = make_simple(&mut events, 1, 0.0, 1.0, 3.0, 1.0, false);
 
-            assert_ordering!(se1, se2, Ordering::Greater);
+            assert_ordering!(events, se1, se2, Ordering::Greater);
         }
     }
 
     #[test]
     fn collinear_same_polygon_different_left() {
-        let (se1, _other2) = make_simple(0, 1.0, 1.0, 5.0, 1.0, true);
-        let (se2, _other1) = make_simple(0, 2.0, 1.0, 3.0, 1.0, true);
+        let mut events = EventArena::new();
+        let (se1, _other2) = make_simple(&mut events, 0, 1.0, 1.0, 5.0, 1.0, true);
+        let (se2, _other1) = make_simple(&mut events, 0, 2.0, 1.0, 3.0, 1.0, true);
 
-        assert_eq!(se1.is_subject, se2.is_subject);
-        assert_ne!(se1.point, se2.point);
-        assert_ordering!(se1, se2, Ordering::Less);
+        assert_eq!(events[se1].is_subject, events[se2].is_subject);
+        assert_ne!(events[se1].point, events[se2].point);
+        assert_ordering!(events, se1, se2, Ordering::Less);
     }
 
     #[test]
     fn t_shaped_cases() {
+        let mut events = EventArena::new();
+
         // shape:  /
         //        /\
-        let (se1, _other1) = make_simple(0, 0.0, 0.0, 1.0, 1.0, true);
-        let (se2, _other2) = make_simple(0, 0.5, 0.5, 1.0, 0.0, true);
-        assert_ordering!(se1, se2, Ordering::Greater);
+        let (se1, _other1) = make_simple(&mut events, 0, 0.0, 0.0, 1.0, 1.0, true);
+        let (se2, _other2) = make_simple(&mut events, 0, 0.5, 0.5, 1.0, 0.0, true);
+        assert_ordering!(events, se1, se2, Ordering::Greater);
 
         // shape: \/
         //         \
-        let (se1, _other1) = make_simple(0, 0.0, 1.0, 1.0, 0.0, true);
-        let (se2, _other2) = make_simple(0, 0.5, 0.5, 1.0, 1.0, true);
-        assert_ordering!(se1, se2, Ordering::Less);
+        let (se1, _other1) = make_simple(&mut events, 0, 0.0, 1.0, 1.0, 0.0, true);
+        let (se2, _other2) = make_simple(&mut events, 0, 0.5, 0.5, 1.0, 1.0, true);
+        assert_ordering!(events, se1, se2, Ordering::Less);
 
         // shape: T
-        let (se1, _other1) = make_simple(0, 0.0, 1.0, 1.0, 1.0, true);
-        let (se2, _other2) = make_simple(0, 0.5, 0.0, 0.5, 1.0, true);
-        assert_ordering!(se1, se2, Ordering::Greater);
+        let (se1, _other1) = make_simple(&mut events, 0, 0.0, 1.0, 1.0, 1.0, true);
+        let (se2, _other2) = make_simple(&mut events, 0, 0.5, 0.0, 0.5, 1.0, true);
+        assert_ordering!(events, se1, se2, Ordering::Greater);
 
         // shape: T upside down
-        let (se1, _other1) = make_simple(0, 0.0, 0.0, 1.0, 0.0, true);
-        let (se2, _other2) = make_simple(0, 0.5, 0.0, 0.5, 1.0, true);
-        assert_ordering!(se1, se2, Ordering::Less);
+        let (se1, _other1) = make_simple(&mut events, 0, 0.0, 0.0, 1.0, 0.0, true);
+        let (se2, _other2) = make_simple(&mut events, 0, 0.5, 0.0, 0.5, 1.0, true);
+        assert_ordering!(events, se1, se2, Ordering::Less);
     }
 
     #[test]
     fn vertical_segment() {
+        let e = &mut EventArena::new();
+
         // vertical reference segment at x = 0, expanding from y = -1 to +1.
-        let (se1, _other1) = make_simple(0, 0.0, -1.0, 0.0, 1.0, true);
+        let (se1, _other1) = make_simple(e, 0, 0.0, -1.0, 0.0, 1.0, true);
 
         // "above" cases
-        let (se2, _other2) = make_simple(0, -1.0, 1.0, 0.0, 1.0, true);
-        assert_ordering!(se1, se2, Ordering::Less);
-        let (se2, _other2) = make_simple(0, 0.0, 1.0, 1.0, 1.0, true);
-        assert_ordering!(se1, se2, Ordering::Less);
-        let (se2, _other2) = make_simple(0, -1.0, 2.0, 0.0, 2.0, true);
-        assert_ordering!(se1, se2, Ordering::Less);
-        let (se2, _other2) = make_simple(0, 0.0, 2.0, 1.0, 2.0, true);
-        assert_ordering!(se1, se2, Ordering::Less);
-        let (se2, _other2) = make_simple(0, 0.0, 1.0, 0.0, 2.0, true);
-        assert_ordering!(se1, se2, Ordering::Less);
+        let (se2, _other2) = make_simple(e, 0, -1.0, 1.0, 0.0, 1.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Less);
+        let (se2, _other2) = make_simple(e, 0, 0.0, 1.0, 1.0, 1.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Less);
+        let (se2, _other2) = make_simple(e, 0, -1.0, 2.0, 0.0, 2.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Less);
+        let (se2, _other2) = make_simple(e, 0, 0.0, 2.0, 1.0, 2.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Less);
+        let (se2, _other2) = make_simple(e, 0, 0.0, 1.0, 0.0, 2.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Less);
 
         // "below" cases
-        let (se2, _other2) = make_simple(0, -1.0, -1.0, 0.0, -1.0, true);
-        assert_ordering!(se1, se2, Ordering::Greater);
-        let (se2, _other2) = make_simple(0, 0.0, -1.0, 1.0, -1.0, true);
-        assert_ordering!(se1, se2, Ordering::Greater);
-        let (se2, _other2) = make_simple(0, -1.0, -2.0, 0.0, -2.0, true);
-        assert_ordering!(se1, se2, Ordering::Greater);
-        let (se2, _other2) = make_simple(0, 0.0, -2.0, 1.0, -2.0, true);
-        assert_ordering!(se1, se2, Ordering::Greater);
-        let (se2, _other2) = make_simple(0, 0.0, -2.0, 0.0, -1.0, true);
-        assert_ordering!(se1, se2, Ordering::Greater);
+        let (se2, _other2) = make_simple(e, 0, -1.0, -1.0, 0.0, -1.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Greater);
+        let (se2, _other2) = make_simple(e, 0, 0.0, -1.0, 1.0, -1.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Greater);
+        let (se2, _other2) = make_simple(e, 0, -1.0, -2.0, 0.0, -2.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Greater);
+        let (se2, _other2) = make_simple(e, 0, 0.0, -2.0, 1.0, -2.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Greater);
+        let (se2, _other2) = make_simple(e, 0, 0.0, -2.0, 0.0, -1.0, true);
+        assert_ordering!(e, se1, se2, Ordering::Greater);
 
         // overlaps
-        let (se2, _other2) = make_simple(0, 0.0, -0.5, 0.0, 0.5, true);
-        assert_ordering!(se1, se2, Ordering::Less);
-        // When left endpoints are identical, the ordering is no longer anti-symmetric.
-        // TODO: Decide if this is a problem.
-        // let (se2, _other2) = make_simple(0, 0.0, -1.0, 0.0, 0.0, true);
-        // assert_ordering!(se1, se2, Ordering::Less); // fails because of its not anti-symmetric.
+        let (se2, _other2) = make_simple(e, 0, 0.0, -0.5, 0.0, 0.5, true);
+        assert_ordering!(e, se1, se2, Ordering::Less);
+        // When left endpoints are identical within the same contour, the segments are ordered
+        // by identity, which must still be anti-symmetric.
+        let (se2, _other2) = make_simple(e, 0, 0.0, -1.0, 0.0, 0.0, true);
+        let ordering = compare_segments(e, se1, se2).unwrap();
+        assert_ne!(ordering, Ordering::Equal);
+        assert_ordering!(e, se1, se2, ordering);
+    }
+
+    #[test]
+    fn invalid_events_are_reported() {
+        let mut events = EventArena::new();
+        let (se1, other1) = make_simple(&mut events, 0, 0.0, 0.0, 1.0, 1.0, false);
+        let (se2, _other2) = make_simple(&mut events, 0, 0.0, 1.0, 1.0, 0.0, true);
+        let lonely = events.push(SweepEvent::new(1, Coord { x: 0.5, y: 0.0 }, true, None, true, true));
+
+        assert_eq!(
+            compare_segments(&events, se1, other1),
+            Err(BooleanOpError::UnexpectedRightEvent)
+        );
+        assert_eq!(
+            compare_segments(&events, other1, se2),
+            Err(BooleanOpError::UnexpectedRightEvent)
+        );
+        assert_eq!(
+            compare_segments(&events, se2, lonely),
+            Err(BooleanOpError::MissingOtherEvent)
+        );
+    }
+
+    #[test]
+    fn starting_on_the_edge_of_a_divided_segment() {
+        let mut events = EventArena::new();
+        let edge = |operand, edge| EdgeSource {
+            operand,
+            edge,
+            ..EdgeSource::default()
+        };
+        let line = [Coord { x: 0.0, y: 0.0 }, Coord { x: 3.0, y: 1.0 }];
+
+        // The part ends at the rounded intersection at x = 2, which is below the edge, so the
+        // point (1.5, 0.5) of the edge is above the part.
+        let part = make_part(&mut events, edge(0, 0), line, line[0], Coord { x: 2.0, y: 2.0 / 3.0 });
+        assert!(events.is_below(part, Coord { x: 1.5, y: 0.5 }));
+        assert!(events.is_on_edge(part, Coord { x: 1.5, y: 0.5 }));
+
+        // Segments starting on the edge are ordered by their right endpoint.
+        let start = Coord { x: 1.5, y: 0.5 };
+        let down = make_part(
+            &mut events,
+            edge(1, 0),
+            [start, Coord { x: 2.0, y: 0.0 }],
+            start,
+            Coord { x: 2.0, y: 0.0 },
+        );
+        let up = make_part(
+            &mut events,
+            edge(1, 1),
+            [start, Coord { x: 2.0, y: 1.0 }],
+            start,
+            Coord { x: 2.0, y: 1.0 },
+        );
+        assert_ordering!(events, part, down, Ordering::Greater);
+        assert_ordering!(events, part, up, Ordering::Less);
+
+        // The part of the other operand on the same edge is collinear, although it is not
+        // exactly on the line of the divided part.
+        let collinear = make_part(&mut events, edge(1, 2), line, start, line[1]);
+        assert!(events.are_on_same_line(part, collinear));
+        assert_ordering!(events, part, collinear, Ordering::Less);
+        assert_ordering!(events, down, collinear, Ordering::Less);
+        assert_ordering!(events, up, collinear, Ordering::Greater);
+    }
+
+    #[test]
+    fn order_of_rounded_parts_is_total() {
+        for seed in 0..10 {
+            check_order_of_rounded_parts(seed);
+        }
+    }
+
+    /// Builds non-crossing input edges, each divided at rounded points, and segments starting
+    /// at points exactly on the edges, which are not exactly on the divided parts. Both operands
+    /// have a part of each edge. The parts start at the same point, like after the sweep has
+    /// divided them at the start of their overlap, but their ends are rounded differently.
+    ///
+    /// The segments which intersect the sweep line at x = 5 must be totally ordered, i.e., the
+    /// order has to be antisymmetric and transitive, and agree with the order of the edges.
+    /// Parts of the same edge are collinear, so the part of the subject is below the one of
+    /// the clipping.
+    fn check_order_of_rounded_parts(seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut events = EventArena::new();
+        let mut segments = Vec::new();
+        let sweep_x = 5.0;
+        for lane in 0..6 {
+            let offset = 3.0 * lane as f64;
+            let slope = rng.gen_range(-6..=6) as f64 / 64.0;
+            let point_at = |x: f64| Coord {
+                x,
+                y: offset + slope * x,
+            };
+            let line = [point_at(0.0), point_at(10.0)];
+            let left_x: f64 = rng.gen_range(0.0..sweep_x);
+            for operand in 0..2 {
+                let source = EdgeSource {
+                    operand,
+                    edge: lane,
+                    ..EdgeSource::default()
+                };
+                let right = point_at(rng.gen_range(sweep_x..10.0));
+                segments.push((
+                    lane,
+                    Some(operand),
+                    make_part(&mut events, source, line, point_at(left_x), right),
+                ));
+
+                let start_x = rng.gen_range(0..40) as f64 / 8.0;
+                let start = point_at(start_x);
+                let end = Coord {
+                    y: point_at(start_x + 5.0).y + rng.gen_range(-1.0..1.0),
+                    ..point_at(start_x + 5.0)
+                };
+                let source = EdgeSource { ring: 1, ..source };
+                segments.push((lane, None, make_part(&mut events, source, [start, end], start, end)));
+            }
+        }
+
+        for &(lane_a, operand_a, a) in &segments {
+            for &(lane_b, operand_b, b) in &segments {
+                let ordering = compare_segments(&events, a, b).unwrap();
+                assert_eq!(compare_segments(&events, b, a).unwrap(), ordering.reverse());
+                assert_eq!(ordering == Ordering::Equal, a == b);
+                if lane_a != lane_b {
+                    assert_eq!(ordering, lane_a.cmp(&lane_b));
+                } else if let (Some(operand_a), Some(operand_b)) = (operand_a, operand_b) {
+                    assert_eq!(ordering, operand_a.cmp(&operand_b));
+                }
+                for &(_, _, c) in &segments {
+                    if ordering == Ordering::Less && compare_segments(&events, b, c).unwrap() == Ordering::Less {
+                        assert_eq!(compare_segments(&events, a, c).unwrap(), Ordering::Less);
+                    }
+                }
+            }
+        }
     }
 }
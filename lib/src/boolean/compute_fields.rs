@@ -1,104 +1,261 @@
-use super::helper::Float;
-use super::sweep_event::{EdgeType, ResultTransition, SweepEvent};
-use super::Operation;
-use std::rc::Rc;
+use super::helper::OrderedField;
+use super::observer::SweepObserver;
+use super::sweep_event::{EdgeType, EventArena, EventId, ResultTransition, SweepEvent};
+use super::{FillRules, Operation};
 
-pub fn compute_fields<F>(event: &Rc<SweepEvent<F>>, maybe_prev: Option<&Rc<SweepEvent<F>>>, operation: Operation)
-where
-    F: Float,
+pub fn compute_fields<F, K, P>(
+    events: &mut EventArena<F, K, P>,
+    event: EventId,
+    maybe_prev: Option<EventId>,
+    operation: Operation,
+    fill_rules: FillRules,
+) where
+    F: OrderedField,
 {
-    if let Some(prev) = maybe_prev {
-        if event.is_subject == prev.is_subject {
-            event.set_in_out(!prev.is_in_out(), prev.is_other_in_out());
-        } else if prev.is_vertical() {
-            event.set_in_out(!prev.is_other_in_out(), !prev.is_in_out());
+    compute_windings(events, event, maybe_prev, fill_rules);
+    let state = WindingState::of(&events[event]);
+    compute_result_fields(events, event, maybe_prev, &state, operation, fill_rules);
+
+    #[cfg(feature = "tracing")]
+    {
+        let se = &events[event];
+        tracing::trace!(
+            event,
+            in_out = se.is_in_out(),
+            other_in_out = se.is_other_in_out(),
+            result_transition = ?se.get_result_transition(),
+            edge_type = ?se.get_edge_type(),
+            "compute fields"
+        );
+    }
+}
+
+/// Computes the winding numbers of the event, which do not depend on the operation.
+fn compute_windings<F, K, P>(
+    events: &mut EventArena<F, K, P>,
+    event: EventId,
+    maybe_prev: Option<EventId>,
+    fill_rules: FillRules,
+) where
+    F: OrderedField,
+{
+    if let Some(prev_id) = maybe_prev {
+        let coincident = is_coincident(events, event, prev_id);
+        let prev = &events[prev_id];
+        // A vertical `prev` can only be below the event if the event starts on it. In this
+        // case the event lies on the right side of `prev`, which is the side the windings
+        // below its group of coincident segments refer to, so neither the winding of `prev`
+        // itself nor of the segments below it in its group must be applied.
+        let (prev_winding_above, prev_other_winding) = if events.is_vertical(prev_id) && !coincident {
+            prev.get_group_windings()
         } else {
-            event.set_in_out(!prev.is_other_in_out(), prev.is_in_out());
+            (prev.get_winding_below() + prev.get_winding(), prev.get_other_winding())
+        };
+        let (prev_group_winding, prev_group_other_winding) = if coincident {
+            prev.get_group_windings()
+        } else {
+            (prev_winding_above, prev_other_winding)
+        };
+        let prev_is_subject = prev.is_subject;
+
+        let event = &mut events[event];
+        if event.is_subject == prev_is_subject {
+            event.set_winding_numbers(prev_winding_above, prev_other_winding);
+            event.set_group_windings(prev_group_winding, prev_group_other_winding);
+        } else {
+            event.set_winding_numbers(prev_other_winding, prev_winding_above);
+            event.set_group_windings(prev_group_other_winding, prev_group_winding);
         }
+    } else {
+        events[event].set_winding_numbers(0, 0);
+        events[event].set_group_windings(0, 0);
+    }
 
+    let event = &mut events[event];
+    event.set_in_out(
+        fill_rules
+            .of_operand(event.is_subject)
+            .is_inside(event.get_winding_below()),
+        !fill_rules
+            .of_operand(!event.is_subject)
+            .is_inside(event.get_other_winding()),
+    );
+}
+
+/// Computes the fields of the event which depend on the operation, i.e., whether it is part
+/// of the result, and its connection to the previous segment in the result.
+fn compute_result_fields<F, K, P>(
+    events: &mut EventArena<F, K, P>,
+    event: EventId,
+    maybe_prev: Option<EventId>,
+    state: &WindingState,
+    operation: Operation,
+    fill_rules: FillRules,
+) where
+    F: OrderedField,
+{
+    set_result_fields(
+        events,
+        event,
+        maybe_prev,
+        state.in_result(operation, fill_rules),
+        state.is_in_above(operation, fill_rules),
+    );
+}
+
+/// Sets whether the segment is part of the result, and if so, whether the region above
+/// it belongs to the result, as well as its connection to the previous segment in the result.
+pub fn set_result_fields<F, K, P>(
+    events: &mut EventArena<F, K, P>,
+    event: EventId,
+    maybe_prev: Option<EventId>,
+    in_result: bool,
+    is_in_above: bool,
+) where
+    F: OrderedField,
+{
+    if let Some(prev) = maybe_prev {
         // Connect to previous in result: Only use the given `prev` if it is
         // part of the result and not a vertical segment. Otherwise connect
         // to its previous in result if any.
-        if prev.is_in_result() && !prev.is_vertical() {
-            event.set_prev_in_result(prev);
-        } else if let Some(prev_of_prev) = prev.get_prev_in_result() {
-            event.set_prev_in_result(&prev_of_prev);
+        if events[prev].is_in_result() && !events.is_vertical(prev) {
+            events[event].set_prev_in_result(prev);
+        } else if let Some(prev_of_prev) = events[prev].get_prev_in_result() {
+            events[event].set_prev_in_result(prev_of_prev);
         } else {
             // Clearing prev_in_result is necessary for re-computations, if the first
             // computation has already set prev_in_result, but it is no longer valid now.
-            event.unset_prev_in_result();
+            events[event].unset_prev_in_result();
         }
     } else {
-        event.set_in_out(false, true);
         // Clearing prev_in_result is necessary for re-computations, if the first
         // computation has already set prev_in_result, but it is no longer valid now.
-        event.unset_prev_in_result();
+        events[event].unset_prev_in_result();
     }
 
     // Determine whether segment is in result, and if so, whether it is an
     // in-out or out-in transition.
-    let in_result = in_result(event, operation);
     let result_transition = if !in_result {
         ResultTransition::None
+    } else if is_in_above {
+        ResultTransition::OutIn
     } else {
-        determine_result_transition(event, operation)
+        ResultTransition::InOut
     };
-    event.set_result_transition(result_transition);
+    events[event].set_result_transition(result_transition);
+}
 
-    #[cfg(feature = "debug-booleanop")]
+/// The fields of an event computed during the sweep which do not depend on the operation.
+#[derive(Clone, Copy, Debug)]
+struct WindingState {
+    is_subject: bool,
+    edge_type: EdgeType,
+    winding: i32,
+    winding_below: i32,
+    other_winding: i32,
+    group_windings: (i32, i32),
+}
+
+impl WindingState {
+    fn of<F, P>(event: &SweepEvent<F, P>) -> WindingState
+    where
+        F: OrderedField,
     {
-        println!(
-            "{{\"computeFields\": {{\"inOut\": {}, \"otherOut\": {}, \"resultTransition\": \"{:?}\", \"edgeType\": \"{:?}\"}}}}",
-            event.is_in_out(),
-            event.is_other_in_out(),
-            event.get_result_transition(),
-            event.get_edge_type(),
-        );
+        WindingState {
+            is_subject: event.is_subject,
+            edge_type: event.get_edge_type(),
+            winding: event.get_winding(),
+            winding_below: event.get_winding_below(),
+            other_winding: event.get_other_winding(),
+            group_windings: event.get_group_windings(),
+        }
+    }
+
+    /// Whether the region described by the winding numbers of the event's own and the
+    /// other polygon belongs to the result.
+    fn is_in(&self, operation: Operation, fill_rules: FillRules, winding: i32, other_winding: i32) -> bool {
+        let this_in = fill_rules.of_operand(self.is_subject).is_inside(winding);
+        let that_in = fill_rules.of_operand(!self.is_subject).is_inside(other_winding);
+        let (subject_in, clipping_in) = if self.is_subject {
+            (this_in, that_in)
+        } else {
+            (that_in, this_in)
+        };
+        operation.is_in_result(subject_in, clipping_in)
+    }
+
+    /// Whether the region directly above the segment belongs to the result.
+    fn is_in_above(&self, operation: Operation, fill_rules: FillRules) -> bool {
+        self.is_in(
+            operation,
+            fill_rules,
+            self.winding_below + self.winding,
+            self.other_winding,
+        )
+    }
+
+    /// Whether the segment separates a region of the result from one that is not.
+    fn in_result(&self, operation: Operation, fill_rules: FillRules) -> bool {
+        if self.edge_type == EdgeType::NonContributing {
+            return false;
+        }
+        let (group_winding, group_other_winding) = self.group_windings;
+        self.is_in(operation, fill_rules, group_winding, group_other_winding) != self.is_in_above(operation, fill_rules)
+    }
+}
+
+/// Records a computation of the fields of an event during the sweep. Replaying all records
+/// in order classifies the events for another operation without repeating the sweep.
+pub struct FieldsRecord {
+    event: EventId,
+    prev: Option<EventId>,
+    state: WindingState,
+}
+
+impl FieldsRecord {
+    pub fn new<F, K, P>(events: &EventArena<F, K, P>, event: EventId, maybe_prev: Option<EventId>) -> FieldsRecord
+    where
+        F: OrderedField,
+    {
+        FieldsRecord {
+            event,
+            prev: maybe_prev,
+            state: WindingState::of(&events[event]),
+        }
+    }
+
+    pub fn replay<F, K, P>(&self, events: &mut EventArena<F, K, P>, operation: Operation, fill_rules: FillRules)
+    where
+        F: OrderedField,
+    {
+        compute_result_fields(events, self.event, self.prev, &self.state, operation, fill_rules);
     }
 }
 
-fn in_result<F>(event: &SweepEvent<F>, operation: Operation) -> bool
+/// Observer recording all computations of the fields during the sweep, see `FieldsRecord`.
+#[derive(Default)]
+pub struct FieldsRecorder {
+    pub records: Vec<FieldsRecord>,
+}
+
+impl<F, K, P> SweepObserver<F, K, P> for FieldsRecorder
 where
-    F: Float,
+    F: OrderedField,
 {
-    match event.get_edge_type() {
-        EdgeType::Normal => match operation {
-            Operation::Intersection => !event.is_other_in_out(),
-            Operation::Union => event.is_other_in_out(),
-            Operation::Difference => {
-                (event.is_subject && event.is_other_in_out()) || (!event.is_subject && !event.is_other_in_out())
-            }
-            Operation::Xor => true,
-        },
-        EdgeType::SameTransition => operation == Operation::Intersection || operation == Operation::Union,
-        EdgeType::DifferentTransition => operation == Operation::Difference,
-        EdgeType::NonContributing => false,
+    fn compute_fields(&mut self, events: &EventArena<F, K, P>, event: EventId, prev: Option<EventId>) {
+        self.records.push(FieldsRecord::new(events, event, prev));
     }
 }
 
-fn determine_result_transition<F>(event: &SweepEvent<F>, operation: Operation) -> ResultTransition
+/// Whether both segments have identical end points. Coincident segments are stacked
+/// in the sweep line, and only the topmost one of such a group contributes to the result.
+pub fn is_coincident<F, K, P>(events: &EventArena<F, K, P>, event: EventId, prev: EventId) -> bool
 where
-    F: Float,
+    F: OrderedField,
 {
-    let this_in = !event.is_in_out();
-    let that_in = !event.is_other_in_out();
-    let is_in = match operation {
-        Operation::Intersection => this_in && that_in,
-        Operation::Union => this_in || that_in,
-        Operation::Xor => this_in ^ that_in,
-        Operation::Difference =>
-        // Difference is assymmetric, so subject vs clipping matters.
-        {
-            if event.is_subject {
-                this_in && !that_in
-            } else {
-                that_in && !this_in
-            }
+    events[event].point == events[prev].point
+        && match (events.other(event), events.other(prev)) {
+            (Some(event_other), Some(prev_other)) => event_other.point == prev_other.point,
+            _ => false,
         }
-    };
-    if is_in {
-        ResultTransition::OutIn
-    } else {
-        ResultTransition::InOut
-    }
 }
@@ -1,44 +1,39 @@
-use super::helper::Float;
-use super::sweep_event::{ResultTransition, SweepEvent};
+use super::error::BooleanOpError;
+use super::helper::OrderedField;
+use super::kernel::Kernel;
+use super::observer::SweepObserver;
+use super::provenance::EdgeSource;
+use super::sweep_event::{EventArena, EventId, ResultTransition};
 use geo_types::Coord;
 use std::collections::HashSet;
-use std::rc::Rc;
 
-fn order_events<F>(sorted_events: &[Rc<SweepEvent<F>>]) -> Vec<Rc<SweepEvent<F>>>
+fn order_events<F, K, P>(events: &mut EventArena<F, K, P>, sorted_events: &[EventId]) -> Vec<EventId>
 where
-    F: Float,
+    F: OrderedField,
+    K: Kernel<F>,
 {
-    let mut result_events: Vec<Rc<SweepEvent<F>>> = Vec::new();
+    let mut result_events: Vec<EventId> = Vec::new();
 
-    for event in sorted_events {
-        if (event.is_left() && event.is_in_result())
-            || (!event.is_left() && event.get_other_event().map(|o| o.is_in_result()).unwrap_or(false))
+    for &event in sorted_events {
+        if (events[event].is_left() && events[event].is_in_result())
+            || (!events[event].is_left() && events.other(event).map(|o| o.is_in_result()).unwrap_or(false))
         {
-            result_events.push(event.clone());
+            result_events.push(event);
         }
     }
 
-    let mut sorted = false;
-    while !sorted {
-        sorted = true;
-        for i in 1..result_events.len() {
-            if result_events[i - 1] < result_events[i] {
-                result_events.swap(i - 1, i);
-                sorted = false;
-            }
-        }
-    }
+    sort_events(events, &mut result_events);
 
     // Populate `other_pos` by initializing with index and swapping with other event.
-    for (pos, event) in result_events.iter().enumerate() {
-        event.set_other_pos(pos as i32)
+    for (pos, &event) in result_events.iter().enumerate() {
+        events[event].set_other_pos(pos as i32)
     }
-    for event in &result_events {
-        if event.is_left() {
-            if let Some(other) = event.get_other_event() {
-                let (a, b) = (event.get_other_pos(), other.get_other_pos());
-                event.set_other_pos(b);
-                other.set_other_pos(a);
+    for &event in &result_events {
+        if events[event].is_left() {
+            if let Some(other) = events[event].get_other_event() {
+                let (a, b) = (events[event].get_other_pos(), events[other].get_other_pos());
+                events[event].set_other_pos(b);
+                events[other].set_other_pos(a);
             }
         }
     }
@@ -46,6 +41,73 @@ where
     result_events
 }
 
+/// Average number of positions an event may be moved by the insertion sort in
+/// `sort_events`, before the events are considered unsorted.
+const MAX_AVERAGE_SHIFT: usize = 4;
+
+/// Sorts the events into the order in which the sweep processes them.
+///
+/// The events are popped from the queue in order, but dividing a segment after its left
+/// event has been processed can change the order of events sharing a point. The input is
+/// therefore nearly sorted, which an insertion sort handles in linear time. If the events
+/// have to be moved too far, a merge sort takes over to bound the worst case.
+///
+/// `cmp_events` is not a total order: events of collinear segments from the same operand
+/// compare as `Greater` in both directions. Both sorts therefore only move an event past
+/// events it `is_after`, which keeps those in the order of the sweep.
+fn sort_events<F, K, P>(events: &EventArena<F, K, P>, result_events: &mut [EventId])
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    let mut budget = result_events.len() * MAX_AVERAGE_SHIFT;
+
+    for i in 1..result_events.len() {
+        let mut j = i;
+        while j > 0 && events.is_after(result_events[j - 1], result_events[j]) {
+            if budget == 0 {
+                merge_sort_events(events, result_events);
+                return;
+            }
+            result_events.swap(j - 1, j);
+            budget -= 1;
+            j -= 1;
+        }
+    }
+}
+
+/// Stable bottom-up merge sort of the events, which takes an event of the second run before
+/// the next event of the first run only if that one `is_after` it. Unlike `slice::sort_by`,
+/// it does not require a total order.
+fn merge_sort_events<F, K, P>(events: &EventArena<F, K, P>, result_events: &mut [EventId])
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    let len = result_events.len();
+    let mut merged = result_events.to_vec();
+    let mut width = 1;
+
+    while width < len {
+        for start in (0..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+            let (mut i, mut j) = (start, mid);
+            for slot in &mut merged[start..end] {
+                if j < end && (i == mid || events.is_after(result_events[i], result_events[j])) {
+                    *slot = result_events[j];
+                    j += 1;
+                } else {
+                    *slot = result_events[i];
+                    i += 1;
+                }
+            }
+        }
+        result_events.copy_from_slice(&merged);
+        width *= 2;
+    }
+}
+
 /// Helper function that identifies groups of sweep event that belong to one
 /// vertex, and precomputes in which order the events within one group should
 /// be iterated. The result is a vector with the semantics:
@@ -133,12 +195,18 @@ fn get_next_pos(pos: i32, processed: &HashSet<i32>, iteration_map: &[usize]) ->
     }
 }
 
-pub struct Contour<F>
+pub struct Contour<F, P = ()>
 where
-    F: Float,
+    F: OrderedField,
 {
     /// Raw Coords of contour
     pub points: Vec<Coord<F>>,
+    /// Vertex payloads of the events the points are taken from, i.e., `payloads[i]` belongs to
+    /// `points[i]`.
+    pub payloads: Vec<P>,
+    /// Input edges the segments of the contour originate from, i.e., `sources[i]` is the
+    /// source of the segment from `points[i]` to `points[i + 1]`.
+    pub sources: Vec<EdgeSource>,
     /// Contour IDs of holes if any.
     pub hole_ids: Vec<i32>,
     /// Contour ID of parent if this contour is a hole.
@@ -149,13 +217,15 @@ where
     pub depth: i32,
 }
 
-impl<F> Contour<F>
+impl<F, P> Contour<F, P>
 where
-    F: Float,
+    F: OrderedField,
 {
-    pub fn new(hole_of: Option<i32>, depth: i32) -> Contour<F> {
+    pub fn new(hole_of: Option<i32>, depth: i32) -> Contour<F, P> {
         Contour {
             points: Vec::new(),
+            payloads: Vec::new(),
+            sources: Vec::new(),
             hole_ids: Vec::new(),
             hole_of,
             depth,
@@ -163,17 +233,22 @@ where
     }
 
     /// This logic implements the 4 cases of parent contours from Fig. 4 in the Martinez paper.
-    pub fn initialize_from_context(
-        event: &Rc<SweepEvent<F>>,
-        contours: &mut [Contour<F>],
+    pub fn initialize_from_context<K>(
+        events: &EventArena<F, K, P>,
+        event: EventId,
+        contours: &mut [Contour<F, P>],
         contour_id: i32,
-    ) -> Contour<F> {
-        if let Some(prev_in_result) = event.get_prev_in_result() {
+    ) -> Result<Contour<F, P>, BooleanOpError> {
+        if let Some(prev_in_result) = events[event].get_prev_in_result() {
+            let prev_in_result = &events[prev_in_result];
             // Note that it is valid to query the "previous in result" for its output contour id,
             // because we must have already processed it (i.e., assigned an output contour id)
             // in an earlier iteration, otherwise it wouldn't be possible that it is "previous in
             // result".
             let lower_contour_id = prev_in_result.get_output_contour_id();
+            if lower_contour_id < 0 || lower_contour_id as usize >= contours.len() {
+                return Err(BooleanOpError::InvalidContourNesting);
+            }
             if prev_in_result.get_result_transition() == ResultTransition::OutIn {
                 // We are inside. Now we have to check if the thing below us is another hole or
                 // an exterior contour.
@@ -184,28 +259,23 @@ where
                     contours[parent_contour_id as usize].hole_ids.push(contour_id);
                     let hole_of = Some(parent_contour_id);
                     let depth = contours[lower_contour_id as usize].depth;
-                    Contour::new(hole_of, depth)
+                    Ok(Contour::new(hole_of, depth))
                 } else {
                     // The lower contour is an exterior contour => Connect the new contour as a hole,
                     // and increment depth.
                     contours[lower_contour_id as usize].hole_ids.push(contour_id);
                     let hole_of = Some(lower_contour_id);
                     let depth = contours[lower_contour_id as usize].depth + 1;
-                    Contour::new(hole_of, depth)
+                    Ok(Contour::new(hole_of, depth))
                 }
             } else {
                 // We are outside => this contour is an exterior contour of same depth.
-                let depth = if lower_contour_id < 0 || lower_contour_id as usize >= contours.len() {
-                    debug_assert!(false, "Invalid lower_contour_id should be impossible.");
-                    0
-                } else {
-                    contours[lower_contour_id as usize].depth
-                };
-                Contour::new(None, depth)
+                let depth = contours[lower_contour_id as usize].depth;
+                Ok(Contour::new(None, depth))
             }
         } else {
             // There is no lower/previous contour => this contour is an exterior contour of depth 0.
-            Contour::new(None, 0)
+            Ok(Contour::new(None, 0))
         }
     }
 
@@ -220,26 +290,58 @@ where
     }
 }
 
-fn mark_as_processed<F>(processed: &mut HashSet<i32>, result_events: &[Rc<SweepEvent<F>>], pos: i32, contour_id: i32)
-where
-    F: Float,
+fn mark_as_processed<F, K, P>(
+    events: &mut EventArena<F, K, P>,
+    processed: &mut HashSet<i32>,
+    result_events: &[EventId],
+    pos: i32,
+    contour_id: i32,
+) where
+    F: OrderedField,
+    K: Kernel<F>,
 {
     processed.insert(pos);
-    result_events[pos as usize].set_output_contour_id(contour_id);
+    events[result_events[pos as usize]].set_output_contour_id(contour_id);
 }
 
-pub fn connect_edges<F>(sorted_events: &[Rc<SweepEvent<F>>]) -> Vec<Contour<F>>
+pub fn connect_edges<F, K, P>(
+    events: &mut EventArena<F, K, P>,
+    sorted_events: &[EventId],
+) -> Result<Vec<Contour<F, P>>, BooleanOpError>
 where
-    F: Float,
+    F: OrderedField,
+    K: Kernel<F>,
+    P: Clone,
 {
-    let result_events = order_events(sorted_events);
+    connect_edges_with_observer(events, sorted_events, &mut ())
+}
 
-    let iteration_map = precompute_iteration_order(&result_events, |a, b| a.point == b.point, |e| e.is_left());
+/// Variant of `connect_edges` which reports the events of the result to `observer`.
+pub fn connect_edges_with_observer<F, K, P, O>(
+    events: &mut EventArena<F, K, P>,
+    sorted_events: &[EventId],
+    observer: &mut O,
+) -> Result<Vec<Contour<F, P>>, BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: Clone,
+    O: SweepObserver<F, K, P>,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("connect_edges").entered();
 
-    #[cfg(feature = "debug-booleanop")]
-    write_debug_csv(&result_events);
+    let result_events = order_events(events, sorted_events);
 
-    let mut contours: Vec<Contour<F>> = Vec::new();
+    let iteration_map = precompute_iteration_order(
+        &result_events,
+        |&a, &b| events[a].point == events[b].point,
+        |&e| events[e].is_left(),
+    );
+
+    observer.result_events(events, &result_events);
+
+    let mut contours: Vec<Contour<F, P>> = Vec::new();
     let mut processed: HashSet<i32> = HashSet::new();
 
     for i in 0..(result_events.len() as i32) {
@@ -248,12 +350,16 @@ where
         }
 
         let contour_id = contours.len() as i32;
-        let mut contour = Contour::initialize_from_context(&result_events[i as usize], &mut contours, contour_id);
+        let mut contour =
+            Contour::initialize_from_context(events, result_events[i as usize], &mut contours, contour_id)?;
 
         let mut pos = i;
 
-        let initial = result_events[pos as usize].point;
+        let initial = events[result_events[pos as usize]].point;
         contour.points.push(initial);
+        contour
+            .payloads
+            .push(events[result_events[pos as usize]].payload.clone());
 
         loop {
             // Loop clarifications:
@@ -265,13 +371,17 @@ where
             // - The contour is extended after following a segment.
             // - Hitting pos == orig_pos after search (B) indicates no continuation and
             //   terminates the loop.
-            mark_as_processed(&mut processed, &result_events, pos, contour_id);
+            mark_as_processed(events, &mut processed, &result_events, pos, contour_id);
 
             // pos advancement (A)
-            pos = result_events[pos as usize].get_other_pos();
+            pos = events[result_events[pos as usize]].get_other_pos();
 
-            mark_as_processed(&mut processed, &result_events, pos, contour_id);
-            contour.points.push(result_events[pos as usize].point);
+            mark_as_processed(events, &mut processed, &result_events, pos, contour_id);
+            contour.points.push(events[result_events[pos as usize]].point);
+            contour
+                .payloads
+                .push(events[result_events[pos as usize]].payload.clone());
+            contour.sources.push(events[result_events[pos as usize]].get_source());
 
             // pos advancement (B)
             let next_pos_opt = get_next_pos(pos, &processed, &iteration_map);
@@ -287,53 +397,23 @@ where
             // Optional: Terminate contours early (to avoid overly long contours that
             // may mix clockwise and counter-clockwise winding rules, which can be more
             // difficult to handle in some use cases).
-            if result_events[pos as usize].point == initial {
+            if events[result_events[pos as usize]].point == initial {
                 break;
             }
         }
 
-        // This assert should be possible once the first stage of the algorithm is robust.
-        // debug_assert_eq!(contour.points.first(), contour.points.last());
+        if contour.points.first() != contour.points.last() {
+            return Err(BooleanOpError::ContourNotClosed);
+        }
+        // The closing point may be taken from another event, but like the point, its payload
+        // repeats the first one.
+        let last = contour.payloads.len() - 1;
+        contour.payloads[last] = contour.payloads[0].clone();
 
         contours.push(contour);
     }
 
-    contours
-}
-
-// Debug csv output generator
-#[cfg(feature = "debug-booleanop")]
-use std::fs::File;
-#[cfg(feature = "debug-booleanop")]
-use std::io::Write;
-
-#[cfg(feature = "debug-booleanop")]
-fn write_debug_csv<F>(events: &[Rc<SweepEvent<F>>])
-where
-    F: Float,
-{
-    let mut writer = File::create("debug.csv").unwrap();
-    writeln!(
-        &mut writer,
-        "index;x;y;other_x;other_y;lr;result_transition;in_out;other_in_out;is_subject;is_exterior_ring;prev_in_result"
-    )
-    .expect("Failed to write to file");
-    for (i, evt) in events.iter().enumerate() {
-        writeln!(&mut writer, "{i};{x:?};{y:?};{other_x:?};{other_y:?};{lr};{transition:?};{in_out};{other_in_out};{subject};{exterior_ring};{prev_in_result:?}",
-            i=i,
-            x=evt.point.x,
-            y=evt.point.y,
-            other_x=evt.get_other_event().unwrap().point.x,
-            other_y=evt.get_other_event().unwrap().point.y,
-            lr=if evt.is_left() { "L" } else { "R" },
-            transition=evt.get_result_transition(),
-            in_out=evt.is_in_out(),
-            other_in_out=evt.is_other_in_out(),
-            subject=evt.is_subject,
-            exterior_ring=evt.is_exterior_ring,
-            prev_in_result=evt.get_prev_in_result().map(|o| format!("{:?}", o.point)),
-        ).expect("Failed to write to file");
-    }
+    Ok(contours)
 }
 
 #[cfg(test)]
@@ -406,3 +486,96 @@ mod test_precompute_iteration_order {
         );
     }
 }
+
+#[cfg(test)]
+mod test_order_events {
+    use super::super::fill_queue::fill_queue;
+    use super::super::subdivide_segments::subdivide;
+    use super::super::{empty_bounding_box, FillRules, Operation};
+    use super::*;
+    use geo_types::{LineString, Polygon};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::cmp::Ordering;
+
+    /// The bubble sort `order_events` used originally, as a reference for the ordering.
+    fn bubble_sorted(events: &EventArena<f64>, mut result_events: Vec<EventId>) -> Vec<EventId> {
+        let mut sorted = false;
+        while !sorted {
+            sorted = true;
+            for i in 1..result_events.len() {
+                if events.cmp_events(result_events[i - 1], result_events[i]) == Ordering::Less {
+                    result_events.swap(i - 1, i);
+                    sorted = false;
+                }
+            }
+        }
+        result_events
+    }
+
+    fn square(x: f64, y: f64, size: f64) -> Polygon<f64> {
+        Polygon::new(
+            LineString::from(vec![(x, y), (x + size, y), (x + size, y + size), (x, y + size), (x, y)]),
+            vec![],
+        )
+    }
+
+    fn check_same_order_as_bubble_sort(subject: &[Polygon<f64>], clipping: &[Polygon<f64>], operation: Operation) {
+        let mut sbbox = empty_bounding_box();
+        let mut cbbox = sbbox;
+        let mut events = EventArena::new();
+        let mut queue = fill_queue(subject, clipping, &mut sbbox, &mut cbbox, operation, &mut events);
+        let sorted_events =
+            subdivide(&mut events, &mut queue, &sbbox, &cbbox, operation, FillRules::default()).unwrap();
+
+        let result_events = order_events(&mut events, &sorted_events);
+
+        assert!(!result_events.is_empty());
+        assert_eq!(result_events, bubble_sorted(&events, result_events.clone()));
+
+        // Reversed input exceeds the budget of the insertion sort.
+        let mut reversed: Vec<_> = result_events.iter().rev().copied().collect();
+        let expected = bubble_sorted(&events, reversed.clone());
+        sort_events(&events, &mut reversed);
+        assert_eq!(reversed, expected);
+    }
+
+    #[test]
+    fn test_order_events_of_overlapping_grids() {
+        // Shifted grids have collinear overlapping edges and many events sharing a point.
+        let subject: Vec<_> = (0..36).map(|i| square((i % 6) as f64, (i / 6) as f64, 1.)).collect();
+        let clipping: Vec<_> = (0..36)
+            .map(|i| square((i % 6) as f64 + 0.5, (i / 6) as f64, 1.))
+            .collect();
+
+        for operation in [
+            Operation::Intersection,
+            Operation::Union,
+            Operation::Difference,
+            Operation::Xor,
+        ] {
+            check_same_order_as_bubble_sort(&subject, &clipping, operation);
+        }
+    }
+
+    #[test]
+    fn test_order_events_of_random_squares() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut random_squares = |n| -> Vec<_> {
+            (0..n)
+                .map(|_| {
+                    square(
+                        rng.gen_range(0..20) as f64,
+                        rng.gen_range(0..20) as f64,
+                        rng.gen_range(1..5) as f64,
+                    )
+                })
+                .collect()
+        };
+
+        for _ in 0..10 {
+            let (subject, clipping) = (random_squares(20), random_squares(20));
+            check_same_order_as_bubble_sort(&subject, &clipping, Operation::Xor);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use super::boolean_all::SharedSweep;
+use super::helper::Float;
+use super::{trivial_result, BooleanOpError, FillRules, Operation};
+use geo_types::{CoordNum, MultiPolygon, Polygon};
+
+/// Division of two operands A and B into the parts covered by only one or by both of them.
+///
+/// All parts are computed from the same sweep, so they tile A ∪ B without gaps or overlaps,
+/// and share identical vertices along their common boundaries.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Division<F>
+where
+    F: CoordNum,
+{
+    /// A ∖ B, i.e., the difference.
+    pub subject_only: MultiPolygon<F>,
+    /// A ∩ B, i.e., the intersection.
+    pub both: MultiPolygon<F>,
+    /// B ∖ A, i.e., the difference with swapped operands.
+    pub clipping_only: MultiPolygon<F>,
+}
+
+pub(crate) fn divide_operation<F>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    fill_rules: FillRules,
+) -> Result<Division<F>, BooleanOpError>
+where
+    F: Float,
+{
+    let reverse_difference = Operation::Custom(|subject_in, clipping_in| clipping_in && !subject_in);
+
+    match SharedSweep::new(subject, clipping, fill_rules)? {
+        Some(mut sweep) => Ok(Division {
+            subject_only: sweep.result_of(Operation::Difference)?,
+            both: sweep.result_of(Operation::Intersection)?,
+            clipping_only: sweep.result_of(reverse_difference)?,
+        }),
+        None => Ok(Division {
+            subject_only: trivial_result(subject, clipping, Operation::Difference),
+            both: trivial_result(subject, clipping, Operation::Intersection),
+            clipping_only: trivial_result(subject, clipping, reverse_difference),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo_types::polygon;
+
+    #[test]
+    fn test_overlapping_squares() {
+        let a = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)];
+        let b = polygon![(x: 1., y: 1.), (x: 3., y: 1.), (x: 3., y: 3.), (x: 1., y: 3.), (x: 1., y: 1.)];
+
+        let division = divide_operation(&[a], &[b], FillRules::default()).unwrap();
+
+        assert_eq!(
+            division.both,
+            MultiPolygon(vec![polygon![
+                (x: 1., y: 1.),
+                (x: 2., y: 1.),
+                (x: 2., y: 2.),
+                (x: 1., y: 2.),
+                (x: 1., y: 1.),
+            ]])
+        );
+        assert_eq!(
+            division.subject_only,
+            MultiPolygon(vec![polygon![
+                (x: 0., y: 0.),
+                (x: 2., y: 0.),
+                (x: 2., y: 1.),
+                (x: 1., y: 1.),
+                (x: 1., y: 2.),
+                (x: 0., y: 2.),
+                (x: 0., y: 0.),
+            ]])
+        );
+        assert_eq!(
+            division.clipping_only,
+            MultiPolygon(vec![polygon![
+                (x: 1., y: 2.),
+                (x: 2., y: 2.),
+                (x: 2., y: 1.),
+                (x: 3., y: 1.),
+                (x: 3., y: 3.),
+                (x: 1., y: 3.),
+                (x: 1., y: 2.),
+            ]])
+        );
+    }
+}
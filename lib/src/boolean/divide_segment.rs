@@ -1,22 +1,33 @@
-use super::helper::Float;
-use super::sweep_event::SweepEvent;
+use super::error::BooleanOpError;
+use super::event_queue::EventQueue;
+use super::helper::OrderedField;
+use super::kernel::Kernel;
+use super::payload::{PayloadSegment, VertexPayload};
+use super::sweep_event::{EventArena, EventId, SweepEvent};
 use geo_types::Coord;
-use std::collections::BinaryHeap;
-use std::rc::Rc;
 
-#[cfg(feature = "debug-booleanop")]
-use super::sweep_event::JsonDebug;
-
-pub fn divide_segment<F>(se_l: &Rc<SweepEvent<F>>, inter: Coord<F>, queue: &mut BinaryHeap<Rc<SweepEvent<F>>>)
+/// Divides the segment of the left event `se_l` at `inter`, because of the segment of the
+/// event `crossing`. The payload of the new vertex is merged from the payloads of both
+/// segments, see `VertexPayload::merge`.
+pub fn divide_segment<F, K, P>(
+    events: &mut EventArena<F, K, P>,
+    se_l: EventId,
+    inter: Coord<F>,
+    crossing: EventId,
+    queue: &mut EventQueue,
+) -> Result<(), BooleanOpError>
 where
-    F: Float,
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
 {
-    debug_assert!(se_l.is_left());
+    if !events[se_l].is_left() {
+        return Err(BooleanOpError::InvalidEventOrder);
+    }
 
-    let se_r = match se_l.get_other_event() {
-        Some(se) => se,
-        None => return,
-    };
+    let se_r = events[se_l]
+        .get_other_event()
+        .ok_or(BooleanOpError::MissingOtherEvent)?;
 
     // The idea is to divide the segment based on the given `inter` coordinate as follows:
     //
@@ -45,93 +56,121 @@ where
 
     // Prevent from corner case 1
     let mut inter = inter;
-    if inter.x == se_l.point.x && inter.y < se_l.point.y {
-        inter.x = inter.x.nextafter(true);
+    if inter.x == events[se_l].point.x && inter.y < events[se_l].point.y {
+        inter.x = inter.x.next_up();
     }
 
-    let r = SweepEvent::new_rc(
-        se_l.contour_id,
-        inter,
-        false,
-        Rc::downgrade(se_l),
-        se_l.is_subject,
-        true,
-    );
-    let l = SweepEvent::new_rc(
-        se_l.contour_id,
+    let line = events.line(se_l).ok_or(BooleanOpError::MissingOtherEvent)?;
+    events[se_l].set_line(line);
+    events[se_r].set_line(line);
+
+    let payload = P::merge(
         inter,
-        true,
-        Rc::downgrade(&se_r),
-        se_l.is_subject,
-        true,
+        &payload_segment(events, se_l)?,
+        &payload_segment(events, crossing)?,
     );
 
+    let (contour_id, is_subject) = (events[se_l].contour_id, events[se_l].is_subject);
+    let mut r = SweepEvent::new(contour_id, inter, false, Some(se_l), is_subject, true).with_payload(payload.clone());
+    let mut l = SweepEvent::new(contour_id, inter, true, Some(se_r), is_subject, true).with_payload(payload);
+
+    r.set_source(events[se_l].get_source());
+    l.set_source(events[se_l].get_source());
+    r.set_line(line);
+    l.set_line(line);
+    r.set_winding(events[se_l].get_winding());
+    l.set_winding(events[se_l].get_winding());
+
+    let r = events.push(r);
+    let l = events.push(l);
+
     // Corner case 1 should be impossible
-    debug_assert!(se_l.is_before(&r));
+    if !events.is_before(se_l, r) {
+        return Err(BooleanOpError::InvalidEventOrder);
+    }
     // Corner case 2 can be accounted for by swapping l / se_r
-    if !l.is_before(&se_r) {
-        se_r.set_left(true);
-        l.set_left(false);
+    if !events.is_before(l, se_r) {
+        events[se_r].set_left(true);
+        events[l].set_left(false);
     }
 
-    se_l.set_other_event(&r);
-    se_r.set_other_event(&l);
+    events[se_l].set_other_event(r);
+    events[se_r].set_other_event(l);
 
-    queue.push(l);
-    queue.push(r);
+    queue.push(events, l);
+    queue.push(events, r);
 
-    #[cfg(feature = "debug-booleanop")]
-    {
-        println!("{{\"divide_segment_se_l\": {}}}", se_l.to_json_debug());
-        println!("{{\"divide_segment_se_r\": {}}}", se_r.to_json_debug());
-    }
+    #[cfg(feature = "tracing")]
+    tracing::trace!(se_l, se_r, x = ?inter.x, y = ?inter.y, "split segment");
+
+    Ok(())
+}
+
+/// The segment of `event` in the form passed to `VertexPayload::merge`.
+fn payload_segment<F, K, P>(
+    events: &EventArena<F, K, P>,
+    event: EventId,
+) -> Result<PayloadSegment<'_, F, P>, BooleanOpError>
+where
+    F: OrderedField,
+{
+    let other = events.other(event).ok_or(BooleanOpError::MissingOtherEvent)?;
+    let (left, right) = if events[event].is_left() {
+        (&events[event], other)
+    } else {
+        (other, &events[event])
+    };
+    Ok(PayloadSegment {
+        start: left.point,
+        end: right.point,
+        start_payload: &left.payload,
+        end_payload: &right.payload,
+        source: left.get_source(),
+    })
 }
 
 #[cfg(test)]
 mod test {
     use super::super::segment_intersection::{intersection, LineIntersection};
-    use super::super::sweep_event::SweepEvent;
     use super::*;
     use geo_types::Coord;
-    use std::collections::BinaryHeap;
-    use std::rc::{Rc, Weak};
 
     fn make_simple(
+        events: &mut EventArena<f64>,
         x: f64,
         y: f64,
         other_x: f64,
         other_y: f64,
         is_subject: bool,
-    ) -> (Rc<SweepEvent<f64>>, Rc<SweepEvent<f64>>) {
-        let other = SweepEvent::new_rc(
-            0,
-            Coord { x: other_x, y: other_y },
-            false,
-            Weak::new(),
-            is_subject,
-            true,
-        );
-        let event = SweepEvent::new_rc(0, Coord { x, y }, true, Rc::downgrade(&other), is_subject, true);
-
-        (event, other)
+    ) -> (EventId, EventId) {
+        events.push_pair(
+            SweepEvent::new(0, Coord { x, y }, true, None, is_subject, true),
+            SweepEvent::new(0, Coord { x: other_x, y: other_y }, false, None, is_subject, true),
+        )
     }
 
     #[test]
     fn divide_segments() {
-        let (se1, other1) = make_simple(0.0, 0.0, 5.0, 5.0, true);
-        let (se2, other2) = make_simple(0.0, 5.0, 5.0, 0.0, false);
-        let mut queue = BinaryHeap::new();
-
-        queue.push(se1.clone());
-        queue.push(se2.clone());
-
-        let inter = match intersection(se1.point, other1.point, se2.point, other2.point) {
+        let mut events = EventArena::new();
+        let (se1, other1) = make_simple(&mut events, 0.0, 0.0, 5.0, 5.0, true);
+        let (se2, other2) = make_simple(&mut events, 0.0, 5.0, 5.0, 0.0, false);
+        let mut queue = EventQueue::new();
+
+        queue.push(&events, se1);
+        queue.push(&events, se2);
+
+        let inter = match intersection(
+            events[se1].point,
+            events[other1].point,
+            events[se2].point,
+            events[other2].point,
+        ) {
             LineIntersection::Point(p) => p,
             _ => panic!("Not a point intersection"),
         };
 
-        divide_segment(&se1, inter, &mut queue);
-        divide_segment(&se2, inter, &mut queue);
+        divide_segment(&mut events, se1, inter, se2, &mut queue).unwrap();
+        divide_segment(&mut events, se2, inter, se1, &mut queue).unwrap();
 
         assert_eq!(queue.len(), 6);
     }
@@ -0,0 +1,60 @@
+use std::error::Error;
+use std::fmt;
+
+/// Reasons why a boolean operation could not produce a result.
+///
+/// Most variants correspond to internal invariants of the sweep line algorithm
+/// which can be violated by numerically degenerate input. They are reported
+/// instead of panicking, so that callers processing untrusted data can skip or
+/// repair the offending geometry.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum BooleanOpError {
+    /// An input coordinate is NaN or infinite.
+    NonFiniteCoordinate,
+    /// A sweep event has lost the event at the other end of its segment.
+    MissingOtherEvent,
+    /// A right event was passed where the left event of a segment is required.
+    UnexpectedRightEvent,
+    /// A segment that is about to leave the sweep line is not part of it.
+    SweepLineInconsistency,
+    /// Subdividing a segment would produce sweep events in the wrong order.
+    InvalidEventOrder,
+    /// A result contour refers to a lower contour which does not exist.
+    InvalidContourNesting,
+    /// Connecting the result edges did not yield a closed contour.
+    ContourNotClosed,
+    /// A custom operation includes the region outside of both operands in its result.
+    UnboundedResult,
+    /// The scale of a fixed precision model is not a positive finite number.
+    InvalidPrecisionModel,
+    /// An integer coordinate exceeds `MAX_INTEGER_COORD` in magnitude.
+    CoordinateOutOfRange,
+    /// The vertex values of an operand do not match the points of its rings.
+    MismatchedVertexValues,
+    /// The sweep divided the segments of the same pair of input edges more often than their
+    /// intersection can, i.e., numerical issues keep dividing the same segments.
+    EventLimitExceeded,
+}
+
+impl fmt::Display for BooleanOpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            BooleanOpError::NonFiniteCoordinate => "input contains a non-finite coordinate",
+            BooleanOpError::MissingOtherEvent => "sweep event without corresponding other event",
+            BooleanOpError::UnexpectedRightEvent => "right event passed instead of a left event",
+            BooleanOpError::SweepLineInconsistency => "sweep line misses event to be removed",
+            BooleanOpError::InvalidEventOrder => "segment subdivision violates the event order",
+            BooleanOpError::InvalidContourNesting => "result contour refers to an invalid lower contour",
+            BooleanOpError::ContourNotClosed => "failed to close result contour",
+            BooleanOpError::UnboundedResult => "operation yields an unbounded result",
+            BooleanOpError::InvalidPrecisionModel => "precision model has an invalid scale",
+            BooleanOpError::CoordinateOutOfRange => "input contains an integer coordinate out of range",
+            BooleanOpError::MismatchedVertexValues => "vertex values do not match the input points",
+            BooleanOpError::EventLimitExceeded => "sweep keeps dividing the segments of the same input edges",
+        };
+        f.write_str(message)
+    }
+}
+
+impl Error for BooleanOpError {}
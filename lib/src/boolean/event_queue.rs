@@ -0,0 +1,114 @@
+use super::helper::OrderedField;
+use super::kernel::Kernel;
+use super::sweep_event::{EventArena, EventId};
+use std::cmp::Ordering;
+
+/// Priority queue of sweep events, which pops the event to be processed next first.
+///
+/// The queue only stores event ids, the order is determined by the events in the arena.
+/// Since the arena is modified while the events are queued, it has to be passed to every
+/// operation. The sifting follows `std::collections::BinaryHeap`, so the events are popped
+/// in the same order as from a binary heap of the events themselves.
+#[derive(Clone, Debug, Default)]
+pub struct EventQueue {
+    heap: Vec<EventId>,
+}
+
+impl EventQueue {
+    pub fn new() -> EventQueue {
+        EventQueue { heap: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The event to be processed next.
+    pub fn peek(&self) -> Option<EventId> {
+        self.heap.first().copied()
+    }
+
+    pub fn push<F, K, P>(&mut self, events: &EventArena<F, K, P>, id: EventId)
+    where
+        F: OrderedField,
+        K: Kernel<F>,
+    {
+        self.heap.push(id);
+        self.sift_up(events, 0, self.heap.len() - 1);
+    }
+
+    pub fn pop<F, K, P>(&mut self, events: &EventArena<F, K, P>) -> Option<EventId>
+    where
+        F: OrderedField,
+        K: Kernel<F>,
+    {
+        self.heap.pop().map(|mut id| {
+            if !self.heap.is_empty() {
+                std::mem::swap(&mut id, &mut self.heap[0]);
+                self.sift_down_to_bottom(events, 0);
+            }
+            id
+        })
+    }
+
+    fn is_less_or_equal<F, K, P>(events: &EventArena<F, K, P>, a: EventId, b: EventId) -> bool
+    where
+        F: OrderedField,
+        K: Kernel<F>,
+    {
+        events.cmp_events(a, b) != Ordering::Greater
+    }
+
+    fn sift_up<F, K, P>(&mut self, events: &EventArena<F, K, P>, start: usize, pos: usize) -> usize
+    where
+        F: OrderedField,
+        K: Kernel<F>,
+    {
+        let id = self.heap[pos];
+        let mut pos = pos;
+        while pos > start {
+            let parent = (pos - 1) / 2;
+            if Self::is_less_or_equal(events, id, self.heap[parent]) {
+                break;
+            }
+            self.heap[pos] = self.heap[parent];
+            pos = parent;
+        }
+        self.heap[pos] = id;
+        pos
+    }
+
+    /// Moves the element at `pos` all the way down to a leaf, and then sifts it up again,
+    /// which requires fewer comparisons for elements that belong to the bottom anyway.
+    fn sift_down_to_bottom<F, K, P>(&mut self, events: &EventArena<F, K, P>, pos: usize)
+    where
+        F: OrderedField,
+        K: Kernel<F>,
+    {
+        let end = self.heap.len();
+        let start = pos;
+        let id = self.heap[pos];
+        let mut pos = pos;
+        let mut child = 2 * pos + 1;
+
+        while child <= end.saturating_sub(2) {
+            if Self::is_less_or_equal(events, self.heap[child], self.heap[child + 1]) {
+                child += 1;
+            }
+            self.heap[pos] = self.heap[child];
+            pos = child;
+            child = 2 * pos + 1;
+        }
+
+        if child == end - 1 {
+            self.heap[pos] = self.heap[child];
+            pos = child;
+        }
+        self.heap[pos] = id;
+        self.sift_up(events, start, pos);
+    }
+}
@@ -0,0 +1,130 @@
+//! Exact arithmetic on floating point expansions, following Shewchuk, "Adaptive Precision
+//! Floating-Point Arithmetic and Fast Robust Geometric Predicates".
+//!
+//! An expansion represents the exact sum of its components, which are nonoverlapping and
+//! sorted by increasing magnitude. Zero components are eliminated, so the last component
+//! determines the sign and approximates the value. Overflow and underflow are not handled,
+//! i.e., the products of the input values have to be within the range of `f64`.
+
+use std::cmp::Ordering;
+
+/// Exact sum `a + b` as the rounded sum and its rounding error.
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Exact product `a * b` as the rounded product and its rounding error.
+#[inline]
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub(crate) struct Expansion(Vec<f64>);
+
+impl Expansion {
+    pub fn new(value: f64) -> Expansion {
+        Expansion(Some(value).filter(|v| *v != 0.0).into_iter().collect())
+    }
+
+    /// Exact difference `a - b`.
+    pub fn difference(a: f64, b: f64) -> Expansion {
+        let (x, y) = two_sum(a, -b);
+        Expansion([y, x].into_iter().filter(|v| *v != 0.0).collect())
+    }
+
+    pub fn sum(&self, other: &Expansion) -> Expansion {
+        other.0.iter().fold(self.clone(), |sum, &b| sum.grow(b))
+    }
+
+    pub fn sub(&self, other: &Expansion) -> Expansion {
+        self.sum(&other.neg())
+    }
+
+    pub fn neg(&self) -> Expansion {
+        Expansion(self.0.iter().map(|v| -v).collect())
+    }
+
+    pub fn mul(&self, other: &Expansion) -> Expansion {
+        other
+            .0
+            .iter()
+            .fold(Expansion::default(), |product, &b| product.sum(&self.scale(b)))
+    }
+
+    /// Exact product with a single value.
+    pub fn scale(&self, b: f64) -> Expansion {
+        let mut components = Vec::with_capacity(2 * self.0.len());
+        let mut q = 0.0;
+        for (i, &a) in self.0.iter().enumerate() {
+            let (product, product_error) = two_product(a, b);
+            if i == 0 {
+                q = product;
+                components.push(product_error);
+            } else {
+                let (sum, sum_error) = two_sum(q, product_error);
+                components.push(sum_error);
+                let (next_q, error) = two_sum(product, sum);
+                components.push(error);
+                q = next_q;
+            }
+        }
+        components.push(q);
+        components.retain(|v| *v != 0.0);
+        Expansion(components)
+    }
+
+    /// Exact sum with a single value.
+    fn grow(self, b: f64) -> Expansion {
+        let mut components = Vec::with_capacity(self.0.len() + 1);
+        let mut q = b;
+        for a in self.0 {
+            let (sum, error) = two_sum(q, a);
+            if error != 0.0 {
+                components.push(error);
+            }
+            q = sum;
+        }
+        if q != 0.0 {
+            components.push(q);
+        }
+        Expansion(components)
+    }
+
+    pub fn signum(&self) -> Ordering {
+        self.0
+            .last()
+            .map_or(Ordering::Equal, |v| v.partial_cmp(&0.0).unwrap_or(Ordering::Equal))
+    }
+
+    /// Approximation of the value, with a relative error of a few ulps.
+    pub fn estimate(&self) -> f64 {
+        self.0.iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_exact_arithmetic() {
+        let a = Expansion::difference(1.0, 1e-30);
+        assert_eq!(a.estimate(), 1.0);
+        assert_eq!(a.sub(&Expansion::new(1.0)).estimate(), -1e-30);
+
+        // (1 + 2^-60)^2 - 1 - 2^-59 = 2^-120, which vanishes in double precision.
+        let b = Expansion::new(1.0).sum(&Expansion::new(2f64.powi(-60)));
+        let square = b.mul(&b);
+        let rest = square.sub(&Expansion::new(1.0)).sub(&Expansion::new(2f64.powi(-59)));
+        assert_eq!(rest.estimate(), 2f64.powi(-120));
+        assert_eq!(rest.signum(), Ordering::Greater);
+        assert_eq!(rest.sub(&rest).signum(), Ordering::Equal);
+        assert_eq!(square.scale(-3.0).signum(), Ordering::Less);
+    }
+}
@@ -0,0 +1,326 @@
+use super::compute_fields::{is_coincident, set_result_fields};
+use super::connect_edges::connect_edges;
+use super::fill_queue::fill_queue_operands;
+use super::helper::Float;
+use super::observer::SweepObserver;
+use super::subdivide_segments::subdivide_observing;
+use super::sweep_event::{EdgeType, EventArena, EventId, SweepEvent};
+use super::{all_coords_finite, contours_to_multi_polygon, empty_bounding_box, BooleanOpError, FillRule, FillRules};
+use geo_types::{MultiPolygon, Polygon};
+use std::collections::HashMap;
+
+/// Boolean expression over any number of operands, which is evaluated in a single sweep.
+///
+/// In contrast to chaining pairwise operations, no intermediate results are computed, so
+/// the result does not accumulate the rounding errors of several sweeps:
+///
+/// ```
+/// use geo_booleanop::boolean::Expr;
+/// use geo_types::{polygon, MultiPolygon};
+///
+/// let a = MultiPolygon(vec![polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 0.)]]);
+/// let b = MultiPolygon(vec![polygon![(x: 1., y: 0.), (x: 3., y: 0.), (x: 3., y: 2.), (x: 1., y: 0.)]]);
+/// let c = MultiPolygon(vec![polygon![(x: 0., y: 1.), (x: 3., y: 1.), (x: 3., y: 3.), (x: 0., y: 1.)]]);
+///
+/// let result = Expr::union(Expr::operand(&a), Expr::operand(&b)).difference(Expr::operand(&c)).evaluate();
+/// ```
+#[derive(Clone, Debug)]
+pub enum Expr<'a, F>
+where
+    F: Float,
+{
+    /// Input polygons, interpreted with the given fill rule.
+    Operand(&'a [Polygon<F>], FillRule),
+    Intersection(Box<Expr<'a, F>>, Box<Expr<'a, F>>),
+    Union(Box<Expr<'a, F>>, Box<Expr<'a, F>>),
+    Difference(Box<Expr<'a, F>>, Box<Expr<'a, F>>),
+    Xor(Box<Expr<'a, F>>, Box<Expr<'a, F>>),
+}
+
+impl<'a, F> Expr<'a, F>
+where
+    F: Float,
+{
+    /// Operand consisting of the polygons, interpreted with the even-odd rule.
+    pub fn operand(polygons: &'a MultiPolygon<F>) -> Expr<'a, F> {
+        Expr::Operand(polygons.0.as_slice(), FillRule::EvenOdd)
+    }
+
+    /// Operand consisting of a single polygon, interpreted with the even-odd rule.
+    pub fn polygon(polygon: &'a Polygon<F>) -> Expr<'a, F> {
+        Expr::Operand(std::slice::from_ref(polygon), FillRule::EvenOdd)
+    }
+
+    /// Uses the fill rule for all operands of the expression.
+    pub fn with_fill_rule(self, fill_rule: FillRule) -> Expr<'a, F> {
+        let with_fill_rule = |expr: Box<Expr<'a, F>>| Box::new(expr.with_fill_rule(fill_rule));
+        match self {
+            Expr::Operand(polygons, _) => Expr::Operand(polygons, fill_rule),
+            Expr::Intersection(a, b) => Expr::Intersection(with_fill_rule(a), with_fill_rule(b)),
+            Expr::Union(a, b) => Expr::Union(with_fill_rule(a), with_fill_rule(b)),
+            Expr::Difference(a, b) => Expr::Difference(with_fill_rule(a), with_fill_rule(b)),
+            Expr::Xor(a, b) => Expr::Xor(with_fill_rule(a), with_fill_rule(b)),
+        }
+    }
+
+    pub fn intersection(self, rhs: Expr<'a, F>) -> Expr<'a, F> {
+        Expr::Intersection(Box::new(self), Box::new(rhs))
+    }
+
+    pub fn union(self, rhs: Expr<'a, F>) -> Expr<'a, F> {
+        Expr::Union(Box::new(self), Box::new(rhs))
+    }
+
+    pub fn difference(self, rhs: Expr<'a, F>) -> Expr<'a, F> {
+        Expr::Difference(Box::new(self), Box::new(rhs))
+    }
+
+    pub fn xor(self, rhs: Expr<'a, F>) -> Expr<'a, F> {
+        Expr::Xor(Box::new(self), Box::new(rhs))
+    }
+
+    /// Computes the result of the expression, reporting degenerate input or internal
+    /// inconsistencies of the sweep as `BooleanOpError` instead of panicking.
+    pub fn try_evaluate(&self) -> Result<MultiPolygon<F>, BooleanOpError> {
+        let mut operands = Vec::new();
+        self.collect_operands(&mut operands);
+
+        if !operands.iter().all(|(polygons, _)| all_coords_finite(polygons)) {
+            return Err(BooleanOpError::NonFiniteCoordinate);
+        }
+
+        let mut bbox = empty_bounding_box();
+        let polygons: Vec<&[Polygon<F>]> = operands.iter().map(|(polygons, _)| *polygons).collect();
+        let fill_rules: Vec<FillRule> = operands.iter().map(|(_, fill_rule)| *fill_rule).collect();
+        let mut events = EventArena::new();
+        let mut event_queue = fill_queue_operands(&polygons, &mut bbox, &mut events);
+
+        // All events belong to the subject, so the fields computed by the sweep itself only
+        // describe the overlay of all operands. They are overridden by the classification
+        // based on the winding numbers of the individual operands, which is applied after the
+        // sweep in the order of the computations, like `FieldsRecord::replay`.
+        let mut classifier = ExprClassifier {
+            expr: self,
+            fill_rules,
+            windings: HashMap::new(),
+            results: Vec::new(),
+        };
+        let sorted_events = subdivide_observing(
+            &mut events,
+            &mut event_queue,
+            &bbox,
+            &bbox,
+            FillRules::default(),
+            &mut classifier,
+        )?;
+        for result in &classifier.results {
+            set_result_fields(
+                &mut events,
+                result.event,
+                result.prev,
+                result.in_result,
+                result.is_in_above,
+            );
+        }
+
+        let contours = connect_edges(&mut events, &sorted_events)?;
+        Ok(contours_to_multi_polygon(&contours))
+    }
+
+    /// Infallible variant of `try_evaluate`.
+    ///
+    /// Panics if the evaluation fails, e.g. because an input coordinate is not finite.
+    pub fn evaluate(&self) -> MultiPolygon<F> {
+        match self.try_evaluate() {
+            Ok(result) => result,
+            Err(err) => panic!("Expression evaluation failed: {}", err),
+        }
+    }
+
+    /// Collects the operands in the order of their indices.
+    fn collect_operands(&self, operands: &mut Vec<(&'a [Polygon<F>], FillRule)>) {
+        match self {
+            Expr::Operand(polygons, fill_rule) => operands.push((polygons, *fill_rule)),
+            Expr::Intersection(a, b) | Expr::Union(a, b) | Expr::Difference(a, b) | Expr::Xor(a, b) => {
+                a.collect_operands(operands);
+                b.collect_operands(operands);
+            }
+        }
+    }
+
+    fn is_inside_with_windings(&self, fill_rules: &[FillRule], windings: &[i32]) -> bool {
+        let inside: Vec<bool> = fill_rules
+            .iter()
+            .zip(windings)
+            .map(|(fill_rule, winding)| fill_rule.is_inside(*winding))
+            .collect();
+        self.is_inside(&inside, &mut 0)
+    }
+
+    /// Evaluates the expression for a region, given whether it is inside of each operand.
+    /// `next_operand` is the index of the first operand of the (sub-)expression.
+    fn is_inside(&self, inside: &[bool], next_operand: &mut usize) -> bool {
+        match self {
+            Expr::Operand(..) => {
+                *next_operand += 1;
+                inside[*next_operand - 1]
+            }
+            Expr::Intersection(a, b) | Expr::Union(a, b) | Expr::Difference(a, b) | Expr::Xor(a, b) => {
+                // Both sides must be evaluated to keep the operand indices in sync.
+                let a_inside = a.is_inside(inside, next_operand);
+                let b_inside = b.is_inside(inside, next_operand);
+                match self {
+                    Expr::Intersection(..) => a_inside && b_inside,
+                    Expr::Union(..) => a_inside || b_inside,
+                    Expr::Difference(..) => a_inside && !b_inside,
+                    _ => a_inside != b_inside,
+                }
+            }
+        }
+    }
+}
+
+/// Observer classifying the events for the expression whenever the sweep computes their
+/// fields.
+struct ExprClassifier<'e, 'a, F>
+where
+    F: Float,
+{
+    expr: &'e Expr<'a, F>,
+    fill_rules: Vec<FillRule>,
+    windings: HashMap<EventId, OperandWindings>,
+    results: Vec<ResultFields>,
+}
+
+/// Classification of an event computed from the segment `prev` below it.
+struct ResultFields {
+    event: EventId,
+    prev: Option<EventId>,
+    in_result: bool,
+    is_in_above: bool,
+}
+
+impl<F> SweepObserver<F> for ExprClassifier<'_, '_, F>
+where
+    F: Float,
+{
+    fn compute_fields(&mut self, events: &EventArena<F>, event: EventId, prev: Option<EventId>) {
+        let event_windings = OperandWindings::of(events, event, prev, &self.windings, self.fill_rules.len());
+        let is_in_below = self
+            .expr
+            .is_inside_with_windings(&self.fill_rules, &event_windings.group_below);
+        let is_in_above = self
+            .expr
+            .is_inside_with_windings(&self.fill_rules, &event_windings.above(&events[event]));
+        self.results.push(ResultFields {
+            event,
+            prev,
+            in_result: events[event].get_edge_type() != EdgeType::NonContributing && is_in_below != is_in_above,
+            is_in_above,
+        });
+        self.windings.insert(event, event_windings);
+    }
+}
+
+/// Winding numbers of all operands below a segment.
+struct OperandWindings {
+    below: Vec<i32>,
+    /// Winding numbers below the group of coincident segments the segment belongs to.
+    group_below: Vec<i32>,
+}
+
+impl OperandWindings {
+    /// Derives the winding numbers from the segment below, analogous to `compute_fields`.
+    fn of<F>(
+        events: &EventArena<F>,
+        event: EventId,
+        maybe_prev: Option<EventId>,
+        windings: &HashMap<EventId, OperandWindings>,
+        num_operands: usize,
+    ) -> OperandWindings
+    where
+        F: Float,
+    {
+        let prev_windings = maybe_prev.and_then(|prev| Some((prev, windings.get(&prev)?)));
+        match prev_windings {
+            Some((prev, prev_windings)) => {
+                let coincident = is_coincident(events, event, prev);
+                // The winding of a vertical `prev` does not apply to segments starting on it.
+                let below = if events.is_vertical(prev) && !coincident {
+                    prev_windings.below.clone()
+                } else {
+                    prev_windings.above(&events[prev])
+                };
+                let group_below = if coincident {
+                    prev_windings.group_below.clone()
+                } else {
+                    below.clone()
+                };
+                OperandWindings { below, group_below }
+            }
+            None => OperandWindings {
+                below: vec![0; num_operands],
+                group_below: vec![0; num_operands],
+            },
+        }
+    }
+
+    fn above<F>(&self, event: &SweepEvent<F>) -> Vec<i32>
+    where
+        F: Float,
+    {
+        let mut above = self.below.clone();
+        above[event.get_operand()] += event.get_winding();
+        above
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo_types::polygon;
+
+    #[test]
+    fn test_is_inside() {
+        let a: MultiPolygon<f64> = MultiPolygon(vec![]);
+        let expr = Expr::operand(&a)
+            .union(Expr::operand(&a))
+            .difference(Expr::operand(&a).intersection(Expr::operand(&a)));
+
+        assert!(expr.is_inside(&[true, false, true, false], &mut 0));
+        assert!(!expr.is_inside(&[false, true, true, true], &mut 0));
+        assert!(!expr.is_inside(&[false, false, true, true], &mut 0));
+    }
+
+    #[test]
+    fn test_three_operands() {
+        let a = polygon![(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.), (x: 0., y: 0.)];
+        let b = polygon![(x: 2., y: 0.), (x: 6., y: 0.), (x: 6., y: 4.), (x: 2., y: 4.), (x: 2., y: 0.)];
+        let c = polygon![(x: 1., y: 1.), (x: 5., y: 1.), (x: 5., y: 3.), (x: 1., y: 3.), (x: 1., y: 1.)];
+
+        let result = Expr::polygon(&a)
+            .intersection(Expr::polygon(&b))
+            .difference(Expr::polygon(&c))
+            .evaluate();
+
+        assert_eq!(
+            result,
+            MultiPolygon(vec![
+                polygon![
+                    (x: 2., y: 0.),
+                    (x: 4., y: 0.),
+                    (x: 4., y: 1.),
+                    (x: 2., y: 1.),
+                    (x: 2., y: 0.),
+                ],
+                polygon![
+                    (x: 2., y: 3.),
+                    (x: 4., y: 3.),
+                    (x: 4., y: 4.),
+                    (x: 2., y: 4.),
+                    (x: 2., y: 3.),
+                ]
+            ])
+        );
+    }
+}
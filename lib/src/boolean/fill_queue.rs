@@ -1,173 +1,325 @@
-use super::helper::Float;
-use geo_types::{LineString, Polygon};
-use std::collections::BinaryHeap;
-use std::rc::{Rc, Weak};
+use super::helper::OrderedField;
+use super::kernel::Kernel;
+use geo_types::{Line, LineString, Polygon};
 
-use super::helper::BoundingBox;
-use super::sweep_event::SweepEvent;
+use super::event_queue::EventQueue;
+use super::helper::{partial_max, partial_min, BoundingBox};
+use super::provenance::EdgeSource;
+use super::sweep_event::{EventArena, SweepEvent};
 use super::Operation;
 
-pub fn fill_queue<F>(
+pub fn fill_queue<F, K>(
     subject: &[Polygon<F>],
     clipping: &[Polygon<F>],
     sbbox: &mut BoundingBox<F>,
     cbbox: &mut BoundingBox<F>,
     operation: Operation,
-) -> BinaryHeap<Rc<SweepEvent<F>>>
+    events: &mut EventArena<F, K>,
+) -> EventQueue
 where
-    F: Float,
+    F: OrderedField,
+    K: Kernel<F>,
 {
-    let mut event_queue: BinaryHeap<Rc<SweepEvent<F>>> = BinaryHeap::new();
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("fill_queue").entered();
+
+    let mut event_queue = EventQueue::new();
     let mut contour_id = 0u32;
 
-    for polygon in subject {
+    for (polygon_index, polygon) in subject.iter().enumerate() {
         contour_id += 1;
-        process_polygon(polygon.exterior(), true, contour_id, &mut event_queue, sbbox, true);
-        for interior in polygon.interiors() {
-            process_polygon(interior, true, contour_id, &mut event_queue, sbbox, false);
-        }
+        let source = EdgeSource {
+            operand: 0,
+            polygon: polygon_index,
+            ..EdgeSource::default()
+        };
+        process_rings(polygon, true, source, contour_id, events, &mut event_queue, sbbox, true);
     }
 
-    for polygon in clipping {
+    for (polygon_index, polygon) in clipping.iter().enumerate() {
         let exterior = operation != Operation::Difference;
         if exterior {
             contour_id += 1;
         }
-        process_polygon(polygon.exterior(), false, contour_id, &mut event_queue, cbbox, exterior);
-        for interior in polygon.interiors() {
-            process_polygon(interior, false, contour_id, &mut event_queue, cbbox, false);
+        let source = EdgeSource {
+            operand: 1,
+            polygon: polygon_index,
+            ..EdgeSource::default()
+        };
+        process_rings(
+            polygon,
+            false,
+            source,
+            contour_id,
+            events,
+            &mut event_queue,
+            cbbox,
+            exterior,
+        );
+    }
+
+    event_queue
+}
+
+/// Fills the queue with the polygons of any number of operands, labelling each event with the
+/// index of its operand. All polygons are added as subject polygons.
+pub fn fill_queue_operands<F, K>(
+    operands: &[&[Polygon<F>]],
+    bbox: &mut BoundingBox<F>,
+    events: &mut EventArena<F, K>,
+) -> EventQueue
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("fill_queue").entered();
+
+    let mut event_queue = EventQueue::new();
+    let mut contour_id = 0u32;
+
+    for (operand, polygons) in operands.iter().enumerate() {
+        for (polygon_index, polygon) in polygons.iter().enumerate() {
+            contour_id += 1;
+            let source = EdgeSource {
+                operand,
+                polygon: polygon_index,
+                ..EdgeSource::default()
+            };
+            process_rings(polygon, true, source, contour_id, events, &mut event_queue, bbox, true);
         }
     }
 
     event_queue
 }
 
-fn process_polygon<F>(
+/// Adds all rings of the polygon, where `source` identifies the polygon.
+#[allow(clippy::too_many_arguments)]
+fn process_rings<F, K>(
+    polygon: &Polygon<F>,
+    is_subject: bool,
+    source: EdgeSource,
+    contour_id: u32,
+    events: &mut EventArena<F, K>,
+    event_queue: &mut EventQueue,
+    bbox: &mut BoundingBox<F>,
+    is_exterior: bool,
+) where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    process_polygon(
+        polygon.exterior(),
+        is_subject,
+        source,
+        contour_id,
+        events,
+        event_queue,
+        bbox,
+        is_exterior,
+    );
+    for (ring, interior) in (1..).zip(polygon.interiors()) {
+        let source = EdgeSource { ring, ..source };
+        process_polygon(
+            interior,
+            is_subject,
+            source,
+            contour_id,
+            events,
+            event_queue,
+            bbox,
+            false,
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn process_polygon<F, K>(
     contour_or_hole: &LineString<F>,
     is_subject: bool,
+    source: EdgeSource,
     contour_id: u32,
-    event_queue: &mut BinaryHeap<Rc<SweepEvent<F>>>,
+    events: &mut EventArena<F, K>,
+    event_queue: &mut EventQueue,
     bbox: &mut BoundingBox<F>,
     is_exterior_ring: bool,
 ) where
-    F: Float,
+    F: OrderedField,
+    K: Kernel<F>,
 {
-    for line in contour_or_hole.lines() {
+    for (edge, line) in contour_or_hole.lines().enumerate() {
         if line.start == line.end {
             continue; // skip collapsed edges
         }
 
-        let e1 = SweepEvent::new_rc(contour_id, line.start, false, Weak::new(), is_subject, is_exterior_ring);
-        let e2 = SweepEvent::new_rc(
-            contour_id,
-            line.end,
-            false,
-            Rc::downgrade(&e1),
-            is_subject,
-            is_exterior_ring,
+        let (e1, e2) = events.push_pair(
+            SweepEvent::new(contour_id, line.start, false, None, is_subject, is_exterior_ring),
+            SweepEvent::new(contour_id, line.end, false, None, is_subject, is_exterior_ring),
         );
-        e1.set_other_event(&e2);
+        events[e1].set_source(EdgeSource { edge, ..source });
+        events[e2].set_source(EdgeSource { edge, ..source });
 
-        if e1 < e2 {
-            e2.set_left(true)
+        // The winding is positive if the ring is traversed from left to right,
+        // i.e., counter-clockwise rings have a positive winding number inside.
+        if events.is_after(e1, e2) {
+            events[e2].set_left(true);
+            events[e1].set_winding(-1);
+            events[e2].set_winding(-1);
         } else {
-            e1.set_left(true)
+            events[e1].set_left(true)
         }
 
-        bbox.min.x = bbox.min.x.min(line.start.x);
-        bbox.min.y = bbox.min.y.min(line.start.y);
-        bbox.max.x = bbox.max.x.max(line.start.x);
-        bbox.max.y = bbox.max.y.max(line.start.y);
+        bbox.min.x = partial_min(bbox.min.x, line.start.x);
+        bbox.min.y = partial_min(bbox.min.y, line.start.y);
+        bbox.max.x = partial_max(bbox.max.x, line.start.x);
+        bbox.max.y = partial_max(bbox.max.y, line.start.y);
 
-        event_queue.push(e1);
-        event_queue.push(e2);
+        event_queue.push(events, e1);
+        event_queue.push(events, e2);
     }
 }
 
+/// Adds a segment of linework to the queue. In contrast to polygon edges, line segments
+/// have no winding, i.e., they do not change whether a region is inside of a polygon.
+/// They are added as clipping segments, so that they are stacked above coincident polygon
+/// edges in the sweep line.
+pub fn process_line_segment<F, K>(
+    line: Line<F>,
+    contour_id: u32,
+    events: &mut EventArena<F, K>,
+    event_queue: &mut EventQueue,
+    bbox: &mut BoundingBox<F>,
+) where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    if line.start == line.end {
+        return;
+    }
+
+    let (e1, e2) = events.push_pair(
+        SweepEvent::new(contour_id, line.start, false, None, false, false),
+        SweepEvent::new(contour_id, line.end, false, None, false, false),
+    );
+    // Every line segment has a contour of its own, which identifies its input edge.
+    let source = EdgeSource {
+        operand: 1,
+        edge: contour_id as usize,
+        ..EdgeSource::default()
+    };
+    events[e1].set_source(source);
+    events[e2].set_source(source);
+    events[e1].set_winding(0);
+    events[e2].set_winding(0);
+
+    if events.is_after(e1, e2) {
+        events[e2].set_left(true);
+    } else {
+        events[e1].set_left(true)
+    }
+
+    for point in [line.start, line.end] {
+        bbox.min.x = partial_min(bbox.min.x, point.x);
+        bbox.min.y = partial_min(bbox.min.y, point.y);
+        bbox.max.x = partial_max(bbox.max.x, point.x);
+        bbox.max.y = partial_max(bbox.max.y, point.y);
+    }
+
+    event_queue.push(events, e1);
+    event_queue.push(events, e2);
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use geo_types::Coord;
     use std::cmp::Ordering;
-    use std::collections::BinaryHeap;
-    use std::rc::{Rc, Weak};
 
-    fn make_simple(x: f64, y: f64, is_subject: bool) -> Rc<SweepEvent<f64>> {
-        SweepEvent::new_rc(0, Coord { x, y }, false, Weak::new(), is_subject, true)
+    fn make_simple(events: &mut EventArena<f64>, x: f64, y: f64, is_subject: bool) -> usize {
+        events.push(SweepEvent::new(0, Coord { x, y }, false, None, is_subject, true))
     }
 
-    fn check_order_in_queue(first: Rc<SweepEvent<f64>>, second: Rc<SweepEvent<f64>>) {
-        let mut queue: BinaryHeap<Rc<SweepEvent<f64>>> = BinaryHeap::new();
+    fn check_order_in_queue(events: &EventArena<f64>, first: usize, second: usize) {
+        let mut queue = EventQueue::new();
 
-        assert_eq!(first.cmp(&second), Ordering::Greater);
-        assert_eq!(second.cmp(&first), Ordering::Less);
+        assert_eq!(events.cmp_events(first, second), Ordering::Greater);
+        assert_eq!(events.cmp_events(second, first), Ordering::Less);
         {
-            queue.push(first.clone());
-            queue.push(second.clone());
+            queue.push(events, first);
+            queue.push(events, second);
 
-            let p1 = queue.pop().unwrap();
-            let p2 = queue.pop().unwrap();
+            let p1 = queue.pop(events).unwrap();
+            let p2 = queue.pop(events).unwrap();
 
-            assert!(Rc::ptr_eq(&first, &p1));
-            assert!(Rc::ptr_eq(&second, &p2));
+            assert_eq!(first, p1);
+            assert_eq!(second, p2);
         }
         {
-            queue.push(second.clone());
-            queue.push(first.clone());
+            queue.push(events, second);
+            queue.push(events, first);
 
-            let p1 = queue.pop().unwrap();
-            let p2 = queue.pop().unwrap();
+            let p1 = queue.pop(events).unwrap();
+            let p2 = queue.pop(events).unwrap();
 
-            assert!(Rc::ptr_eq(&first, &p1));
-            assert!(Rc::ptr_eq(&second, &p2));
+            assert_eq!(first, p1);
+            assert_eq!(second, p2);
         }
     }
 
     #[test]
     fn test_least_by_x() {
-        check_order_in_queue(make_simple(0.0, 0.0, false), make_simple(0.5, 0.5, false))
+        let mut events = EventArena::new();
+        let first = make_simple(&mut events, 0.0, 0.0, false);
+        let second = make_simple(&mut events, 0.5, 0.5, false);
+        check_order_in_queue(&events, first, second)
     }
 
     #[test]
     fn test_least_by_y() {
-        check_order_in_queue(make_simple(0.0, 0.0, false), make_simple(0.0, 0.5, false))
+        let mut events = EventArena::new();
+        let first = make_simple(&mut events, 0.0, 0.0, false);
+        let second = make_simple(&mut events, 0.0, 0.5, false);
+        check_order_in_queue(&events, first, second)
     }
 
     #[test]
     fn test_least_left() {
-        let e1 = make_simple(0.0, 0.0, false);
-        e1.set_left(true);
-        let e2 = make_simple(0.0, 0.0, false);
-        e2.set_left(false);
+        let mut events = EventArena::new();
+        let e1 = make_simple(&mut events, 0.0, 0.0, false);
+        events[e1].set_left(true);
+        let e2 = make_simple(&mut events, 0.0, 0.0, false);
+        events[e2].set_left(false);
 
-        check_order_in_queue(e2, e1)
+        check_order_in_queue(&events, e2, e1)
     }
 
     #[test]
     fn test_shared_edge_not_colinear() {
-        let other_e1 = make_simple(1.0, 1.0, false);
-        let e1 = make_simple(0.0, 0.0, false);
-        e1.set_other_event(&other_e1);
-        e1.set_left(true);
-        let other_e2 = make_simple(2.0, 3.0, false);
-        let e2 = make_simple(0.0, 0.0, false);
-        e2.set_other_event(&other_e2);
-        e2.set_left(true);
-
-        check_order_in_queue(e1, e2)
+        let mut events = EventArena::new();
+        let other_e1 = make_simple(&mut events, 1.0, 1.0, false);
+        let e1 = make_simple(&mut events, 0.0, 0.0, false);
+        events[e1].set_other_event(other_e1);
+        events[e1].set_left(true);
+        let other_e2 = make_simple(&mut events, 2.0, 3.0, false);
+        let e2 = make_simple(&mut events, 0.0, 0.0, false);
+        events[e2].set_other_event(other_e2);
+        events[e2].set_left(true);
+
+        check_order_in_queue(&events, e1, e2)
     }
 
     #[test]
     fn test_collinear_edges() {
-        let other_e1 = make_simple(1.0, 1.0, true);
-        let e1 = make_simple(0.0, 0.0, true);
-        e1.set_other_event(&other_e1);
-        e1.set_left(true);
-        let other_e2 = make_simple(2.0, 2.0, false);
-        let e2 = make_simple(0.0, 0.0, false);
-        e2.set_other_event(&other_e2);
-        e2.set_left(true);
-
-        check_order_in_queue(e1, e2)
+        let mut events = EventArena::new();
+        let other_e1 = make_simple(&mut events, 1.0, 1.0, true);
+        let e1 = make_simple(&mut events, 0.0, 0.0, true);
+        events[e1].set_other_event(other_e1);
+        events[e1].set_left(true);
+        let other_e2 = make_simple(&mut events, 2.0, 2.0, false);
+        let e2 = make_simple(&mut events, 0.0, 0.0, false);
+        events[e2].set_other_event(other_e2);
+        events[e2].set_left(true);
+
+        check_order_in_queue(&events, e1, e2)
     }
 }
@@ -2,12 +2,47 @@ use float_next_after::NextAfter as NextAfterFloat;
 use geo_types::{Coord, CoordNum};
 use num_traits::Float as NumTraitsFloat;
 use std::cmp::Ordering;
-use std::fmt::{Debug, Display};
-use std::{f32, f64};
+use std::fmt::Display;
 
-pub trait Float: NumTraitsFloat + Debug + Display + NextAfter + Into<f64> {}
+/// Scalar type of the coordinates on which the sweep operates, which has to be an ordered
+/// field. Besides floating point numbers, this allows exact number types like rationals,
+/// whose predicates are computed natively in the type, see `FastKernel`.
+///
+/// The coordinates of `geo_types` are `Copy`, so only exact types of a fixed size qualify,
+/// like `num_rational::Ratio<i64>`, whose arithmetic is exact as long as it does not
+/// overflow. Heap allocated types like `BigRational` or the types of `rug` cannot be used.
+pub trait OrderedField: CoordNum {
+    /// The least value greater than `self`. An intersection point which is rounded to the
+    /// left of the segment it divides is moved by this step. Types with exact arithmetic
+    /// never round, and can keep the default, which returns `self`.
+    fn next_up(self) -> Self {
+        self
+    }
+}
+
+/// Floating point scalar type of the coordinates, which is required by the operations
+/// relying on floating point arithmetic, like `BooleanOp` and the robust kernels.
+///
+/// The trait is implemented for the floating point types only, rather than for all types
+/// satisfying its bounds, so that `BooleanOp` can be implemented separately for integer
+/// coordinates, see `IntegerCoord`.
+pub trait Float: OrderedField + NumTraitsFloat + Display + NextAfter + Into<f64> {}
+
+impl OrderedField for f64 {
+    fn next_up(self) -> f64 {
+        self.nextafter(true)
+    }
+}
+
+impl OrderedField for f32 {
+    fn next_up(self) -> f32 {
+        self.nextafter(true)
+    }
+}
 
-impl<T: NumTraitsFloat + Debug + Display + NextAfter + Into<f64>> Float for T {}
+impl Float for f64 {}
+
+impl Float for f32 {}
 
 pub trait NextAfter: NumTraitsFloat {
     fn nextafter(self, up: bool) -> Self;
@@ -16,9 +51,9 @@ pub trait NextAfter: NumTraitsFloat {
 impl NextAfter for f64 {
     fn nextafter(self, up: bool) -> Self {
         if up {
-            self.next_after(std::f64::INFINITY)
+            self.next_after(f64::INFINITY)
         } else {
-            self.next_after(std::f64::NEG_INFINITY)
+            self.next_after(f64::NEG_INFINITY)
         }
     }
 }
@@ -26,9 +61,9 @@ impl NextAfter for f64 {
 impl NextAfter for f32 {
     fn nextafter(self, up: bool) -> Self {
         if up {
-            self.next_after(std::f32::INFINITY)
+            self.next_after(f32::INFINITY)
         } else {
-            self.next_after(std::f32::NEG_INFINITY)
+            self.next_after(f32::NEG_INFINITY)
         }
     }
 }
@@ -51,6 +86,26 @@ pub fn less_if_inversed(condition: bool) -> Ordering {
     }
 }
 
+/// The smaller of two values, for types that are only `PartialOrd`.
+#[inline]
+pub fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+/// The greater of two values, for types that are only `PartialOrd`.
+#[inline]
+pub fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
 /// A bounded 2D quadrilateral whose area is defined by minimum and maximum `Coords`.
 ///
 /// A simple implementation copied from geo_types 0.4.0, because this version is a better
@@ -96,7 +151,7 @@ pub mod test {
             x.nextafter(true)
         }
 
-        assert_eq!(dummy(0_f64), 0_f64.next_after(std::f64::INFINITY));
-        assert_eq!(dummy(0_f32), 0_f32.next_after(std::f32::INFINITY));
+        assert_eq!(dummy(0_f64), 0_f64.next_after(f64::INFINITY));
+        assert_eq!(dummy(0_f32), 0_f32.next_after(f32::INFINITY));
     }
 }
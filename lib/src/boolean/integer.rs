@@ -0,0 +1,732 @@
+use super::boolean_all::boolean_all_operation;
+use super::divide::divide_operation;
+use super::error::BooleanOpError;
+use super::fill_queue::fill_queue;
+use super::intersection_full::intersection_full_operation;
+use super::kernel::DefaultKernel;
+use super::observer::SweepObserver;
+use super::operand::{Operand, Operands};
+use super::subdivide_segments::subdivide_with_observer;
+use super::sweep_event::{EventArena, EventId};
+use super::sweep_line::{SplaySweepLine, SweepLineStatus};
+use super::{
+    boolean_operation, empty_bounding_box, traced_boolean_operation, BooleanOp, BooleanResults, Division, EdgeSource,
+    FillRules, Operation, PrecisionModel, TracedMultiPolygon,
+};
+use geo_types::{Coord, CoordNum, Geometry, GeometryCollection, LineString, MultiPolygon, Polygon};
+use num_traits::NumCast;
+
+/// Integer scalar type of the coordinates, for which `BooleanOp` is implemented on
+/// `Polygon<i32>`, `Polygon<i64>` and their `MultiPolygon`s.
+///
+/// The operations on integer coordinates are exact: the operands are snap rounded to the
+/// integer grid, where the intersections of their edges are computed with 128 bit integer
+/// arithmetic and rounded to the nearest integer coordinates. Afterwards, edges only meet at
+/// their endpoints, so the sweep neither depends on the accuracy of intersection points, nor
+/// on the orientation tests, which are exact for integers anyway.
+///
+/// Coordinates must not exceed `MAX_INTEGER_COORD` in magnitude, otherwise the operations
+/// fail with `BooleanOpError::CoordinateOutOfRange`.
+pub trait IntegerCoord: CoordNum + Into<i64> {}
+
+impl IntegerCoord for i32 {}
+
+impl IntegerCoord for i64 {}
+
+/// Largest magnitude of integer coordinates, for which all intermediate results of the
+/// exact computations fit into 128 bits.
+pub const MAX_INTEGER_COORD: i64 = 1 << 40;
+
+/// Integer point, or the center of the grid cell `[x - 0.5, x + 0.5) × [y - 0.5, y + 0.5)`.
+type Point = (i64, i64);
+
+/// Operands which are snap rounded to the integer grid, in floating point coordinates, which
+/// represent integers up to `MAX_INTEGER_COORD` exactly.
+#[derive(Default)]
+struct SnappedOperands {
+    polygons: [Vec<Polygon<f64>>; 2],
+    /// `origins[operand][p]` locates the snapped polygon `p` in the input.
+    origins: [Vec<PolygonOrigin>; 2],
+}
+
+struct PolygonOrigin {
+    polygon: usize,
+    rings: Vec<RingOrigin>,
+}
+
+struct RingOrigin {
+    ring: usize,
+    /// The input edge every snapped edge is part of.
+    edges: Vec<usize>,
+}
+
+impl SnappedOperands {
+    /// Maps the source of an edge of the snapped operands to the input.
+    fn input_source(&self, source: EdgeSource) -> EdgeSource {
+        let polygon = &self.origins[source.operand][source.polygon];
+        let ring = &polygon.rings[source.ring];
+        EdgeSource {
+            operand: source.operand,
+            polygon: polygon.polygon,
+            ring: ring.ring,
+            edge: ring.edges[source.edge],
+        }
+    }
+}
+
+/// Snap rounds the rings of both operands to the integer grid.
+///
+/// Every input vertex and the rounded intersection point of every pair of crossing input
+/// edges is a hot pixel. Each edge is replaced by the polyline through the centers of all hot
+/// pixels it passes, which only meet other such polylines at their vertices.
+fn snap_round<T>(subject: &[Polygon<T>], clipping: &[Polygon<T>]) -> Result<SnappedOperands, BooleanOpError>
+where
+    T: IntegerCoord,
+{
+    let range = -MAX_INTEGER_COORD..=MAX_INTEGER_COORD;
+    let in_range = |polygons: &[Polygon<T>]| {
+        rings(polygons)
+            .flat_map(|ring| ring.0.iter())
+            .all(|&c| range.contains(&c.x.into()) && range.contains(&c.y.into()))
+    };
+    if !in_range(subject) || !in_range(clipping) {
+        return Err(BooleanOpError::CoordinateOutOfRange);
+    }
+
+    let hot_pixels = HotPixels::new(subject, clipping)?;
+    let mut snapped = SnappedOperands::default();
+    for (operand, polygons) in [subject, clipping].into_iter().enumerate() {
+        for (index, polygon) in polygons.iter().enumerate() {
+            if let Some((polygon, rings)) = hot_pixels.snap_polygon(polygon) {
+                snapped.polygons[operand].push(polygon);
+                snapped.origins[operand].push(PolygonOrigin { polygon: index, rings });
+            }
+        }
+    }
+    Ok(snapped)
+}
+
+/// Grid cells containing an input vertex or an intersection of input edges.
+struct HotPixels {
+    /// Sorted by x, then by y.
+    centers: Vec<Point>,
+}
+
+impl HotPixels {
+    fn new<T>(subject: &[Polygon<T>], clipping: &[Polygon<T>]) -> Result<HotPixels, BooleanOpError>
+    where
+        T: IntegerCoord,
+    {
+        // Every pair of crossing edges is checked for an intersection by the union sweep,
+        // which yields the pairs whose exact intersection has to be computed.
+        let float_subject: Vec<_> = subject.iter().map(to_float_polygon).collect();
+        let float_clipping: Vec<_> = clipping.iter().map(to_float_polygon).collect();
+        let mut sbbox = empty_bounding_box();
+        let mut cbbox = sbbox;
+        let mut events = EventArena::new();
+        let mut event_queue = fill_queue(
+            &float_subject,
+            &float_clipping,
+            &mut sbbox,
+            &mut cbbox,
+            Operation::Union,
+            &mut events,
+        );
+
+        let mut collector = IntersectionCollector {
+            operands: [subject, clipping],
+            intersections: Vec::new(),
+        };
+        subdivide_with_observer(
+            &mut events,
+            &mut event_queue,
+            &sbbox,
+            &cbbox,
+            Operation::Union,
+            FillRules::default(),
+            &mut collector,
+        )?;
+
+        let mut centers = collector.intersections;
+        centers.extend(
+            rings(subject)
+                .chain(rings(clipping))
+                .flat_map(|ring| ring.0.iter().map(|&c| to_point(c))),
+        );
+        centers.sort_unstable();
+        centers.dedup();
+        Ok(HotPixels { centers })
+    }
+
+    /// Snaps the rings of the polygon along with their origins, or returns `None` if its
+    /// exterior collapses.
+    fn snap_polygon<T>(&self, polygon: &Polygon<T>) -> Option<(Polygon<f64>, Vec<RingOrigin>)>
+    where
+        T: IntegerCoord,
+    {
+        let mut rings = std::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .enumerate()
+            .filter_map(|(index, ring)| {
+                let (snapped, edges) = self.snap_ring(ring)?;
+                Some((snapped, RingOrigin { ring: index, edges }))
+            });
+        let (exterior, exterior_origin) = rings.next().filter(|(_, origin)| origin.ring == 0)?;
+        let (interiors, interior_origins): (Vec<_>, Vec<_>) = rings.unzip();
+        let origins = std::iter::once(exterior_origin).chain(interior_origins).collect();
+        Some((Polygon::new(exterior, interiors), origins))
+    }
+
+    /// Snaps the ring, along with the input edge of every snapped edge, or returns `None` if
+    /// it collapses to less than three vertices.
+    fn snap_ring<T>(&self, ring: &LineString<T>) -> Option<(LineString<f64>, Vec<usize>)>
+    where
+        T: IntegerCoord,
+    {
+        let mut coords = Vec::new();
+        let mut edges = Vec::new();
+        for (edge, line) in ring.lines().enumerate() {
+            let start = to_point(line.start);
+            let end = to_point(line.end);
+            let mut passed: Vec<Point> = self
+                .candidates(start, end)
+                .filter(|&center| passes_pixel(start, end, center))
+                .collect();
+            // The edge passes the columns of cells in the direction of x, and the cells
+            // within a column in the direction of y.
+            let direction = ((end.0 - start.0).signum(), (end.1 - start.1).signum());
+            passed.sort_unstable_by_key(|center| (center.0 * direction.0, center.1 * direction.1));
+            // The last pixel is the one of the end point, which starts the next edge.
+            passed.pop();
+            for center in passed {
+                coords.push(Coord {
+                    x: center.0 as f64,
+                    y: center.1 as f64,
+                });
+                edges.push(edge);
+            }
+        }
+        if coords.len() < 3 {
+            return None;
+        }
+        coords.push(coords[0]);
+        Some((LineString(coords), edges))
+    }
+
+    /// The hot pixels within the bounding box of the segment.
+    fn candidates(&self, start: Point, end: Point) -> impl Iterator<Item = Point> + '_ {
+        let (min_x, max_x) = (start.0.min(end.0), start.0.max(end.0));
+        let (min_y, max_y) = (start.1.min(end.1), start.1.max(end.1));
+        let first = self.centers.partition_point(|c| c.0 < min_x);
+        self.centers[first..]
+            .iter()
+            .take_while(move |c| c.0 <= max_x)
+            .filter(move |c| c.1 >= min_y && c.1 <= max_y)
+            .copied()
+    }
+}
+
+/// Collects the rounded intersections of the input edges which are checked by the sweep.
+struct IntersectionCollector<'a, T>
+where
+    T: IntegerCoord,
+{
+    /// The integer operands, whose edges are located by the sources of the events.
+    operands: [&'a [Polygon<T>]; 2],
+    intersections: Vec<Point>,
+}
+
+impl<T> IntersectionCollector<'_, T>
+where
+    T: IntegerCoord,
+{
+    fn input_edge(&self, source: EdgeSource) -> (Point, Point) {
+        let polygon = &self.operands[source.operand][source.polygon];
+        let ring = match source.ring {
+            0 => polygon.exterior(),
+            ring => &polygon.interiors()[ring - 1],
+        };
+        (to_point(ring.0[source.edge]), to_point(ring.0[source.edge + 1]))
+    }
+}
+
+impl<T> SweepObserver<f64> for IntersectionCollector<'_, T>
+where
+    T: IntegerCoord,
+{
+    fn check_intersection(&mut self, events: &EventArena<f64>, below: EventId, above: EventId) {
+        let a = self.input_edge(events[below].get_source());
+        let b = self.input_edge(events[above].get_source());
+        self.intersections.extend(rounded_intersection(a, b));
+    }
+}
+
+/// The intersection point of two edges crossing in a single point, rounded to the nearest
+/// integer coordinates. Edges which overlap only meet at vertices, which are hot pixels
+/// anyway.
+fn rounded_intersection(a: (Point, Point), b: (Point, Point)) -> Option<Point> {
+    let wide = |p: Point| (p.0 as i128, p.1 as i128);
+    let (a1, a2, b1) = (wide(a.0), wide(a.1), wide(b.0));
+    let va = (a2.0 - a1.0, a2.1 - a1.1);
+    let vb = (wide(b.1).0 - b1.0, wide(b.1).1 - b1.1);
+    let e = (b1.0 - a1.0, b1.1 - a1.1);
+
+    // The intersection is at a1 + va * s / denominator = b1 + vb * t / denominator.
+    let mut denominator = cross_product(va, vb);
+    if denominator == 0 {
+        return None;
+    }
+    let mut s = cross_product(e, vb);
+    let mut t = cross_product(e, va);
+    if denominator < 0 {
+        denominator = -denominator;
+        s = -s;
+        t = -t;
+    }
+    if s < 0 || s > denominator || t < 0 || t > denominator {
+        return None;
+    }
+    Some((
+        round_quotient(a1.0 * denominator + va.0 * s, denominator),
+        round_quotient(a1.1 * denominator + va.1 * s, denominator),
+    ))
+}
+
+#[inline]
+fn cross_product(a: (i128, i128), b: (i128, i128)) -> i128 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+/// Rounds `numerator / denominator` for a positive denominator, with ties rounded up like
+/// `PrecisionModel::make_precise`.
+#[inline]
+fn round_quotient(numerator: i128, denominator: i128) -> i64 {
+    (2 * numerator + denominator).div_euclid(2 * denominator) as i64
+}
+
+/// Whether the segment from `start` to `end` passes the half-open cell around `center`.
+fn passes_pixel(start: Point, end: Point, center: Point) -> bool {
+    // In doubled coordinates relative to the center, the cell is [-1, 1) × [-1, 1).
+    let s = (2 * (start.0 - center.0) as i128, 2 * (start.1 - center.1) as i128);
+    let d = (2 * (end.0 - start.0) as i128, 2 * (end.1 - start.1) as i128);
+
+    // Liang-Barsky clipping against the closed cell, with the parameters as fractions with
+    // positive denominators.
+    let less = |a: (i128, i128), b: (i128, i128)| a.0 * b.1 < b.0 * a.1;
+    let mut t0 = (0, 1);
+    let mut t1 = (1, 1);
+    for (p, q) in [(-d.0, s.0 + 1), (d.0, 1 - s.0), (-d.1, s.1 + 1), (d.1, 1 - s.1)] {
+        if p == 0 {
+            if q < 0 {
+                return false;
+            }
+        } else if p < 0 {
+            if less(t0, (-q, -p)) {
+                t0 = (-q, -p);
+            }
+        } else if less((q, p), t1) {
+            t1 = (q, p);
+        }
+    }
+    if less(t1, t0) {
+        return false;
+    }
+
+    // The cell excludes its upper and right boundary, so the segment has to pass it
+    // somewhere else than only there.
+    let beyond_max = |s: i128, d: i128, t: (i128, i128)| s * t.1 + d * t.0 >= t.1;
+    let outside = |s, d| beyond_max(s, d, t0) && beyond_max(s, d, t1);
+    !outside(s.0, d.0) && !outside(s.1, d.1)
+}
+
+fn rings<T>(polygons: &[Polygon<T>]) -> impl Iterator<Item = &LineString<T>>
+where
+    T: CoordNum,
+{
+    polygons
+        .iter()
+        .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+}
+
+fn to_point<T>(coord: Coord<T>) -> Point
+where
+    T: IntegerCoord,
+{
+    (coord.x.into(), coord.y.into())
+}
+
+fn to_float_polygon<T>(polygon: &Polygon<T>) -> Polygon<f64>
+where
+    T: IntegerCoord,
+{
+    let to_float = |ring: &LineString<T>| {
+        ring.0
+            .iter()
+            .map(|&c| {
+                let (x, y) = to_point(c);
+                Coord {
+                    x: x as f64,
+                    y: y as f64,
+                }
+            })
+            .collect::<LineString<f64>>()
+    };
+    Polygon::new(
+        to_float(polygon.exterior()),
+        polygon.interiors().iter().map(to_float).collect(),
+    )
+}
+
+/// Rounds the ring to integer coordinates, removing the edges which collapse, and the
+/// corresponding entries of `sources` if given.
+///
+/// Since the snap rounded operands only meet at their vertices, the result has integer
+/// coordinates already, up to numerical inaccuracies of the sweep.
+fn to_integer_ring<T>(ring: &LineString<f64>, sources: Option<&mut Vec<EdgeSource>>) -> LineString<T>
+where
+    T: IntegerCoord,
+{
+    let mut coords: Vec<Coord<T>> = Vec::with_capacity(ring.0.len());
+    let mut kept_edges = Vec::with_capacity(ring.0.len());
+    for (index, c) in ring.0.iter().enumerate() {
+        let c = to_integer_coord(*c);
+        if coords.last() == Some(&c) {
+            continue;
+        }
+        if index > 0 {
+            kept_edges.push(index - 1);
+        }
+        coords.push(c);
+    }
+    if let Some(sources) = sources {
+        *sources = kept_edges.into_iter().map(|edge| sources[edge]).collect();
+    }
+    LineString(coords)
+}
+
+fn to_integer_coord<T>(c: Coord<f64>) -> Coord<T>
+where
+    T: IntegerCoord,
+{
+    let round = |value: f64| <T as NumCast>::from(value.round()).expect("result is within the input bounds");
+    Coord {
+        x: round(c.x),
+        y: round(c.y),
+    }
+}
+
+fn to_integer_polygon<T>(polygon: &Polygon<f64>) -> Polygon<T>
+where
+    T: IntegerCoord,
+{
+    Polygon::new(
+        to_integer_ring(polygon.exterior(), None),
+        polygon
+            .interiors()
+            .iter()
+            .map(|ring| to_integer_ring(ring, None))
+            .collect(),
+    )
+}
+
+fn to_integer<T>(result: &MultiPolygon<f64>) -> MultiPolygon<T>
+where
+    T: IntegerCoord,
+{
+    MultiPolygon(result.0.iter().map(to_integer_polygon).collect())
+}
+
+fn integer_boolean_operation<T, S>(
+    subject: &[Polygon<T>],
+    clipping: &[Polygon<T>],
+    operation: Operation,
+    fill_rules: FillRules,
+) -> Result<MultiPolygon<T>, BooleanOpError>
+where
+    T: IntegerCoord,
+    S: SweepLineStatus,
+{
+    let snapped = snap_round(subject, clipping)?;
+    let [subject, clipping] = &snapped.polygons;
+    let result = boolean_operation::<f64, S>(subject, clipping, operation, fill_rules)?;
+    Ok(to_integer(&result))
+}
+
+fn integer_precise_boolean_operation<T>(
+    subject: &[Polygon<T>],
+    clipping: &[Polygon<T>],
+    operation: Operation,
+    fill_rules: FillRules,
+    precision: PrecisionModel<T>,
+) -> Result<MultiPolygon<T>, BooleanOpError>
+where
+    T: IntegerCoord,
+{
+    // Integers are multiples of `1 / scale` for every positive integer scale, so the result
+    // is precise already.
+    if let PrecisionModel::Fixed { scale } = precision {
+        if scale <= T::zero() {
+            return Err(BooleanOpError::InvalidPrecisionModel);
+        }
+    }
+    integer_boolean_operation::<T, SplaySweepLine>(subject, clipping, operation, fill_rules)
+}
+
+fn integer_traced_boolean_operation<T>(
+    subject: &[Polygon<T>],
+    clipping: &[Polygon<T>],
+    operation: Operation,
+    fill_rules: FillRules,
+) -> Result<TracedMultiPolygon<T>, BooleanOpError>
+where
+    T: IntegerCoord,
+{
+    let snapped = snap_round(subject, clipping)?;
+    let [snapped_subject, snapped_clipping] = &snapped.polygons;
+    let traced = traced_boolean_operation::<f64, SplaySweepLine, DefaultKernel>(
+        snapped_subject,
+        snapped_clipping,
+        operation,
+        fill_rules,
+    )?;
+
+    let mut result = TracedMultiPolygon {
+        multi_polygon: MultiPolygon(Vec::new()),
+        sources: Vec::new(),
+    };
+    for (polygon, mut polygon_sources) in traced.multi_polygon.0.iter().zip(traced.sources) {
+        for ring_sources in polygon_sources.iter_mut() {
+            for source in ring_sources.iter_mut() {
+                *source = snapped.input_source(*source);
+            }
+        }
+        let (exterior_sources, interior_sources) = polygon_sources.split_at_mut(1);
+        result.multi_polygon.0.push(Polygon::new(
+            to_integer_ring(polygon.exterior(), Some(&mut exterior_sources[0])),
+            polygon
+                .interiors()
+                .iter()
+                .zip(interior_sources)
+                .map(|(ring, sources)| to_integer_ring(ring, Some(sources)))
+                .collect(),
+        ));
+        result.sources.push(polygon_sources);
+    }
+    Ok(result)
+}
+
+fn integer_boolean_all_operation<T>(
+    subject: &[Polygon<T>],
+    clipping: &[Polygon<T>],
+    fill_rules: FillRules,
+) -> Result<BooleanResults<T>, BooleanOpError>
+where
+    T: IntegerCoord,
+{
+    let snapped = snap_round(subject, clipping)?;
+    let [subject, clipping] = &snapped.polygons;
+    let results = boolean_all_operation(subject, clipping, fill_rules)?;
+    Ok(BooleanResults {
+        intersection: to_integer(&results.intersection),
+        union: to_integer(&results.union),
+        difference: to_integer(&results.difference),
+        xor: to_integer(&results.xor),
+    })
+}
+
+fn integer_divide_operation<T>(
+    subject: &[Polygon<T>],
+    clipping: &[Polygon<T>],
+    fill_rules: FillRules,
+) -> Result<Division<T>, BooleanOpError>
+where
+    T: IntegerCoord,
+{
+    let snapped = snap_round(subject, clipping)?;
+    let [subject, clipping] = &snapped.polygons;
+    let division = divide_operation(subject, clipping, fill_rules)?;
+    Ok(Division {
+        subject_only: to_integer(&division.subject_only),
+        both: to_integer(&division.both),
+        clipping_only: to_integer(&division.clipping_only),
+    })
+}
+fn integer_intersection_full_operation<T>(
+    subject: &[Polygon<T>],
+    clipping: &[Polygon<T>],
+    fill_rules: FillRules,
+) -> Result<GeometryCollection<T>, BooleanOpError>
+where
+    T: IntegerCoord,
+{
+    let snapped = snap_round(subject, clipping)?;
+    let [subject, clipping] = &snapped.polygons;
+    let result = intersection_full_operation(subject, clipping, fill_rules)?;
+    Ok(GeometryCollection(
+        result
+            .0
+            .iter()
+            .map(|geometry| match geometry {
+                Geometry::Polygon(polygon) => Geometry::Polygon(to_integer_polygon(polygon)),
+                Geometry::LineString(line) => Geometry::LineString(to_integer_ring(line, None)),
+                Geometry::Point(point) => Geometry::Point(to_integer_coord(point.0).into()),
+                _ => unreachable!("the full intersection consists of polygons, line strings and points"),
+            })
+            .collect(),
+    ))
+}
+
+// `BooleanOp` and `Operands` are implemented for each integer type separately, since a
+// generic implementation for all `IntegerCoord` types would conflict with the one for all
+// `Float` types.
+macro_rules! impl_boolean_op_for_integers {
+    (@impl $t:ty, $lhs:ty, $rhs:ty) => {
+        impl BooleanOp<$t, $rhs> for $lhs {
+            fn boolean(&self, rhs: &$rhs, operation: Operation) -> MultiPolygon<$t> {
+                self.boolean_with_fill_rules(rhs, operation, FillRules::default())
+            }
+        }
+    };
+    (@operands $t:ty) => {
+        impl<Lhs, Rhs> Operands<$t, Rhs> for Lhs
+        where
+            Lhs: Operand<$t>,
+            Rhs: Operand<$t>,
+        {
+            fn boolean_operation<S>(
+                &self,
+                rhs: &Rhs,
+                operation: Operation,
+                fill_rules: FillRules,
+            ) -> Result<MultiPolygon<$t>, BooleanOpError>
+            where
+                S: SweepLineStatus,
+            {
+                integer_boolean_operation::<$t, S>(self.polygons(), rhs.polygons(), operation, fill_rules)
+            }
+
+            fn precise_boolean_operation(
+                &self,
+                rhs: &Rhs,
+                operation: Operation,
+                fill_rules: FillRules,
+                precision: PrecisionModel<$t>,
+            ) -> Result<MultiPolygon<$t>, BooleanOpError> {
+                integer_precise_boolean_operation(self.polygons(), rhs.polygons(), operation, fill_rules, precision)
+            }
+
+            fn traced_boolean_operation(
+                &self,
+                rhs: &Rhs,
+                operation: Operation,
+                fill_rules: FillRules,
+            ) -> Result<TracedMultiPolygon<$t>, BooleanOpError> {
+                integer_traced_boolean_operation(self.polygons(), rhs.polygons(), operation, fill_rules)
+            }
+
+            fn boolean_all_operation(
+                &self,
+                rhs: &Rhs,
+                fill_rules: FillRules,
+            ) -> Result<BooleanResults<$t>, BooleanOpError> {
+                integer_boolean_all_operation(self.polygons(), rhs.polygons(), fill_rules)
+            }
+
+            fn divide_operation(&self, rhs: &Rhs, fill_rules: FillRules) -> Result<Division<$t>, BooleanOpError> {
+                integer_divide_operation(self.polygons(), rhs.polygons(), fill_rules)
+            }
+
+            fn intersection_full_operation(
+                &self,
+                rhs: &Rhs,
+                fill_rules: FillRules,
+            ) -> Result<GeometryCollection<$t>, BooleanOpError> {
+                integer_intersection_full_operation(self.polygons(), rhs.polygons(), fill_rules)
+            }
+        }
+    };
+    ($($t:ty),*) => {
+        $(
+            impl_boolean_op_for_integers!(@operands $t);
+            impl_boolean_op_for_integers!(@impl $t, Polygon<$t>, Polygon<$t>);
+            impl_boolean_op_for_integers!(@impl $t, Polygon<$t>, MultiPolygon<$t>);
+            impl_boolean_op_for_integers!(@impl $t, MultiPolygon<$t>, MultiPolygon<$t>);
+            impl_boolean_op_for_integers!(@impl $t, MultiPolygon<$t>, Polygon<$t>);
+        )*
+    };
+}
+
+impl_boolean_op_for_integers!(i32, i64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo_types::polygon;
+
+    #[test]
+    fn test_rounded_intersection() {
+        assert_eq!(rounded_intersection(((0, 0), (4, 4)), ((0, 4), (4, 0))), Some((2, 2)));
+        // (1.5, 1.5) rounds up, (-0.5, 0) as well.
+        assert_eq!(rounded_intersection(((0, 0), (3, 3)), ((0, 3), (3, 0))), Some((2, 2)));
+        assert_eq!(
+            rounded_intersection(((-1, 1), (0, -1)), ((-2, 0), (2, 0))),
+            Some((0, 0))
+        );
+        assert_eq!(rounded_intersection(((0, 0), (1, 1)), ((2, 0), (3, -1))), None);
+        assert_eq!(rounded_intersection(((0, 0), (2, 0)), ((1, 0), (3, 0))), None);
+        // Large coordinates, where the products exceed 64 bits.
+        let m = MAX_INTEGER_COORD;
+        assert_eq!(
+            rounded_intersection(((-m, -m), (m, m)), ((-m, m), (m, -m))),
+            Some((0, 0))
+        );
+        assert_eq!(
+            rounded_intersection(((-m, -m + 1), (m, m)), ((-m, m), (m, -m))),
+            Some((0, 0))
+        );
+    }
+
+    #[test]
+    fn test_passes_half_open_pixels() {
+        assert!(passes_pixel((0, 0), (4, 1), (2, 0)));
+        assert!(passes_pixel((0, 0), (4, 1), (4, 1)));
+        assert!(!passes_pixel((0, 0), (4, 1), (1, 1)));
+        // At x = 2, the segment is on the boundary between two cells, which belongs to the
+        // upper one.
+        assert!(passes_pixel((0, 0), (4, 1), (2, 1)));
+        assert!(!passes_pixel((0, 0), (2, 2), (0, 1)));
+        // The segment runs along the lower boundary of the upper cells, which belongs to them,
+        // but the diagonal only touches the corners of the cells next to its pixels.
+        assert!(passes_pixel((0, 0), (1, 1), (1, 1)));
+        assert!(!passes_pixel((0, 0), (1, 1), (0, 1)));
+        assert!(!passes_pixel((0, 0), (1, 1), (1, 0)));
+        assert!(!passes_pixel((0, 0), (0, 4), (1, 2)));
+    }
+
+    #[test]
+    fn test_crossing_edges_are_snapped() {
+        // The edges of the triangles cross at (1.5, 1.5), which is rounded to (2, 2).
+        let subject = polygon![(x: 0, y: 0), (x: 3, y: 3), (x: 0, y: 3), (x: 0, y: 0)];
+        let clipping = polygon![(x: 0, y: 3), (x: 3, y: 0), (x: 3, y: 3), (x: 0, y: 3)];
+        let snapped = snap_round(&[subject], &[clipping]).unwrap();
+        assert_eq!(
+            snapped.polygons[0][0].exterior().0,
+            vec![
+                Coord { x: 0., y: 0. },
+                Coord { x: 2., y: 2. },
+                Coord { x: 3., y: 3. },
+                Coord { x: 0., y: 3. },
+                Coord { x: 0., y: 0. },
+            ]
+        );
+        assert_eq!(snapped.origins[0][0].rings[0].edges, vec![0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_coordinates_out_of_range() {
+        let m = MAX_INTEGER_COORD + 1;
+        let subject = polygon![(x: 0, y: 0), (x: m, y: 0), (x: m, y: 1), (x: 0, y: 0)];
+        let clipping = polygon![(x: 0, y: 0), (x: 1, y: 0), (x: 1, y: 1), (x: 0, y: 0)];
+        assert_eq!(subject.try_union(&clipping), Err(BooleanOpError::CoordinateOutOfRange));
+    }
+}
@@ -0,0 +1,371 @@
+use super::connect_edges::connect_edges;
+use super::fill_queue::fill_queue;
+use super::helper::Float;
+use super::kernel::{DefaultKernel, Kernel};
+use super::subdivide_segments::subdivide;
+use super::sweep_event::{EdgeType, EventArena, SweepEvent};
+use super::{
+    all_coords_finite, bounding_boxes_disjoint, contours_to_multi_polygon, empty_bounding_box, BooleanOpError,
+    FillRules, Operation,
+};
+use geo_types::{Coord, Geometry, GeometryCollection, LineString, Polygon};
+
+/// Location where the boundaries of the operands meet, collected from the events.
+struct Touch<F>
+where
+    F: Float,
+{
+    point: Coord<F>,
+    /// Whether the boundary of the subject and of the clipping polygons pass the point.
+    boundaries: [bool; 2],
+    /// Whether the point is part of the polygons or the lines of the result.
+    covered: bool,
+}
+
+pub(crate) fn intersection_full_operation<F>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    fill_rules: FillRules,
+) -> Result<GeometryCollection<F>, BooleanOpError>
+where
+    F: Float,
+{
+    if !all_coords_finite(subject) || !all_coords_finite(clipping) {
+        return Err(BooleanOpError::NonFiniteCoordinate);
+    }
+
+    let mut sbbox = empty_bounding_box();
+    let mut cbbox = sbbox;
+
+    let mut events = EventArena::new();
+    let mut event_queue = fill_queue(
+        subject,
+        clipping,
+        &mut sbbox,
+        &mut cbbox,
+        Operation::Intersection,
+        &mut events,
+    );
+
+    // Bounding boxes which only touch are not disjoint, so this only skips operands which
+    // cannot even touch each other.
+    if bounding_boxes_disjoint(&sbbox, &cbbox) {
+        return Ok(GeometryCollection(Vec::new()));
+    }
+
+    let sorted_events = subdivide(
+        &mut events,
+        &mut event_queue,
+        &sbbox,
+        &cbbox,
+        Operation::Intersection,
+        fill_rules,
+    )?;
+
+    let mut lines: Vec<LineString<F>> = Vec::new();
+    let mut touches = Vec::with_capacity(sorted_events.len());
+    for &id in &sorted_events {
+        let event = &events[id];
+        let other_event = events.other(id).ok_or(BooleanOpError::MissingOtherEvent)?;
+        // The fields of a segment are computed for its left event only.
+        let left = if event.is_left() { event } else { other_event };
+        let boundaries = operand_boundaries(left, fill_rules);
+        // Segments of both boundaries which are not a boundary of the polygons of the result,
+        // i.e., both interiors are on different sides, are shared lines of the result.
+        let is_shared_line = boundaries == [true, true] && !left.is_in_result();
+
+        if event.is_left() && is_shared_line {
+            add_line_segment(&mut lines, event.point, other_event.point);
+        }
+        touches.push(Touch {
+            point: event.point,
+            boundaries,
+            covered: left.is_in_result() || is_shared_line,
+        });
+    }
+
+    let points = isolated_points(touches);
+    let contours = connect_edges(&mut events, &sorted_events)?;
+    let polygons = contours_to_multi_polygon(&contours);
+
+    Ok(GeometryCollection(
+        polygons
+            .0
+            .into_iter()
+            .map(Geometry::Polygon)
+            .chain(lines.into_iter().map(Geometry::LineString))
+            .chain(points.into_iter().map(|point| Geometry::Point(point.into())))
+            .collect(),
+    ))
+}
+
+/// Whether the segment of the left event `event` is part of the boundary of the subject and
+/// of the clipping polygons respectively.
+///
+/// Of a group of coincident segments, only the upper one, whose edge type is
+/// `SameTransition` or `DifferentTransition`, represents the group. Its own operand and the
+/// other one are compared between the windings below the group and above it.
+fn operand_boundaries<F>(event: &SweepEvent<F>, fill_rules: FillRules) -> [bool; 2]
+where
+    F: Float,
+{
+    let edge_type = event.get_edge_type();
+    if edge_type == EdgeType::NonContributing {
+        return [false, false];
+    }
+    let (own_fill_rule, other_fill_rule) = (
+        fill_rules.of_operand(event.is_subject),
+        fill_rules.of_operand(!event.is_subject),
+    );
+    let (group_winding_below, group_other_winding) = event.get_group_windings();
+    let is_own_boundary = own_fill_rule.is_inside(group_winding_below)
+        != own_fill_rule.is_inside(event.get_winding_below() + event.get_winding());
+    let is_other_boundary = edge_type != EdgeType::Normal
+        && other_fill_rule.is_inside(group_other_winding) != other_fill_rule.is_inside(event.get_other_winding());
+
+    if event.is_subject {
+        [is_own_boundary, is_other_boundary]
+    } else {
+        [is_other_boundary, is_own_boundary]
+    }
+}
+
+/// Adds the segment to the lines: it continues a line ending at one of its endpoints, joins
+/// the two lines ending at its endpoints, or starts a new line. Segments are added in the
+/// order of the sweep, so they reach a line at either end, and the lines they connect may
+/// have been started separately.
+fn add_line_segment<F>(lines: &mut Vec<LineString<F>>, start: Coord<F>, end: Coord<F>)
+where
+    F: Float,
+{
+    let at_start = line_ending_at(lines, start, None);
+    let at_end = line_ending_at(lines, end, at_start);
+    match (at_start, at_end) {
+        (Some(i), Some(j)) => {
+            // The lines are joined into the one started first, keeping its direction.
+            let ((first, at_first), (second, at_second)) = if i < j {
+                ((i, start), (j, end))
+            } else {
+                ((j, end), (i, start))
+            };
+            let mut other = lines.remove(second).0;
+            let line = &mut lines[first].0;
+            if line.last() == Some(&at_first) {
+                if other.first() != Some(&at_second) {
+                    other.reverse();
+                }
+                line.append(&mut other);
+            } else {
+                if other.last() != Some(&at_second) {
+                    other.reverse();
+                }
+                other.append(line);
+                *line = other;
+            }
+        }
+        (Some(i), None) => extend_line(&mut lines[i], start, end),
+        (None, Some(j)) => extend_line(&mut lines[j], end, start),
+        (None, None) => lines.push(LineString(vec![start, end])),
+    }
+}
+
+/// Index of the most recent open line other than `excluded` which starts or ends at `point`.
+fn line_ending_at<F>(lines: &[LineString<F>], point: Coord<F>, excluded: Option<usize>) -> Option<usize>
+where
+    F: Float,
+{
+    lines.iter().enumerate().rev().find_map(|(i, line)| {
+        let (first, last) = (line.0.first(), line.0.last());
+        (Some(i) != excluded && first != last && (first == Some(&point) || last == Some(&point))).then_some(i)
+    })
+}
+
+/// Extends the line ending at `point` by the segment from `point` to `next`.
+fn extend_line<F>(line: &mut LineString<F>, point: Coord<F>, next: Coord<F>)
+where
+    F: Float,
+{
+    if line.0.last() == Some(&point) {
+        line.0.push(next);
+    } else {
+        line.0.insert(0, next);
+    }
+}
+
+/// Points where the boundaries of both operands meet, but which are not covered by the
+/// polygons or the lines of the result.
+fn isolated_points<F>(mut touches: Vec<Touch<F>>) -> Vec<Coord<F>>
+where
+    F: Float,
+{
+    // Events at the same point are usually, but not necessarily processed consecutively, e.g.
+    // if a segment is divided at a point which has been processed already.
+    touches.sort_by(|a, b| DefaultKernel::compare_points(a.point, b.point));
+
+    let mut points = Vec::new();
+    let mut remaining = touches.as_slice();
+    while let Some(first) = remaining.first() {
+        let split = remaining
+            .iter()
+            .position(|touch| touch.point != first.point)
+            .unwrap_or(remaining.len());
+        let (group, rest) = remaining.split_at(split);
+        remaining = rest;
+
+        let touches_subject = group.iter().any(|touch| touch.boundaries[0]);
+        let touches_clipping = group.iter().any(|touch| touch.boundaries[1]);
+        if touches_subject && touches_clipping && !group.iter().any(|touch| touch.covered) {
+            points.push(first.point);
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::helper::test::xy;
+    use super::*;
+    use geo_types::{line_string, point, polygon};
+
+    fn square(x: f64, y: f64, size: f64) -> Polygon<f64> {
+        polygon![
+            (x: x, y: y),
+            (x: x + size, y: y),
+            (x: x + size, y: y + size),
+            (x: x, y: y + size),
+            (x: x, y: y),
+        ]
+    }
+
+    fn intersection_full(a: Polygon<f64>, b: Polygon<f64>) -> Vec<Geometry<f64>> {
+        intersection_full_operation(&[a], &[b], FillRules::default()).unwrap().0
+    }
+
+    #[test]
+    fn test_shared_edge() {
+        let result = intersection_full(square(0., 0., 1.), square(1., 0., 1.));
+        assert_eq!(
+            result,
+            vec![Geometry::LineString(line_string![(x: 1., y: 0.), (x: 1., y: 1.)])]
+        );
+    }
+
+    #[test]
+    fn test_partially_shared_edges_are_joined() {
+        // The shared boundary is divided at the corner of the smaller square.
+        let result = intersection_full(square(0., 0., 2.), square(2., 1., 1.));
+        assert_eq!(
+            result,
+            vec![Geometry::LineString(line_string![(x: 2., y: 1.), (x: 2., y: 2.)])]
+        );
+
+        let l_shape = polygon![
+            (x: 1., y: 0.), (x: 3., y: 0.), (x: 3., y: 3.), (x: 0., y: 3.), (x: 0., y: 1.), (x: 1., y: 1.), (x: 1., y: 0.),
+        ];
+        let result = intersection_full(square(0., 0., 1.), l_shape);
+        assert_eq!(
+            result,
+            vec![Geometry::LineString(
+                line_string![(x: 0., y: 1.), (x: 1., y: 1.), (x: 1., y: 0.)]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_shared_polyline_is_one_line() {
+        // The segments of the shared boundary arrive out of order: the lower segment starting
+        // at (1, 1) starts a second line, which the upper one joins to the first line.
+        let polyline = [(0., 2.), (2., 2.), (1., 1.), (3., 0.)];
+        let above = Polygon::new(
+            polyline
+                .iter()
+                .chain(&[(3., 3.), (0., 3.), (0., 2.)])
+                .copied()
+                .collect(),
+            vec![],
+        );
+        let below = Polygon::new(
+            polyline
+                .iter()
+                .chain(&[(3., -1.), (0., -1.), (0., 2.)])
+                .copied()
+                .collect(),
+            vec![],
+        );
+        let result = intersection_full(above, below);
+        assert_eq!(result, vec![Geometry::LineString(polyline.iter().copied().collect())]);
+    }
+
+    #[test]
+    fn test_add_line_segment() {
+        let (a, b, c, d) = (xy(0, 0), xy(1, 0), xy(2, 1), xy(3, 0));
+        let mut lines = Vec::new();
+        add_line_segment(&mut lines, a, b);
+        add_line_segment(&mut lines, c, d);
+        assert_eq!(lines.len(), 2);
+        add_line_segment(&mut lines, b, c);
+        assert_eq!(lines, vec![LineString(vec![a, b, c, d])]);
+
+        // A segment ending at the start of a line continues it.
+        let mut lines = vec![LineString(vec![b, c])];
+        add_line_segment(&mut lines, a, b);
+        assert_eq!(lines, vec![LineString(vec![a, b, c])]);
+
+        // A segment connecting both ends of a line closes it.
+        add_line_segment(&mut lines, a, c);
+        assert_eq!(lines, vec![LineString(vec![c, a, b, c])]);
+        add_line_segment(&mut lines, c, d);
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_shared_corner() {
+        let result = intersection_full(square(0., 0., 1.), square(1., 1., 1.));
+        assert_eq!(result, vec![Geometry::Point(point!(x: 1., y: 1.))]);
+
+        // A corner touching the interior of an edge.
+        let triangle = polygon![(x: 1., y: 0.5), (x: 2., y: 0.), (x: 2., y: 1.), (x: 1., y: 0.5)];
+        let result = intersection_full(square(0., 0., 1.), triangle);
+        assert_eq!(result, vec![Geometry::Point(point!(x: 1., y: 0.5))]);
+    }
+
+    #[test]
+    fn test_overlap_has_no_lower_dimensional_parts() {
+        let result = intersection_full(square(0., 0., 2.), square(1., 1., 2.));
+        assert_eq!(result, vec![Geometry::Polygon(square(1., 1., 1.))]);
+
+        // The shared edge is a boundary of the result polygon.
+        let result = intersection_full(square(0., 0., 2.), square(0., 0., 1.));
+        assert_eq!(result, vec![Geometry::Polygon(square(0., 0., 1.))]);
+    }
+
+    #[test]
+    fn test_polygon_with_touching_parts() {
+        // The second square overlaps the first one and shares an edge and a corner with others.
+        let subject = [square(0., 0., 1.), square(3., 0., 1.), square(5., 5., 1.)];
+        let clipping = [square(1., 0., 2.5), square(6., 6., 1.)];
+        let result = intersection_full_operation(&subject, &clipping, FillRules::default()).unwrap();
+
+        assert_eq!(
+            result.0,
+            vec![
+                Geometry::Polygon(
+                    polygon![(x: 3., y: 0.), (x: 3.5, y: 0.), (x: 3.5, y: 1.), (x: 3., y: 1.), (x: 3., y: 0.)]
+                ),
+                Geometry::LineString(line_string![(x: 1., y: 0.), (x: 1., y: 1.)]),
+                Geometry::Point(point!(x: 6., y: 6.)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disjoint_operands() {
+        assert!(intersection_full(square(0., 0., 1.), square(2., 0., 1.)).is_empty());
+        assert!(
+            intersection_full_operation(&[], &[square(0., 0., 1.)], FillRules::default())
+                .unwrap()
+                .0
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use super::error::BooleanOpError;
+use super::helper::{Float, OrderedField};
+use super::segment_intersection::{exact_intersection, inexact_intersection, intersection, LineIntersection};
+use super::signed_area::signed_area;
+use super::sweep_line::SplaySweepLine;
+use super::{traced_boolean_operation, FillRules, Operation};
+use geo_types::{Coord, MultiPolygon, Polygon};
+use std::cmp::Ordering;
+
+/// Geometric predicates and constructions used by the sweep.
+///
+/// The kernel is selected by the type of the `EventArena`, so all events of an operation are
+/// ordered and intersected consistently. The methods have no receiver, kernels are plain
+/// marker types like `DefaultKernel`.
+pub trait Kernel<F>
+where
+    F: OrderedField,
+{
+    /// Orientation of `p2` relative to the line from `p0` to `p1`, i.e., the sign of the
+    /// signed area of the triangle: `Greater` if the points are in counter-clockwise order,
+    /// `Less` if they are in clockwise order and `Equal` if they are collinear.
+    fn orientation(p0: Coord<F>, p1: Coord<F>, p2: Coord<F>) -> Ordering;
+
+    /// Intersection of the segments from `a1` to `a2` and from `b1` to `b2`. Overlaps are
+    /// reported in the direction of the first segment.
+    fn intersection(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> LineIntersection<F>;
+
+    /// Order in which the sweep line passes the points, i.e., by x and then by y.
+    fn compare_points(p: Coord<F>, q: Coord<F>) -> Ordering {
+        p.x.partial_cmp(&q.x)
+            .unwrap_or(Ordering::Equal)
+            .then(p.y.partial_cmp(&q.y).unwrap_or(Ordering::Equal))
+    }
+}
+
+/// The kernel used by `BooleanOp`: orientations are exact (see the `robust` crate), while
+/// intersections are computed in floating point arithmetic. With the `exact-intersection`
+/// feature, it is identical to `RobustKernel`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultKernel;
+
+impl<F> Kernel<F> for DefaultKernel
+where
+    F: Float,
+{
+    fn orientation(p0: Coord<F>, p1: Coord<F>, p2: Coord<F>) -> Ordering {
+        robust_orientation(p0, p1, p2)
+    }
+
+    fn intersection(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> LineIntersection<F> {
+        intersection(a1, a2, b1, b2)
+    }
+}
+
+/// Kernel computing everything natively in the arithmetic of the scalar type. For floating
+/// point numbers, it is the fastest, but nearly collinear points can be misclassified, which
+/// may produce invalid results or make the sweep fail with
+/// `BooleanOpError::SweepLineInconsistency` on degenerate input. For types with exact
+/// arithmetic like rationals, all predicates and intersection points are exact.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FastKernel;
+
+impl<F> Kernel<F> for FastKernel
+where
+    F: OrderedField,
+{
+    fn orientation(p0: Coord<F>, p1: Coord<F>, p2: Coord<F>) -> Ordering {
+        let area = (p0.x - p2.x) * (p1.y - p2.y) - (p0.y - p2.y) * (p1.x - p2.x);
+        area.partial_cmp(&F::zero()).unwrap_or(Ordering::Equal)
+    }
+
+    fn intersection(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> LineIntersection<F> {
+        inexact_intersection(a1, a2, b1, b2)
+    }
+}
+
+/// Kernel with exact orientations and exactly classified intersections, whose intersection
+/// points are the correctly rounded exact intersections. It is the slowest kernel.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RobustKernel;
+
+impl<F> Kernel<F> for RobustKernel
+where
+    F: Float,
+{
+    fn orientation(p0: Coord<F>, p1: Coord<F>, p2: Coord<F>) -> Ordering {
+        robust_orientation(p0, p1, p2)
+    }
+
+    fn intersection(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> LineIntersection<F> {
+        exact_intersection(a1, a2, b1, b2)
+    }
+}
+
+fn robust_orientation<F>(p0: Coord<F>, p1: Coord<F>, p2: Coord<F>) -> Ordering
+where
+    F: Float,
+{
+    signed_area(p0, p1, p2).partial_cmp(&0.).unwrap_or(Ordering::Equal)
+}
+
+/// Computes a boolean operation like `BooleanOp::try_boolean_with_fill_rules`, using the
+/// kernel `K` instead of `DefaultKernel`, e.g. `try_boolean_with_kernel::<_, RobustKernel>`.
+pub fn try_boolean_with_kernel<F, K>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    operation: Operation,
+    fill_rules: FillRules,
+) -> Result<MultiPolygon<F>, BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    traced_boolean_operation::<F, SplaySweepLine, K>(subject, clipping, operation, fill_rules)
+        .map(|traced| traced.multi_polygon)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::helper::test::xy;
+    use super::*;
+
+    fn check_orientation<K: Kernel<f64>>() {
+        assert_eq!(K::orientation(xy(0, 0), xy(1, 0), xy(0, 1)), Ordering::Greater);
+        assert_eq!(K::orientation(xy(0, 0), xy(0, 1), xy(1, 0)), Ordering::Less);
+        assert_eq!(K::orientation(xy(0, 0), xy(1, 1), xy(2, 2)), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_orientation() {
+        check_orientation::<DefaultKernel>();
+        check_orientation::<FastKernel>();
+        check_orientation::<RobustKernel>();
+
+        // The floating point computation cancels out the tiny offset of the last point.
+        let (p0, p1) = (xy(0.5, 0.5), xy(12, 12));
+        let p2 = xy(24, 24.000000000000004);
+        assert_eq!(FastKernel::orientation(p0, p1, p2), Ordering::Equal);
+        assert_eq!(RobustKernel::orientation(p0, p1, p2), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_compare_points() {
+        assert_eq!(DefaultKernel::compare_points(xy(0, 1), xy(1, 0)), Ordering::Less);
+        assert_eq!(DefaultKernel::compare_points(xy(1, 1), xy(1, 0)), Ordering::Greater);
+        assert_eq!(DefaultKernel::compare_points(xy(1, 0), xy(1, 0)), Ordering::Equal);
+    }
+}
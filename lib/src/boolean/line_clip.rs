@@ -0,0 +1,351 @@
+use super::fill_queue::{fill_queue, process_line_segment};
+use super::helper::Float;
+use super::subdivide_segments::subdivide;
+use super::sweep_event::EventArena;
+use super::{all_coords_finite, empty_bounding_box, BooleanOpError, FillRule, FillRules, Operation};
+use geo_types::{Coord, LineString, MultiLineString, MultiPolygon, Polygon};
+use std::cmp::Ordering;
+
+/// Policy for parts of the linework which lie exactly on the boundary of the polygons.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoundaryPolicy {
+    /// Boundary parts are reported as inside.
+    Inside,
+    /// Boundary parts are reported as outside.
+    Outside,
+    /// Boundary parts are neither reported as inside nor as outside.
+    Discard,
+}
+
+/// Result of clipping linework against polygons.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ClippedLines<F>
+where
+    F: Float,
+{
+    pub inside: MultiLineString<F>,
+    pub outside: MultiLineString<F>,
+}
+
+pub trait LineClip<F, Rhs>
+where
+    F: Float,
+{
+    /// Splits the linework into the parts inside and outside of `rhs`, reporting degenerate
+    /// input or internal inconsistencies of the sweep as `BooleanOpError` instead of panicking.
+    ///
+    /// The parts preserve the direction of the input lines, and consecutive parts of the same
+    /// class are joined into one line string.
+    fn try_clip(&self, rhs: &Rhs, boundary: BoundaryPolicy) -> Result<ClippedLines<F>, BooleanOpError>;
+
+    /// Infallible variant of `try_clip`.
+    ///
+    /// Panics if the operation fails, e.g. because an input coordinate is not finite.
+    fn clip(&self, rhs: &Rhs, boundary: BoundaryPolicy) -> ClippedLines<F> {
+        match self.try_clip(rhs, boundary) {
+            Ok(result) => result,
+            Err(err) => panic!("Line clipping failed: {}", err),
+        }
+    }
+
+    fn clip_inside(&self, rhs: &Rhs, boundary: BoundaryPolicy) -> MultiLineString<F> {
+        self.clip(rhs, boundary).inside
+    }
+
+    fn clip_outside(&self, rhs: &Rhs, boundary: BoundaryPolicy) -> MultiLineString<F> {
+        self.clip(rhs, boundary).outside
+    }
+}
+
+impl<F> LineClip<F, Polygon<F>> for LineString<F>
+where
+    F: Float,
+{
+    fn try_clip(&self, rhs: &Polygon<F>, boundary: BoundaryPolicy) -> Result<ClippedLines<F>, BooleanOpError> {
+        clip_lines(std::slice::from_ref(self), std::slice::from_ref(rhs), boundary)
+    }
+}
+
+impl<F> LineClip<F, MultiPolygon<F>> for LineString<F>
+where
+    F: Float,
+{
+    fn try_clip(&self, rhs: &MultiPolygon<F>, boundary: BoundaryPolicy) -> Result<ClippedLines<F>, BooleanOpError> {
+        clip_lines(std::slice::from_ref(self), rhs.0.as_slice(), boundary)
+    }
+}
+
+impl<F> LineClip<F, Polygon<F>> for MultiLineString<F>
+where
+    F: Float,
+{
+    fn try_clip(&self, rhs: &Polygon<F>, boundary: BoundaryPolicy) -> Result<ClippedLines<F>, BooleanOpError> {
+        clip_lines(self.0.as_slice(), std::slice::from_ref(rhs), boundary)
+    }
+}
+
+impl<F> LineClip<F, MultiPolygon<F>> for MultiLineString<F>
+where
+    F: Float,
+{
+    fn try_clip(&self, rhs: &MultiPolygon<F>, boundary: BoundaryPolicy) -> Result<ClippedLines<F>, BooleanOpError> {
+        clip_lines(self.0.as_slice(), rhs.0.as_slice(), boundary)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Location {
+    Inside,
+    Outside,
+    Boundary,
+}
+
+/// Part of an input segment after the sweep has split it at all intersections.
+struct Piece<F>
+where
+    F: Float,
+{
+    segment_id: u32,
+    left: Coord<F>,
+    right: Coord<F>,
+    location: Location,
+}
+
+fn clip_lines<F>(
+    lines: &[LineString<F>],
+    polygons: &[Polygon<F>],
+    boundary: BoundaryPolicy,
+) -> Result<ClippedLines<F>, BooleanOpError>
+where
+    F: Float,
+{
+    let line_coords_finite = lines
+        .iter()
+        .flat_map(|line| line.0.iter())
+        .all(|c| c.x.is_finite() && c.y.is_finite());
+    if !line_coords_finite || !all_coords_finite(polygons) {
+        return Err(BooleanOpError::NonFiniteCoordinate);
+    }
+
+    let mut sbbox = empty_bounding_box();
+    let mut cbbox = sbbox;
+
+    // The polygons are the subject, so their contour ids are 1..=polygons.len(). Every line
+    // segment gets a unique contour id above that range, which allows to map the pieces back.
+    let mut events = EventArena::new();
+    let mut event_queue = fill_queue(polygons, &[], &mut sbbox, &mut cbbox, Operation::Union, &mut events);
+    let first_segment_id = polygons.len() as u32 + 1;
+    for (segment_id, line) in (first_segment_id..).zip(lines.iter().flat_map(|line| line.lines())) {
+        process_line_segment(line, segment_id, &mut events, &mut event_queue, &mut cbbox);
+    }
+
+    // The operation is irrelevant for the classification of the linework, but in contrast to
+    // intersection and difference a union never terminates the sweep early.
+    let sorted_events = subdivide(
+        &mut events,
+        &mut event_queue,
+        &sbbox,
+        &cbbox,
+        Operation::Union,
+        FillRules::default(),
+    )?;
+
+    let mut pieces = Vec::new();
+    for &id in &sorted_events {
+        let event = &events[id];
+        if !event.is_left() || event.is_subject {
+            continue;
+        }
+        let other_event = events.other(id).ok_or(BooleanOpError::MissingOtherEvent)?;
+        // Line segments are stacked above all coincident polygon edges, so the polygon
+        // windings below the coincident group and at the segment itself tell whether
+        // the segment is on the boundary.
+        let (_, polygon_winding_below) = event.get_group_windings();
+        let inside_below = FillRule::EvenOdd.is_inside(polygon_winding_below);
+        let inside_above = FillRule::EvenOdd.is_inside(event.get_other_winding());
+        let location = match (inside_below, inside_above) {
+            (true, true) => Location::Inside,
+            (false, false) => Location::Outside,
+            _ => Location::Boundary,
+        };
+        pieces.push(Piece {
+            segment_id: event.contour_id,
+            left: event.point,
+            right: other_event.point,
+            location,
+        });
+    }
+
+    // Events are processed from left to right, so sorting stably by segment yields the pieces
+    // of every segment in left to right order.
+    pieces.sort_by_key(|piece| piece.segment_id);
+
+    let mut builder = LineBuilder::new(boundary);
+    let mut remaining = pieces.as_slice();
+    let mut segment_id = first_segment_id;
+    for line in lines {
+        builder.finish_line();
+        for segment in line.lines() {
+            let split = remaining
+                .iter()
+                .position(|piece| piece.segment_id != segment_id)
+                .unwrap_or(remaining.len());
+            let (segment_pieces, rest) = remaining.split_at(split);
+            remaining = rest;
+            segment_id += 1;
+
+            if is_left_to_right(segment.start, segment.end) {
+                segment_pieces
+                    .iter()
+                    .for_each(|piece| builder.add(piece.left, piece.right, piece.location));
+            } else {
+                segment_pieces
+                    .iter()
+                    .rev()
+                    .for_each(|piece| builder.add(piece.right, piece.left, piece.location));
+            }
+        }
+    }
+    builder.finish_line();
+
+    Ok(ClippedLines {
+        inside: MultiLineString(builder.inside),
+        outside: MultiLineString(builder.outside),
+    })
+}
+
+/// Whether `start` is processed before `end` by the sweep.
+fn is_left_to_right<F>(start: Coord<F>, end: Coord<F>) -> bool
+where
+    F: Float,
+{
+    match start.x.partial_cmp(&end.x) {
+        Some(Ordering::Less) => true,
+        Some(Ordering::Greater) => false,
+        _ => start.y < end.y,
+    }
+}
+
+/// Joins consecutive pieces of the same class into line strings.
+struct LineBuilder<F>
+where
+    F: Float,
+{
+    boundary: BoundaryPolicy,
+    inside: Vec<LineString<F>>,
+    outside: Vec<LineString<F>>,
+    current: Vec<Coord<F>>,
+    current_location: Option<Location>,
+}
+
+impl<F> LineBuilder<F>
+where
+    F: Float,
+{
+    fn new(boundary: BoundaryPolicy) -> LineBuilder<F> {
+        LineBuilder {
+            boundary,
+            inside: Vec::new(),
+            outside: Vec::new(),
+            current: Vec::new(),
+            current_location: None,
+        }
+    }
+
+    fn add(&mut self, start: Coord<F>, end: Coord<F>, location: Location) {
+        let location = match (location, self.boundary) {
+            (Location::Boundary, BoundaryPolicy::Inside) => Location::Inside,
+            (Location::Boundary, BoundaryPolicy::Outside) => Location::Outside,
+            (location, _) => location,
+        };
+        if self.current_location != Some(location) || self.current.last() != Some(&start) {
+            self.finish_line();
+            self.current.push(start);
+            self.current_location = Some(location);
+        }
+        self.current.push(end);
+    }
+
+    fn finish_line(&mut self) {
+        let points = std::mem::take(&mut self.current);
+        match self.current_location.take() {
+            Some(Location::Inside) => self.inside.push(LineString(points)),
+            Some(Location::Outside) => self.outside.push(LineString(points)),
+            Some(Location::Boundary) | None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo_types::{line_string, polygon};
+
+    fn square() -> Polygon<f64> {
+        polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)]
+    }
+
+    #[test]
+    fn test_crossing_line() {
+        let line = line_string![(x: -1., y: 1.), (x: 3., y: 1.)];
+
+        let result = line.clip(&square(), BoundaryPolicy::Inside);
+
+        assert_eq!(
+            result.inside,
+            MultiLineString(vec![line_string![(x: 0., y: 1.), (x: 2., y: 1.)]])
+        );
+        assert_eq!(
+            result.outside,
+            MultiLineString(vec![
+                line_string![(x: -1., y: 1.), (x: 0., y: 1.)],
+                line_string![(x: 2., y: 1.), (x: 3., y: 1.)],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_direction_is_preserved() {
+        let line = line_string![(x: 3., y: 1.), (x: 1., y: 1.), (x: 1., y: 3.)];
+
+        let result = line.clip(&square(), BoundaryPolicy::Inside);
+
+        assert_eq!(
+            result.inside,
+            MultiLineString(vec![line_string![(x: 2., y: 1.), (x: 1., y: 1.), (x: 1., y: 2.)]])
+        );
+        assert_eq!(
+            result.outside,
+            MultiLineString(vec![
+                line_string![(x: 3., y: 1.), (x: 2., y: 1.)],
+                line_string![(x: 1., y: 2.), (x: 1., y: 3.)],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_boundary_policy() {
+        let line = line_string![(x: -1., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)];
+
+        let inside = line.clip(&square(), BoundaryPolicy::Inside);
+        let outside = line.clip(&square(), BoundaryPolicy::Outside);
+        let discard = line.clip(&square(), BoundaryPolicy::Discard);
+
+        assert_eq!(
+            inside.inside,
+            MultiLineString(vec![line_string![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)]])
+        );
+        assert_eq!(
+            outside.outside,
+            MultiLineString(vec![line_string![(x: -1., y: 0.), (x: 0., y: 0.), (x: 1., y: 0.)]])
+        );
+        assert_eq!(
+            discard.inside,
+            MultiLineString(vec![line_string![(x: 1., y: 0.), (x: 1., y: 1.)]])
+        );
+        assert_eq!(
+            discard.outside,
+            MultiLineString(vec![line_string![(x: -1., y: 0.), (x: 0., y: 0.)]])
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use super::connect_edges::connect_edges;
+use super::fill_queue::fill_queue;
+use super::helper::Float;
+use super::subdivide_segments::subdivide;
+use super::sweep_event::EventArena;
+use super::{
+    all_coords_finite, contours_to_multi_polygon, empty_bounding_box, BooleanOpError, FillRule, FillRules, Operation,
+};
+use geo_types::{MultiPolygon, Polygon};
+
+pub trait MakeValid<F>
+where
+    F: Float,
+{
+    /// Resolves self-intersections, overlapping parts and holes crossing their shell by
+    /// overlaying the geometry with itself. The regions considered inside are determined
+    /// by `fill_rule`, the result consists of valid polygons.
+    fn try_make_valid_with_fill_rule(&self, fill_rule: FillRule) -> Result<MultiPolygon<F>, BooleanOpError>;
+
+    /// Infallible variant of `try_make_valid_with_fill_rule`.
+    ///
+    /// Panics if the operation fails, e.g. because an input coordinate is not finite.
+    fn make_valid_with_fill_rule(&self, fill_rule: FillRule) -> MultiPolygon<F> {
+        match self.try_make_valid_with_fill_rule(fill_rule) {
+            Ok(result) => result,
+            Err(err) => panic!("Making geometry valid failed: {}", err),
+        }
+    }
+
+    fn try_make_valid(&self) -> Result<MultiPolygon<F>, BooleanOpError> {
+        self.try_make_valid_with_fill_rule(FillRule::EvenOdd)
+    }
+
+    fn make_valid(&self) -> MultiPolygon<F> {
+        self.make_valid_with_fill_rule(FillRule::EvenOdd)
+    }
+}
+
+impl<F> MakeValid<F> for Polygon<F>
+where
+    F: Float,
+{
+    fn try_make_valid_with_fill_rule(&self, fill_rule: FillRule) -> Result<MultiPolygon<F>, BooleanOpError> {
+        self_overlay(std::slice::from_ref(self), fill_rule)
+    }
+}
+
+impl<F> MakeValid<F> for MultiPolygon<F>
+where
+    F: Float,
+{
+    fn try_make_valid_with_fill_rule(&self, fill_rule: FillRule) -> Result<MultiPolygon<F>, BooleanOpError> {
+        self_overlay(self.0.as_slice(), fill_rule)
+    }
+}
+
+/// Runs the polygons as a single operand through the sweep, which results in the region
+/// they cover according to the fill rule.
+pub(crate) fn self_overlay<F>(polygons: &[Polygon<F>], fill_rule: FillRule) -> Result<MultiPolygon<F>, BooleanOpError>
+where
+    F: Float,
+{
+    if !all_coords_finite(polygons) {
+        return Err(BooleanOpError::NonFiniteCoordinate);
+    }
+
+    let mut sbbox = empty_bounding_box();
+    let mut cbbox = sbbox;
+
+    // A union with an empty clipping operand is the subject itself.
+    let mut events = EventArena::new();
+    let mut event_queue = fill_queue(polygons, &[], &mut sbbox, &mut cbbox, Operation::Union, &mut events);
+
+    let sorted_events = subdivide(
+        &mut events,
+        &mut event_queue,
+        &sbbox,
+        &cbbox,
+        Operation::Union,
+        FillRules::new(fill_rule, FillRule::EvenOdd),
+    )?;
+
+    let contours = connect_edges(&mut events, &sorted_events)?;
+
+    Ok(contours_to_multi_polygon(&contours))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo_types::polygon;
+
+    #[test]
+    fn test_bow_tie() {
+        let bow_tie = polygon![(x: 0., y: 0.), (x: 2., y: 2.), (x: 2., y: 0.), (x: 0., y: 2.), (x: 0., y: 0.)];
+
+        assert_eq!(
+            bow_tie.make_valid(),
+            MultiPolygon(vec![
+                polygon![(x: 0., y: 0.), (x: 1., y: 1.), (x: 0., y: 2.), (x: 0., y: 0.)],
+                polygon![(x: 1., y: 1.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 1., y: 1.)],
+            ])
+        );
+    }
+}
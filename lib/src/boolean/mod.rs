@@ -1,37 +1,483 @@
-use geo_types::{Coord, LineString, MultiPolygon, Polygon};
+use geo_types::{Coord, CoordNum, GeometryCollection, LineString, MultiPolygon, Polygon};
 
+mod boolean_all;
 pub mod compare_segments;
 pub mod compute_fields;
 mod connect_edges;
+mod divide;
 mod divide_segment;
+mod error;
+pub mod event_queue;
+mod expansion;
+mod expr;
 pub mod fill_queue;
 mod helper;
+mod integer;
+mod intersection_full;
+mod kernel;
+mod line_clip;
+mod make_valid;
+mod observer;
+mod operand;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod payload;
 pub mod possible_intersection;
+mod precision;
+mod provenance;
 mod segment_intersection;
 mod signed_area;
+mod stepper;
 pub mod subdivide_segments;
 pub mod sweep_event;
+pub mod sweep_line;
+mod unary_union;
+mod vertex_values;
 
-pub use helper::{BoundingBox, Float};
+pub use boolean_all::BooleanResults;
+pub use divide::Division;
+pub use error::BooleanOpError;
+pub use expr::Expr;
+pub use helper::{BoundingBox, Float, OrderedField};
+pub use integer::{IntegerCoord, MAX_INTEGER_COORD};
+pub use kernel::{try_boolean_with_kernel, DefaultKernel, FastKernel, Kernel, RobustKernel};
+pub use line_clip::{BoundaryPolicy, ClippedLines, LineClip};
+pub use make_valid::MakeValid;
+pub use observer::{try_boolean_observed, JsonTraceWriter, SweepObserver};
+#[cfg(feature = "rayon")]
+pub use parallel::{
+    par_boolean_many, par_intersection_many, par_unary_union, try_par_boolean_many, try_par_unary_union,
+};
+pub use payload::{try_boolean_with_payload, PayloadSegment, VertexPayload};
+pub use precision::PrecisionModel;
+pub use provenance::{EdgeSource, TracedMultiPolygon};
+pub use segment_intersection::LineIntersection;
+pub use stepper::{StepFields, StepSegment, StepSplit, SweepStep, SweepStepper};
+pub use sweep_line::{BTreeSweepLine, SkipListSweepLine, SplaySweepLine, SweepLineStatus};
+pub use unary_union::{try_unary_union, try_unary_union_iter, unary_union, unary_union_iter};
+pub use vertex_values::{try_boolean_with_values, Interpolate, ValuedMultiPolygon};
 
-use self::connect_edges::connect_edges;
+use self::connect_edges::{connect_edges, Contour};
 use self::fill_queue::fill_queue;
-use self::subdivide_segments::subdivide;
+use self::helper::partial_min;
+use self::operand::Operands;
+use self::precision::{is_valid_scale, make_precise_result, snap_round};
+use self::provenance::{contours_to_traced_multi_polygon, trace_input};
+use self::subdivide_segments::subdivide_with_sweep_line;
+use self::sweep_event::EventArena;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum Operation {
     Intersection,
     Difference,
     Union,
     Xor,
+    /// Operation given by its truth table: the function receives whether a region is inside
+    /// of the subject and inside of the clipping polygons, and returns whether the region
+    /// belongs to the result. E.g., `|subject_in, clipping_in| clipping_in && !subject_in`
+    /// computes the difference with swapped operands.
+    ///
+    /// The result must be bounded, i.e., the region outside of both operands must not belong
+    /// to it, otherwise the operation fails with `BooleanOpError::UnboundedResult`.
+    Custom(fn(bool, bool) -> bool),
+}
+
+// Custom operations are compared by function address, which is only a best effort: the same
+// function may have several addresses, and functions with identical code may share one.
+impl PartialEq for Operation {
+    fn eq(&self, other: &Operation) -> bool {
+        match (self, other) {
+            (Operation::Custom(a), Operation::Custom(b)) => std::ptr::fn_addr_eq(*a, *b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Operation {}
+
+impl Operation {
+    /// Whether a region belongs to the result of the operation, given whether it is
+    /// inside of the subject and inside of the clipping polygons.
+    pub fn is_in_result(self, subject_in: bool, clipping_in: bool) -> bool {
+        match self {
+            Operation::Intersection => subject_in && clipping_in,
+            Operation::Union => subject_in || clipping_in,
+            Operation::Xor => subject_in ^ clipping_in,
+            Operation::Difference => subject_in && !clipping_in,
+            Operation::Custom(region_in_result) => region_in_result(subject_in, clipping_in),
+        }
+    }
+
+    /// The x coordinate after which no more events can contribute to the result, because
+    /// only one of the operands has segments there, or `None` if all events can contribute.
+    pub(crate) fn sweep_end<F>(self, sbbox: &BoundingBox<F>, cbbox: &BoundingBox<F>) -> Option<F>
+    where
+        F: OrderedField,
+    {
+        match (self.is_in_result(true, false), self.is_in_result(false, true)) {
+            (false, false) => Some(partial_min(sbbox.max.x, cbbox.max.x)),
+            (true, false) => Some(sbbox.max.x),
+            (false, true) => Some(cbbox.max.x),
+            (true, true) => None,
+        }
+    }
+}
+
+/// Rule deciding which regions are considered inside of a polygon, based on the
+/// winding number of its rings around them.
+///
+/// Counter-clockwise rings increase the winding number of the enclosed region by one,
+/// clockwise rings decrease it by one. Only `EvenOdd` is independent of ring orientation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FillRule {
+    /// Regions with an odd winding number are inside. This is the classic
+    /// interpretation of the algorithm, which does not depend on ring orientation.
+    #[default]
+    EvenOdd,
+    /// Regions with a non-zero winding number are inside.
+    NonZero,
+    /// Regions with a positive winding number are inside.
+    Positive,
+    /// Regions with a negative winding number are inside.
+    Negative,
+}
+
+impl FillRule {
+    pub fn is_inside(self, winding_number: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding_number % 2 != 0,
+            FillRule::NonZero => winding_number != 0,
+            FillRule::Positive => winding_number > 0,
+            FillRule::Negative => winding_number < 0,
+        }
+    }
+}
+
+/// Fill rules used to interpret the subject and the clipping polygons of an operation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct FillRules {
+    pub subject: FillRule,
+    pub clipping: FillRule,
+}
+
+impl FillRules {
+    pub fn new(subject: FillRule, clipping: FillRule) -> FillRules {
+        FillRules { subject, clipping }
+    }
+
+    /// Uses the same fill rule for both operands.
+    pub fn both(fill_rule: FillRule) -> FillRules {
+        FillRules::new(fill_rule, fill_rule)
+    }
+
+    /// Fill rule of the subject (`is_subject == true`) or the clipping operand.
+    pub fn of_operand(self, is_subject: bool) -> FillRule {
+        if is_subject {
+            self.subject
+        } else {
+            self.clipping
+        }
+    }
+
+    pub(crate) fn is_default(self) -> bool {
+        self == FillRules::default()
+    }
 }
 
 pub trait BooleanOp<F, Rhs = Self>
 where
-    F: Float,
+    F: CoordNum,
 {
+    /// Computes the boolean operation using the even-odd fill rule for both operands.
+    ///
+    /// Panics if the operation fails, e.g. because an input coordinate is not finite, see
+    /// `try_boolean`.
     fn boolean(&self, rhs: &Rhs, operation: Operation) -> MultiPolygon<F>;
 
+    /// Computes the boolean operation, interpreting the subject and the clipping polygons
+    /// with the given fill rules. Degenerate input or internal inconsistencies of the sweep
+    /// are reported as `BooleanOpError` instead of panicking.
+    fn try_boolean_with_fill_rules(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+    ) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_boolean_with_sweep_line::<SplaySweepLine>(rhs, operation, fill_rules)
+    }
+
+    /// Variant of `try_boolean_with_fill_rules` which maintains the segments intersecting
+    /// the sweep line in `S` instead of the default `SplaySweepLine`. The result does not
+    /// depend on `S`, only the performance does.
+    fn try_boolean_with_sweep_line<S>(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+    ) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        S: SweepLineStatus,
+        Self: Operands<F, Rhs>,
+    {
+        self.boolean_operation::<S>(rhs, operation, fill_rules)
+    }
+
+    /// Infallible variant of `try_boolean_with_fill_rules`.
+    ///
+    /// Panics if the operation fails, e.g. because an input coordinate is not finite.
+    fn boolean_with_fill_rules(&self, rhs: &Rhs, operation: Operation, fill_rules: FillRules) -> MultiPolygon<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        match self.try_boolean_with_fill_rules(rhs, operation, fill_rules) {
+            Ok(result) => result,
+            Err(err) => panic!("Boolean operation {:?} failed: {}", operation, err),
+        }
+    }
+
+    /// Computes the boolean operation using the even-odd fill rule for both operands,
+    /// reporting degenerate input or internal inconsistencies of the sweep as
+    /// `BooleanOpError` instead of panicking.
+    fn try_boolean(&self, rhs: &Rhs, operation: Operation) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_boolean_with_fill_rules(rhs, operation, FillRules::default())
+    }
+
+    /// Variant of `try_boolean_with_fill_rules` which rounds the coordinates of the result
+    /// to `precision`. With a fixed precision model, the operands are snap rounded to its
+    /// grid first, so that the result is valid at that precision, i.e., rounding does not
+    /// introduce self-intersections.
+    fn try_boolean_with_precision(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+        precision: PrecisionModel<F>,
+    ) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.precise_boolean_operation(rhs, operation, fill_rules, precision)
+    }
+
+    /// Infallible variant of `try_boolean_with_precision`.
+    ///
+    /// Panics if the operation fails, e.g. because an input coordinate is not finite.
+    fn boolean_with_precision(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+        precision: PrecisionModel<F>,
+    ) -> MultiPolygon<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        match self.try_boolean_with_precision(rhs, operation, fill_rules, precision) {
+            Ok(result) => result,
+            Err(err) => panic!("Boolean operation {:?} failed: {}", operation, err),
+        }
+    }
+
+    /// Variant of `try_boolean_with_fill_rules` which additionally reports the input edge
+    /// every edge of the result originates from.
+    fn try_boolean_traced_with_fill_rules(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+    ) -> Result<TracedMultiPolygon<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.traced_boolean_operation(rhs, operation, fill_rules)
+    }
+
+    /// Infallible variant of `try_boolean_traced_with_fill_rules`.
+    ///
+    /// Panics if the operation fails, e.g. because an input coordinate is not finite.
+    fn boolean_traced_with_fill_rules(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+    ) -> TracedMultiPolygon<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        match self.try_boolean_traced_with_fill_rules(rhs, operation, fill_rules) {
+            Ok(result) => result,
+            Err(err) => panic!("Boolean operation {:?} failed: {}", operation, err),
+        }
+    }
+
+    fn try_boolean_traced(&self, rhs: &Rhs, operation: Operation) -> Result<TracedMultiPolygon<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_boolean_traced_with_fill_rules(rhs, operation, FillRules::default())
+    }
+
+    fn boolean_traced(&self, rhs: &Rhs, operation: Operation) -> TracedMultiPolygon<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.boolean_traced_with_fill_rules(rhs, operation, FillRules::default())
+    }
+
+    /// Computes intersection, union, difference and xor of both operands at once. The sweep
+    /// is performed only once, and its events are classified for each of the operations.
+    fn try_boolean_all_with_fill_rules(
+        &self,
+        rhs: &Rhs,
+        fill_rules: FillRules,
+    ) -> Result<BooleanResults<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.boolean_all_operation(rhs, fill_rules)
+    }
+
+    /// Infallible variant of `try_boolean_all_with_fill_rules`.
+    ///
+    /// Panics if the operation fails, e.g. because an input coordinate is not finite.
+    fn boolean_all_with_fill_rules(&self, rhs: &Rhs, fill_rules: FillRules) -> BooleanResults<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        match self.try_boolean_all_with_fill_rules(rhs, fill_rules) {
+            Ok(results) => results,
+            Err(err) => panic!("Boolean operations failed: {}", err),
+        }
+    }
+
+    fn try_boolean_all(&self, rhs: &Rhs) -> Result<BooleanResults<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_boolean_all_with_fill_rules(rhs, FillRules::default())
+    }
+
+    fn boolean_all(&self, rhs: &Rhs) -> BooleanResults<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.boolean_all_with_fill_rules(rhs, FillRules::default())
+    }
+
+    /// Divides both operands into the parts covered only by `self`, by both, and only by
+    /// `rhs`. In contrast to computing the differences and the intersection separately, the
+    /// parts are guaranteed to fit together exactly, see `Division`.
+    fn try_divide_with_fill_rules(&self, rhs: &Rhs, fill_rules: FillRules) -> Result<Division<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.divide_operation(rhs, fill_rules)
+    }
+
+    /// Infallible variant of `try_divide_with_fill_rules`.
+    ///
+    /// Panics if the operation fails, e.g. because an input coordinate is not finite.
+    fn divide_with_fill_rules(&self, rhs: &Rhs, fill_rules: FillRules) -> Division<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        match self.try_divide_with_fill_rules(rhs, fill_rules) {
+            Ok(division) => division,
+            Err(err) => panic!("Division failed: {}", err),
+        }
+    }
+
+    fn try_divide(&self, rhs: &Rhs) -> Result<Division<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_divide_with_fill_rules(rhs, FillRules::default())
+    }
+
+    fn divide(&self, rhs: &Rhs) -> Division<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.divide_with_fill_rules(rhs, FillRules::default())
+    }
+
+    /// Computes the intersection including its lower dimensional parts, which
+    /// `Operation::Intersection` drops: besides the polygons where the interiors overlap, the
+    /// collection contains the line strings where the boundaries coincide, e.g. the shared edge
+    /// of adjacent polygons, and the points where the boundaries touch otherwise, e.g. at a
+    /// shared corner. Polygons come first, followed by the line strings and the points.
+    fn try_intersection_full_with_fill_rules(
+        &self,
+        rhs: &Rhs,
+        fill_rules: FillRules,
+    ) -> Result<GeometryCollection<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.intersection_full_operation(rhs, fill_rules)
+    }
+
+    /// Infallible variant of `try_intersection_full_with_fill_rules`.
+    ///
+    /// Panics if the operation fails, e.g. because an input coordinate is not finite.
+    fn intersection_full_with_fill_rules(&self, rhs: &Rhs, fill_rules: FillRules) -> GeometryCollection<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        match self.try_intersection_full_with_fill_rules(rhs, fill_rules) {
+            Ok(result) => result,
+            Err(err) => panic!("Full intersection failed: {}", err),
+        }
+    }
+
+    fn try_intersection_full(&self, rhs: &Rhs) -> Result<GeometryCollection<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_intersection_full_with_fill_rules(rhs, FillRules::default())
+    }
+
+    fn intersection_full(&self, rhs: &Rhs) -> GeometryCollection<F>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.intersection_full_with_fill_rules(rhs, FillRules::default())
+    }
+
+    fn try_intersection(&self, rhs: &Rhs) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_boolean(rhs, Operation::Intersection)
+    }
+
+    fn try_difference(&self, rhs: &Rhs) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_boolean(rhs, Operation::Difference)
+    }
+
+    fn try_union(&self, rhs: &Rhs) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_boolean(rhs, Operation::Union)
+    }
+
+    fn try_xor(&self, rhs: &Rhs) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        Self: Operands<F, Rhs>,
+    {
+        self.try_boolean(rhs, Operation::Xor)
+    }
+
     fn intersection(&self, rhs: &Rhs) -> MultiPolygon<F> {
         self.boolean(rhs, Operation::Intersection)
     }
@@ -54,7 +500,7 @@ where
     F: Float,
 {
     fn boolean(&self, rhs: &Polygon<F>, operation: Operation) -> MultiPolygon<F> {
-        boolean_operation(&[self.clone()], &[rhs.clone()], operation)
+        self.boolean_with_fill_rules(rhs, operation, FillRules::default())
     }
 }
 
@@ -63,7 +509,7 @@ where
     F: Float,
 {
     fn boolean(&self, rhs: &MultiPolygon<F>, operation: Operation) -> MultiPolygon<F> {
-        boolean_operation(&[self.clone()], rhs.0.as_slice(), operation)
+        self.boolean_with_fill_rules(rhs, operation, FillRules::default())
     }
 }
 
@@ -72,7 +518,7 @@ where
     F: Float,
 {
     fn boolean(&self, rhs: &MultiPolygon<F>, operation: Operation) -> MultiPolygon<F> {
-        boolean_operation(self.0.as_slice(), rhs.0.as_slice(), operation)
+        self.boolean_with_fill_rules(rhs, operation, FillRules::default())
     }
 }
 
@@ -81,15 +527,101 @@ where
     F: Float,
 {
     fn boolean(&self, rhs: &Polygon<F>, operation: Operation) -> MultiPolygon<F> {
-        boolean_operation(self.0.as_slice(), &[rhs.clone()], operation)
+        self.boolean_with_fill_rules(rhs, operation, FillRules::default())
     }
 }
 
-fn boolean_operation<F>(subject: &[Polygon<F>], clipping: &[Polygon<F>], operation: Operation) -> MultiPolygon<F>
+fn boolean_operation<F, S>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    operation: Operation,
+    fill_rules: FillRules,
+) -> Result<MultiPolygon<F>, BooleanOpError>
+where
+    F: Float,
+    S: SweepLineStatus,
+{
+    traced_boolean_operation::<F, S, DefaultKernel>(subject, clipping, operation, fill_rules)
+        .map(|traced| traced.multi_polygon)
+}
+
+fn precise_boolean_operation<F>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    operation: Operation,
+    fill_rules: FillRules,
+    precision: PrecisionModel<F>,
+) -> Result<MultiPolygon<F>, BooleanOpError>
 where
     F: Float,
 {
-    let mut sbbox = BoundingBox {
+    let scale = match precision {
+        PrecisionModel::Floating => {
+            return boolean_operation::<F, SplaySweepLine>(subject, clipping, operation, fill_rules)
+        }
+        PrecisionModel::Fixed { scale } => scale,
+    };
+    if !is_valid_scale(scale) {
+        return Err(BooleanOpError::InvalidPrecisionModel);
+    }
+    if !all_coords_finite(subject) || !all_coords_finite(clipping) {
+        return Err(BooleanOpError::NonFiniteCoordinate);
+    }
+
+    let (subject, clipping) = snap_round(subject, clipping, scale)?;
+    let result = boolean_operation::<F, SplaySweepLine>(&subject, &clipping, operation, fill_rules)?;
+    Ok(make_precise_result(result, scale))
+}
+
+fn traced_boolean_operation<F, S, K>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    operation: Operation,
+    fill_rules: FillRules,
+) -> Result<TracedMultiPolygon<F>, BooleanOpError>
+where
+    F: OrderedField,
+    S: SweepLineStatus,
+    K: Kernel<F>,
+{
+    if !all_coords_finite(subject) || !all_coords_finite(clipping) {
+        return Err(BooleanOpError::NonFiniteCoordinate);
+    }
+    if operation.is_in_result(false, false) {
+        return Err(BooleanOpError::UnboundedResult);
+    }
+
+    // The trivial result passes the input through unmodified, which is only valid if the input
+    // is interpreted like the output, i.e., with the even-odd rule.
+    let (mut sbbox, mut cbbox) = match (initial_bounding_box(subject), initial_bounding_box(clipping)) {
+        (Some(sbbox), Some(cbbox)) => (sbbox, cbbox),
+        _ if fill_rules.is_default() => return Ok(trivial_traced_result(subject, clipping, operation)),
+        // Without segments, an operand does not limit the sweep, so it may take the bounding
+        // box of the other one.
+        (Some(bbox), None) | (None, Some(bbox)) => (bbox, bbox),
+        (None, None) => return Ok(contours_to_traced_multi_polygon::<F>(&[])),
+    };
+
+    let mut events = EventArena::<F, K>::default();
+    let mut event_queue = fill_queue(subject, clipping, &mut sbbox, &mut cbbox, operation, &mut events);
+
+    if fill_rules.is_default() && bounding_boxes_disjoint(&sbbox, &cbbox) {
+        return Ok(trivial_traced_result(subject, clipping, operation));
+    }
+
+    let sorted_events =
+        subdivide_with_sweep_line::<F, K, _, S>(&mut events, &mut event_queue, &sbbox, &cbbox, operation, fill_rules)?;
+
+    let contours = connect_edges(&mut events, &sorted_events)?;
+
+    Ok(contours_to_traced_multi_polygon(&contours))
+}
+
+pub(crate) fn empty_bounding_box<F>() -> BoundingBox<F>
+where
+    F: Float,
+{
+    BoundingBox {
         min: Coord {
             x: F::infinity(),
             y: F::infinity(),
@@ -98,20 +630,28 @@ where
             x: F::neg_infinity(),
             y: F::neg_infinity(),
         },
-    };
-    let mut cbbox = sbbox;
-
-    let mut event_queue = fill_queue(subject, clipping, &mut sbbox, &mut cbbox, operation);
-
-    if sbbox.min.x > cbbox.max.x || cbbox.min.x > sbbox.max.x || sbbox.min.y > cbbox.max.y || cbbox.min.y > sbbox.max.y
-    {
-        return trivial_result(subject, clipping, operation);
     }
+}
 
-    let sorted_events = subdivide(&mut event_queue, &sbbox, &cbbox, operation);
-
-    let contours = connect_edges(&sorted_events);
+/// The bounding box of the first coordinate of `polygons`, which `fill_queue` extends to the
+/// bounding box of all coordinates, or `None` if there are no coordinates. In contrast to
+/// `empty_bounding_box`, this does not require infinite values.
+fn initial_bounding_box<F>(polygons: &[Polygon<F>]) -> Option<BoundingBox<F>>
+where
+    F: OrderedField,
+{
+    polygons
+        .iter()
+        .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+        .flat_map(|ring| ring.0.first())
+        .next()
+        .map(|c| BoundingBox { min: *c, max: *c })
+}
 
+pub(crate) fn contours_to_multi_polygon<F>(contours: &[Contour<F>]) -> MultiPolygon<F>
+where
+    F: OrderedField,
+{
     // Convert contours into polygons
     let polygons: Vec<Polygon<F>> = contours
         .iter()
@@ -129,13 +669,49 @@ where
     MultiPolygon(polygons)
 }
 
-fn trivial_result<F>(subject: &[Polygon<F>], clipping: &[Polygon<F>], operation: Operation) -> MultiPolygon<F>
+pub(crate) fn all_coords_finite<F>(polygons: &[Polygon<F>]) -> bool
+where
+    F: OrderedField,
+{
+    // Multiplying by zero yields zero unless the value is infinite or NaN, which also works
+    // for types without such values.
+    let is_finite = |v: F| v * F::zero() == F::zero();
+    polygons.iter().all(|polygon| {
+        std::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .flat_map(|ring| ring.0.iter())
+            .all(|c| is_finite(c.x) && is_finite(c.y))
+    })
+}
+
+pub(crate) fn bounding_boxes_disjoint<F>(sbbox: &BoundingBox<F>, cbbox: &BoundingBox<F>) -> bool
+where
+    F: OrderedField,
+{
+    sbbox.min.x > cbbox.max.x || cbbox.min.x > sbbox.max.x || sbbox.min.y > cbbox.max.y || cbbox.min.y > sbbox.max.y
+}
+
+pub(crate) fn trivial_result<F>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    operation: Operation,
+) -> MultiPolygon<F>
 where
     F: Float,
 {
-    match operation {
-        Operation::Intersection => MultiPolygon(vec![]),
-        Operation::Difference => MultiPolygon(Vec::from(subject)),
-        Operation::Union | Operation::Xor => MultiPolygon(subject.iter().chain(clipping).cloned().collect()),
-    }
+    trivial_traced_result(subject, clipping, operation).multi_polygon
+}
+
+fn trivial_traced_result<F>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    operation: Operation,
+) -> TracedMultiPolygon<F>
+where
+    F: OrderedField,
+{
+    // The operands do not overlap, so each of them is either part of the result or not.
+    let subject = Some((0, subject)).filter(|_| operation.is_in_result(true, false));
+    let clipping = Some((1, clipping)).filter(|_| operation.is_in_result(false, true));
+    trace_input(subject.into_iter().chain(clipping))
 }
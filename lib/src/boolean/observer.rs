@@ -0,0 +1,301 @@
+use super::connect_edges::connect_edges_with_observer;
+use super::error::BooleanOpError;
+use super::fill_queue::fill_queue;
+use super::helper::{Float, OrderedField};
+use super::kernel::DefaultKernel;
+use super::subdivide_segments::subdivide_with_observer;
+use super::sweep_event::{EventArena, EventId};
+use super::{all_coords_finite, contours_to_multi_polygon, empty_bounding_box, FillRules, Operation};
+use geo_types::{MultiPolygon, Polygon};
+use std::io::{self, Write};
+
+/// Receives the progress of the sweep of an operation, see `try_boolean_observed`.
+///
+/// All methods do nothing by default. The events are passed by their id in `events`, which
+/// holds the current state of all events, including the ones created by dividing segments.
+/// `K` is the kernel and `P` the vertex payload of the arena, observers which don't depend
+/// on them can implement the trait for any kernel and payload.
+pub trait SweepObserver<F, K = DefaultKernel, P = ()>
+where
+    F: OrderedField,
+{
+    /// Called when `event` is taken from the queue, before it is processed.
+    fn process_event(&mut self, _events: &EventArena<F, K, P>, _event: EventId) {}
+
+    /// Called before the segments of the left events `below` and `above`, which are
+    /// neighbors in the sweep line, are checked for an intersection.
+    fn check_intersection(&mut self, _events: &EventArena<F, K, P>, _below: EventId, _above: EventId) {}
+
+    /// Called after a segment has been divided at an intersection or at the endpoint of an
+    /// overlapping segment. `end` is the new event ending the part before the division point,
+    /// `start` the new event starting the part after it.
+    fn split(&mut self, _events: &EventArena<F, K, P>, _end: EventId, _start: EventId) {}
+
+    /// Called after the fields of the left `event` have been computed from the segment
+    /// `prev` below it, which may happen repeatedly if overlapping segments are found later on.
+    fn compute_fields(&mut self, _events: &EventArena<F, K, P>, _event: EventId, _prev: Option<EventId>) {}
+
+    /// Called before the segment of the left `event` is removed from the sweep line.
+    fn remove(&mut self, _events: &EventArena<F, K, P>, _event: EventId) {}
+
+    /// Called with the events of the result, in the order in which they are connected.
+    fn result_events(&mut self, _events: &EventArena<F, K, P>, _result_events: &[EventId]) {}
+}
+
+/// Observer ignoring all progress, used by the operations which are not observed.
+impl<F, K, P> SweepObserver<F, K, P> for () where F: OrderedField {}
+
+/// Computes a boolean operation like `BooleanOp::try_boolean_with_fill_rules`, reporting the
+/// progress of the sweep to `observer`.
+///
+/// In contrast to `BooleanOp`, the sweep is performed even if the bounding boxes of the
+/// operands are disjoint, so that the observer always sees the full sweep.
+pub fn try_boolean_observed<F, O>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    operation: Operation,
+    fill_rules: FillRules,
+    observer: &mut O,
+) -> Result<MultiPolygon<F>, BooleanOpError>
+where
+    F: Float,
+    O: SweepObserver<F>,
+{
+    if !all_coords_finite(subject) || !all_coords_finite(clipping) {
+        return Err(BooleanOpError::NonFiniteCoordinate);
+    }
+    if operation.is_in_result(false, false) {
+        return Err(BooleanOpError::UnboundedResult);
+    }
+
+    let mut sbbox = empty_bounding_box();
+    let mut cbbox = sbbox;
+    let mut events = EventArena::new();
+    let mut event_queue = fill_queue(subject, clipping, &mut sbbox, &mut cbbox, operation, &mut events);
+
+    let sorted_events = subdivide_with_observer(
+        &mut events,
+        &mut event_queue,
+        &sbbox,
+        &cbbox,
+        operation,
+        fill_rules,
+        observer,
+    )?;
+    let contours = connect_edges_with_observer(&mut events, &sorted_events, observer)?;
+    Ok(contours_to_multi_polygon(&contours))
+}
+
+/// Observer writing the progress of the sweep as JSON, one object per line, which is keyed
+/// by the kind of step, e.g. `{"processEvent": {"self": ..., "other": ...}}`.
+///
+/// Writing stops at the first error, which is returned by `finish`.
+pub struct JsonTraceWriter<W>
+where
+    W: Write,
+{
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W> JsonTraceWriter<W>
+where
+    W: Write,
+{
+    pub fn new(writer: W) -> JsonTraceWriter<W> {
+        JsonTraceWriter { writer, error: None }
+    }
+
+    /// Flushes the writer and returns it, or the first error which occurred while writing.
+    pub fn finish(mut self) -> io::Result<W> {
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => self.writer.flush().map(|_| self.writer),
+        }
+    }
+
+    fn write_line(&mut self, key: &str, value: String) {
+        if self.error.is_none() {
+            if let Err(err) = writeln!(self.writer, "{{\"{}\": {}}}", key, value) {
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
+impl<F, K, P, W> SweepObserver<F, K, P> for JsonTraceWriter<W>
+where
+    F: Float,
+    W: Write,
+{
+    fn process_event(&mut self, events: &EventArena<F, K, P>, event: EventId) {
+        self.write_line("processEvent", event_json(events, event));
+    }
+
+    fn check_intersection(&mut self, events: &EventArena<F, K, P>, below: EventId, above: EventId) {
+        let value = format!(
+            "{{\"below\": {}, \"above\": {}}}",
+            event_json(events, below),
+            event_json(events, above)
+        );
+        self.write_line("checkIntersection", value);
+    }
+
+    fn split(&mut self, events: &EventArena<F, K, P>, end: EventId, start: EventId) {
+        let value = format!(
+            "{{\"end\": {}, \"start\": {}}}",
+            event_json(events, end),
+            event_json(events, start)
+        );
+        self.write_line("divideSegment", value);
+    }
+
+    fn compute_fields(&mut self, events: &EventArena<F, K, P>, event: EventId, _prev: Option<EventId>) {
+        let se = &events[event];
+        let value = format!(
+            "{{\"event\": {}, \"inOut\": {}, \"otherOut\": {}, \"resultTransition\": \"{:?}\", \"edgeType\": \"{:?}\"}}",
+            event_json_short(events, event),
+            se.is_in_out(),
+            se.is_other_in_out(),
+            se.get_result_transition(),
+            se.get_edge_type(),
+        );
+        self.write_line("computeFields", value);
+    }
+
+    fn remove(&mut self, events: &EventArena<F, K, P>, event: EventId) {
+        self.write_line("removing", event_json(events, event));
+    }
+
+    fn result_events(&mut self, events: &EventArena<F, K, P>, result_events: &[EventId]) {
+        for (index, &event) in result_events.iter().enumerate() {
+            let se = &events[event];
+            let value = format!(
+                "{{\"index\": {}, \"event\": {}, \"resultTransition\": \"{:?}\", \"inOut\": {}, \"otherInOut\": {}, \"isExteriorRing\": {}}}",
+                index,
+                event_json(events, event),
+                se.get_result_transition(),
+                se.is_in_out(),
+                se.is_other_in_out(),
+                se.is_exterior_ring,
+            );
+            self.write_line("resultEvent", value);
+        }
+    }
+}
+
+fn event_json<F, K, P>(events: &EventArena<F, K, P>, event: EventId) -> String
+where
+    F: Float,
+{
+    match events[event].get_other_event() {
+        Some(other) => format!(
+            "{{\"self\": {}, \"other\": {}}}",
+            event_json_short(events, event),
+            event_json_short(events, other),
+        ),
+        None => format!("{{\"self\": {}}}", event_json_short(events, event)),
+    }
+}
+
+fn event_json_short<F, K, P>(events: &EventArena<F, K, P>, event: EventId) -> String
+where
+    F: Float,
+{
+    let se = &events[event];
+    format!(
+        "{{\"addr\": \"{}\", \"point\": [{}, {}], \"type\": \"{}\", \"poly\": \"{}\"}}",
+        event,
+        se.point.x,
+        se.point.y,
+        if se.is_left() { "L" } else { "R" },
+        if se.is_subject { "A" } else { "B" },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boolean::BooleanOp;
+    use geo_types::polygon;
+
+    #[derive(Default)]
+    struct Counter {
+        processed: usize,
+        splits: Vec<(EventId, EventId)>,
+        result_events: usize,
+    }
+
+    impl SweepObserver<f64> for Counter {
+        fn process_event(&mut self, _events: &EventArena<f64>, _event: EventId) {
+            self.processed += 1;
+        }
+
+        fn split(&mut self, _events: &EventArena<f64>, end: EventId, start: EventId) {
+            self.splits.push((end, start));
+        }
+
+        fn result_events(&mut self, _events: &EventArena<f64>, result_events: &[EventId]) {
+            self.result_events = result_events.len();
+        }
+    }
+
+    fn squares() -> (Polygon<f64>, Polygon<f64>) {
+        let subject = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)];
+        let clipping = polygon![(x: 1., y: 1.), (x: 3., y: 1.), (x: 3., y: 3.), (x: 1., y: 3.), (x: 1., y: 1.)];
+        (subject, clipping)
+    }
+
+    #[test]
+    fn test_observed_operation() {
+        let (subject, clipping) = squares();
+        let mut counter = Counter::default();
+
+        let result = try_boolean_observed(
+            std::slice::from_ref(&subject),
+            std::slice::from_ref(&clipping),
+            Operation::Union,
+            FillRules::default(),
+            &mut counter,
+        )
+        .unwrap();
+
+        assert_eq!(result, subject.union(&clipping));
+        assert_eq!(counter.processed, 24);
+        assert_eq!(counter.splits.len(), 4);
+        // The union consists of 8 edges, each with a left and a right event.
+        assert_eq!(counter.result_events, 16);
+    }
+
+    #[test]
+    fn test_json_trace() {
+        let (subject, clipping) = squares();
+        let mut writer = JsonTraceWriter::new(Vec::new());
+
+        try_boolean_observed(
+            &[subject],
+            &[clipping],
+            Operation::Xor,
+            FillRules::default(),
+            &mut writer,
+        )
+        .unwrap();
+
+        let trace = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let lines: Vec<_> = trace.lines().collect();
+        assert_eq!(
+            lines.iter().filter(|l| l.starts_with("{\"processEvent\": ")).count(),
+            24
+        );
+        assert_eq!(
+            lines.iter().filter(|l| l.starts_with("{\"divideSegment\": ")).count(),
+            4
+        );
+        assert!(lines.iter().all(|l| l.starts_with('{') && l.ends_with('}')));
+        assert_eq!(
+            lines[0],
+            "{\"processEvent\": {\"self\": {\"addr\": \"0\", \"point\": [0, 0], \"type\": \"L\", \"poly\": \"A\"}, \
+             \"other\": {\"addr\": \"1\", \"point\": [2, 0], \"type\": \"R\", \"poly\": \"A\"}}}"
+        );
+    }
+}
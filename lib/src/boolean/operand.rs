@@ -0,0 +1,146 @@
+use super::boolean_all::{boolean_all_operation, BooleanResults};
+use super::divide::{divide_operation, Division};
+use super::error::BooleanOpError;
+use super::helper::Float;
+use super::intersection_full::intersection_full_operation;
+use super::kernel::DefaultKernel;
+use super::sweep_line::{SplaySweepLine, SweepLineStatus};
+use super::{
+    boolean_operation, precise_boolean_operation, traced_boolean_operation, FillRules, Operation, PrecisionModel,
+    TracedMultiPolygon,
+};
+use geo_types::{CoordNum, GeometryCollection, MultiPolygon, Polygon};
+
+/// Polygons of an operand, to implement `BooleanOp` for all combinations of `Polygon` and
+/// `MultiPolygon` alike.
+pub trait Operand<T>
+where
+    T: CoordNum,
+{
+    fn polygons(&self) -> &[Polygon<T>];
+}
+
+impl<T> Operand<T> for Polygon<T>
+where
+    T: CoordNum,
+{
+    fn polygons(&self) -> &[Polygon<T>] {
+        std::slice::from_ref(self)
+    }
+}
+
+impl<T> Operand<T> for MultiPolygon<T>
+where
+    T: CoordNum,
+{
+    fn polygons(&self) -> &[Polygon<T>] {
+        self.0.as_slice()
+    }
+}
+
+/// Operands `Self` and `Rhs` the operations of `BooleanOp` are implemented for, i.e., the
+/// polygons of the `Float` types and of the `IntegerCoord` types, whose operands are snap
+/// rounded first. The provided methods of `BooleanOp` are bounded by this trait, so that
+/// implementing `BooleanOp` only requires `boolean`.
+///
+/// The trait is public only nominally, so that it can bound the methods of `BooleanOp`. It is
+/// not exported, and thus cannot be implemented or named outside of the crate.
+pub trait Operands<F, Rhs>
+where
+    F: CoordNum,
+{
+    fn boolean_operation<S>(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+    ) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        S: SweepLineStatus;
+
+    fn precise_boolean_operation(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+        precision: PrecisionModel<F>,
+    ) -> Result<MultiPolygon<F>, BooleanOpError>;
+
+    fn traced_boolean_operation(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+    ) -> Result<TracedMultiPolygon<F>, BooleanOpError>;
+
+    fn boolean_all_operation(&self, rhs: &Rhs, fill_rules: FillRules) -> Result<BooleanResults<F>, BooleanOpError>;
+
+    fn divide_operation(&self, rhs: &Rhs, fill_rules: FillRules) -> Result<Division<F>, BooleanOpError>;
+
+    fn intersection_full_operation(
+        &self,
+        rhs: &Rhs,
+        fill_rules: FillRules,
+    ) -> Result<GeometryCollection<F>, BooleanOpError>;
+}
+
+// The integer types are implemented separately in `integer`, which does not conflict with
+// this implementation because `Float` is not implemented for them.
+impl<F, Lhs, Rhs> Operands<F, Rhs> for Lhs
+where
+    F: Float,
+    Lhs: Operand<F>,
+    Rhs: Operand<F>,
+{
+    fn boolean_operation<S>(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+    ) -> Result<MultiPolygon<F>, BooleanOpError>
+    where
+        S: SweepLineStatus,
+    {
+        boolean_operation::<F, S>(self.polygons(), rhs.polygons(), operation, fill_rules)
+    }
+
+    fn precise_boolean_operation(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+        precision: PrecisionModel<F>,
+    ) -> Result<MultiPolygon<F>, BooleanOpError> {
+        precise_boolean_operation(self.polygons(), rhs.polygons(), operation, fill_rules, precision)
+    }
+
+    fn traced_boolean_operation(
+        &self,
+        rhs: &Rhs,
+        operation: Operation,
+        fill_rules: FillRules,
+    ) -> Result<TracedMultiPolygon<F>, BooleanOpError> {
+        traced_boolean_operation::<F, SplaySweepLine, DefaultKernel>(
+            self.polygons(),
+            rhs.polygons(),
+            operation,
+            fill_rules,
+        )
+    }
+
+    fn boolean_all_operation(&self, rhs: &Rhs, fill_rules: FillRules) -> Result<BooleanResults<F>, BooleanOpError> {
+        boolean_all_operation(self.polygons(), rhs.polygons(), fill_rules)
+    }
+
+    fn divide_operation(&self, rhs: &Rhs, fill_rules: FillRules) -> Result<Division<F>, BooleanOpError> {
+        divide_operation(self.polygons(), rhs.polygons(), fill_rules)
+    }
+
+    fn intersection_full_operation(
+        &self,
+        rhs: &Rhs,
+        fill_rules: FillRules,
+    ) -> Result<GeometryCollection<F>, BooleanOpError> {
+        intersection_full_operation(self.polygons(), rhs.polygons(), fill_rules)
+    }
+}
@@ -0,0 +1,120 @@
+use super::helper::Float;
+use super::operand::Operands;
+use super::unary_union::try_unary_union;
+use super::{BooleanOp, BooleanOpError, Operation};
+use geo_types::{MultiPolygon, Polygon};
+use rayon::prelude::*;
+
+/// Minimum number of polygons merged by a single sweep in `par_unary_union`.
+const MIN_CHUNK_SIZE: usize = 16;
+
+/// Computes the boolean operation of `subject` with each of the `clips` in parallel.
+///
+/// The results are returned in the order of `clips`. Panics if any of the operations
+/// fails, see `try_par_boolean_many` for details.
+pub fn par_boolean_many<F, S, C>(subject: &S, clips: &[C], operation: Operation) -> Vec<MultiPolygon<F>>
+where
+    F: Float + Send + Sync,
+    S: BooleanOp<F, C> + Sync,
+    C: Sync,
+{
+    clips.par_iter().map(|clip| subject.boolean(clip, operation)).collect()
+}
+
+/// Fallible variant of `par_boolean_many`, reporting the result of every operation.
+pub fn try_par_boolean_many<F, S, C>(
+    subject: &S,
+    clips: &[C],
+    operation: Operation,
+) -> Vec<Result<MultiPolygon<F>, BooleanOpError>>
+where
+    F: Float + Send + Sync,
+    S: BooleanOp<F, C> + Operands<F, C> + Sync,
+    C: Sync,
+{
+    clips
+        .par_iter()
+        .map(|clip| subject.try_boolean(clip, operation))
+        .collect()
+}
+
+/// Intersects `subject` with each of the `clips` in parallel, e.g. to cut a layer into tiles.
+///
+/// Panics if any of the intersections fails, see `try_par_boolean_many` for details.
+pub fn par_intersection_many<F, S, C>(subject: &S, clips: &[C]) -> Vec<MultiPolygon<F>>
+where
+    F: Float + Send + Sync,
+    S: BooleanOp<F, C> + Sync,
+    C: Sync,
+{
+    par_boolean_many(subject, clips, Operation::Intersection)
+}
+
+/// Computes the union of an arbitrary number of polygons in parallel.
+///
+/// The polygons are split into one chunk per thread, each chunk is merged by a single
+/// sweep like in `unary_union`, and the partial results are combined by a cascade of
+/// pairwise unions.
+///
+/// Panics if the operation fails, see `try_par_unary_union` for details.
+pub fn par_unary_union<F>(polygons: &[Polygon<F>]) -> MultiPolygon<F>
+where
+    F: Float + Send + Sync,
+{
+    match try_par_unary_union(polygons) {
+        Ok(result) => result,
+        Err(err) => panic!("Unary union failed: {}", err),
+    }
+}
+
+/// Fallible variant of `par_unary_union`.
+pub fn try_par_unary_union<F>(polygons: &[Polygon<F>]) -> Result<MultiPolygon<F>, BooleanOpError>
+where
+    F: Float + Send + Sync,
+{
+    let chunk_size = (polygons.len() / rayon::current_num_threads()).max(MIN_CHUNK_SIZE);
+
+    polygons
+        .par_chunks(chunk_size)
+        .map(try_unary_union)
+        .try_reduce_with(|a, b| a.try_union(&b))
+        .unwrap_or_else(|| Ok(MultiPolygon(Vec::new())))
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::unary_union::unary_union;
+    use super::*;
+    use geo_types::polygon;
+
+    fn square(x: f64, y: f64, size: f64) -> Polygon<f64> {
+        polygon![(x: x, y: y), (x: x + size, y: y), (x: x + size, y: y + size), (x: x, y: y + size), (x: x, y: y)]
+    }
+
+    #[test]
+    fn test_par_intersection_many_keeps_order() {
+        let subject = square(0., 0., 4.);
+        let clips: Vec<_> = (0..8).map(|i| square(i as f64 - 1., 1., 2.)).collect();
+
+        let results = par_intersection_many(&subject, &clips);
+
+        let expected: Vec<_> = clips.iter().map(|clip| subject.intersection(clip)).collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_par_unary_union_cascades_chunks() {
+        // Enough polygons for several chunks, which share edges across chunk boundaries.
+        let squares: Vec<_> = (0..64).map(|i| square((i % 8) as f64, (i / 8) as f64, 1.)).collect();
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+
+        let result = pool.install(|| par_unary_union(&squares));
+
+        assert_eq!(result, unary_union(&squares));
+    }
+
+    #[test]
+    fn test_par_unary_union_of_empty_input() {
+        assert_eq!(par_unary_union::<f64>(&[]), MultiPolygon(Vec::new()));
+    }
+}
@@ -0,0 +1,256 @@
+use super::connect_edges::{connect_edges, Contour};
+use super::error::BooleanOpError;
+use super::fill_queue::fill_queue;
+use super::helper::{Float, OrderedField};
+use super::provenance::EdgeSource;
+use super::subdivide_segments::subdivide;
+use super::sweep_event::EventArena;
+use super::vertex_values::ValuedMultiPolygon;
+use super::{all_coords_finite, bounding_boxes_disjoint, initial_bounding_box, FillRules, Operation};
+use geo_types::{Coord, CoordNum, LineString, MultiPolygon, Polygon};
+
+/// User data attached to the vertices of the operands, like ids or timestamps, which is
+/// carried through the sweep by the events, see `try_boolean_with_payload`.
+///
+/// Like the kernel, the merge of payloads is selected by the type, so all intersection
+/// vertices of an operation are treated consistently.
+pub trait VertexPayload<F>: Clone
+where
+    F: OrderedField,
+{
+    /// Payload of the vertex created at `point` when the segment `divided` is divided because
+    /// of the segment `crossing`. If the segments overlap, `point` is an endpoint of
+    /// `crossing`, otherwise it is their intersection point. As both segments are divided at a
+    /// crossing, this is called twice with the roles of the segments swapped.
+    fn merge(point: Coord<F>, divided: &PayloadSegment<'_, F, Self>, crossing: &PayloadSegment<'_, F, Self>) -> Self;
+}
+
+/// No payload, which is used by all operations not carrying user data.
+impl<F> VertexPayload<F> for ()
+where
+    F: OrderedField,
+{
+    fn merge(_point: Coord<F>, _divided: &PayloadSegment<'_, F, ()>, _crossing: &PayloadSegment<'_, F, ()>) {}
+}
+
+/// Segment involved in the creation of an intersection vertex, see `VertexPayload::merge`.
+///
+/// The segment may be a part of an input edge which has been divided before, in which case
+/// its endpoints carry the payloads created by earlier merges.
+#[derive(Clone, Copy, Debug)]
+pub struct PayloadSegment<'a, F, P>
+where
+    F: CoordNum,
+{
+    /// The left endpoint, i.e., the one the sweep line passes first.
+    pub start: Coord<F>,
+    pub end: Coord<F>,
+    pub start_payload: &'a P,
+    pub end_payload: &'a P,
+    /// Input edge the segment is part of.
+    pub source: EdgeSource,
+}
+
+/// Computes a boolean operation like `BooleanOp::try_boolean_with_fill_rules`, carrying the
+/// payloads attached to the vertices of the operands through the sweep.
+///
+/// Every output vertex exposes the payload of the event it is taken from: input vertices keep
+/// their payload, and vertices created by dividing segments get the payload merged by
+/// `P::merge`. Where several events share a point, e.g. at a crossing or at coinciding input
+/// vertices, the payload of one of them is reported.
+pub fn try_boolean_with_payload<F, P>(
+    subject: &ValuedMultiPolygon<F, P>,
+    clipping: &ValuedMultiPolygon<F, P>,
+    operation: Operation,
+    fill_rules: FillRules,
+) -> Result<ValuedMultiPolygon<F, P>, BooleanOpError>
+where
+    F: Float,
+    P: VertexPayload<F>,
+{
+    if !subject.is_consistent() || !clipping.is_consistent() {
+        return Err(BooleanOpError::MismatchedVertexValues);
+    }
+    let (subject_polygons, clipping_polygons) = (&subject.multi_polygon.0, &clipping.multi_polygon.0);
+    if !all_coords_finite(subject_polygons) || !all_coords_finite(clipping_polygons) {
+        return Err(BooleanOpError::NonFiniteCoordinate);
+    }
+    if operation.is_in_result(false, false) {
+        return Err(BooleanOpError::UnboundedResult);
+    }
+
+    // Like for `BooleanOp`, the operands are passed through unmodified if they cannot interact.
+    let passed_through = || {
+        let subject = Some(subject).filter(|_| operation.is_in_result(true, false));
+        let clipping = Some(clipping).filter(|_| operation.is_in_result(false, true));
+        let operands = subject.into_iter().chain(clipping);
+        ValuedMultiPolygon {
+            multi_polygon: MultiPolygon(operands.clone().flat_map(|o| o.multi_polygon.0.clone()).collect()),
+            values: operands.flat_map(|o| o.values.clone()).collect(),
+        }
+    };
+    let (mut sbbox, mut cbbox) = match (
+        initial_bounding_box(subject_polygons),
+        initial_bounding_box(clipping_polygons),
+    ) {
+        (Some(sbbox), Some(cbbox)) => (sbbox, cbbox),
+        _ if fill_rules.is_default() => return Ok(passed_through()),
+        (Some(bbox), None) | (None, Some(bbox)) => (bbox, bbox),
+        (None, None) => return Ok(contours_to_valued_multi_polygon::<F, P>(&[])),
+    };
+
+    let mut events = EventArena::new();
+    let mut event_queue = fill_queue(
+        subject_polygons,
+        clipping_polygons,
+        &mut sbbox,
+        &mut cbbox,
+        operation,
+        &mut events,
+    );
+
+    if fill_rules.is_default() && bounding_boxes_disjoint(&sbbox, &cbbox) {
+        return Ok(passed_through());
+    }
+
+    let operands = [subject, clipping];
+    let mut events =
+        events.with_payloads(|_, event| operands[event.get_operand()].vertex_value(event.get_source(), event.point));
+    let sorted_events = subdivide(&mut events, &mut event_queue, &sbbox, &cbbox, operation, fill_rules)?;
+    let contours = connect_edges(&mut events, &sorted_events)?;
+
+    Ok(contours_to_valued_multi_polygon(&contours))
+}
+
+fn contours_to_valued_multi_polygon<F, P>(contours: &[Contour<F, P>]) -> ValuedMultiPolygon<F, P>
+where
+    F: OrderedField,
+    P: Clone,
+{
+    let mut polygons = Vec::new();
+    let mut values = Vec::new();
+    for contour in contours.iter().filter(|contour| contour.is_exterior()) {
+        let holes = contour.hole_ids.iter().map(|hole_id| &contours[*hole_id as usize]);
+        polygons.push(Polygon::new(
+            LineString(contour.points.clone()),
+            holes.clone().map(|hole| LineString(hole.points.clone())).collect(),
+        ));
+        values.push(
+            std::iter::once(contour)
+                .chain(holes)
+                .map(|ring| ring.payloads.clone())
+                .collect(),
+        );
+    }
+
+    ValuedMultiPolygon {
+        multi_polygon: MultiPolygon(polygons),
+        values,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo_types::polygon;
+
+    /// Records where a vertex comes from.
+    #[derive(Clone, PartialEq, Debug)]
+    enum Origin {
+        Input(usize),
+        Crossing(EdgeSource, EdgeSource),
+    }
+
+    impl VertexPayload<f64> for Origin {
+        fn merge(
+            _point: Coord<f64>,
+            divided: &PayloadSegment<'_, f64, Origin>,
+            crossing: &PayloadSegment<'_, f64, Origin>,
+        ) -> Origin {
+            Origin::Crossing(divided.source, crossing.source)
+        }
+    }
+
+    fn source(operand: usize, edge: usize) -> EdgeSource {
+        EdgeSource {
+            operand,
+            polygon: 0,
+            ring: 0,
+            edge,
+        }
+    }
+
+    fn with_ids(polygon: Polygon<f64>, first_id: usize) -> ValuedMultiPolygon<f64, Origin> {
+        let ids = (0..polygon.exterior().0.len())
+            .map(|i| Origin::Input(first_id + i))
+            .collect();
+        ValuedMultiPolygon {
+            multi_polygon: MultiPolygon(vec![polygon]),
+            values: vec![vec![ids]],
+        }
+    }
+
+    #[test]
+    fn test_input_and_crossing_payloads() {
+        let a = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)];
+        let b = polygon![(x: 1., y: 1.), (x: 3., y: 1.), (x: 3., y: 3.), (x: 1., y: 3.), (x: 1., y: 1.)];
+        let (subject, clipping) = (with_ids(a, 0), with_ids(b, 10));
+
+        let result = try_boolean_with_payload(&subject, &clipping, Operation::Union, FillRules::default()).unwrap();
+
+        // The points are (0, 0), (2, 0), (2, 1), (3, 1), (3, 3), (1, 3), (1, 2), (0, 2), (0, 0).
+        assert_eq!(result.multi_polygon.0.len(), 1);
+        assert_eq!(result.values[0].len(), 1);
+        let payloads = &result.values[0][0];
+        assert_eq!(payloads.len(), 9);
+        let is_crossing = |payload: &Origin, a: EdgeSource, b: EdgeSource| {
+            *payload == Origin::Crossing(a, b) || *payload == Origin::Crossing(b, a)
+        };
+        assert_eq!(payloads[0], Origin::Input(0));
+        assert_eq!(payloads[1], Origin::Input(1));
+        assert!(
+            is_crossing(&payloads[2], source(0, 1), source(1, 0)),
+            "{:?}",
+            payloads[2]
+        );
+        assert_eq!(payloads[3], Origin::Input(11));
+        assert_eq!(payloads[4], Origin::Input(12));
+        assert_eq!(payloads[5], Origin::Input(13));
+        assert!(
+            is_crossing(&payloads[6], source(0, 2), source(1, 3)),
+            "{:?}",
+            payloads[6]
+        );
+        assert_eq!(payloads[7], Origin::Input(3));
+        assert_eq!(payloads[8], Origin::Input(0));
+    }
+
+    #[test]
+    fn test_disjoint_operands_are_passed_through() {
+        let a = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let b = polygon![(x: 5., y: 0.), (x: 6., y: 0.), (x: 6., y: 1.), (x: 5., y: 0.)];
+        let (subject, clipping) = (with_ids(a, 0), with_ids(b, 10));
+
+        let union = try_boolean_with_payload(&subject, &clipping, Operation::Union, FillRules::default()).unwrap();
+        assert_eq!(
+            union.values,
+            vec![subject.values[0].clone(), clipping.values[0].clone()]
+        );
+        let intersection =
+            try_boolean_with_payload(&subject, &clipping, Operation::Intersection, FillRules::default()).unwrap();
+        assert!(intersection.values.is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_payloads() {
+        let a = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let mut subject = with_ids(a, 0);
+        let clipping = subject.clone();
+        subject.values[0][0].pop();
+
+        assert_eq!(
+            try_boolean_with_payload(&subject, &clipping, Operation::Union, FillRules::default()),
+            Err(BooleanOpError::MismatchedVertexValues)
+        );
+    }
+}
@@ -1,42 +1,51 @@
+use super::compare_segments::segment_order;
 use super::divide_segment::divide_segment;
-use super::helper::Float;
-use super::segment_intersection::{intersection, LineIntersection};
-use super::sweep_event::{EdgeType, SweepEvent};
-use std::collections::BinaryHeap;
-use std::rc::Rc;
-
-pub fn possible_intersection<F>(
-    se1: &Rc<SweepEvent<F>>,
-    se2: &Rc<SweepEvent<F>>,
-    queue: &mut BinaryHeap<Rc<SweepEvent<F>>>,
-) -> u8
+use super::error::BooleanOpError;
+use super::event_queue::EventQueue;
+use super::helper::OrderedField;
+use super::kernel::Kernel;
+use super::payload::VertexPayload;
+use super::segment_intersection::LineIntersection;
+use super::sweep_event::{EdgeType, EventArena, EventId};
+use geo_types::Coord;
+use std::cmp::Ordering;
+
+pub fn possible_intersection<F, K, P>(
+    events: &mut EventArena<F, K, P>,
+    se1: EventId,
+    se2: EventId,
+    queue: &mut EventQueue,
+) -> Result<u8, BooleanOpError>
 where
-    F: Float,
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
 {
-    let (other1, other2) = match (se1.get_other_event(), se2.get_other_event()) {
+    let (other1, other2) = match (events[se1].get_other_event(), events[se2].get_other_event()) {
         (Some(other1), Some(other2)) => (other1, other2),
-        _ => return 0,
+        _ => return Err(BooleanOpError::MissingOtherEvent),
     };
+    let (se1_point, other1_point) = (events[se1].point, events[other1].point);
+    let (se2_point, other2_point) = (events[se2].point, events[other2].point);
 
-    let inter = intersection(se1.point, other1.point, se2.point, other2.point);
+    let inter = if events.are_on_same_line(se1, se2) {
+        collinear_intersection::<F, K>([se1_point, other1_point], [se2_point, other2_point])
+    } else {
+        K::intersection(se1_point, other1_point, se2_point, other2_point)
+    };
 
-    #[cfg(feature = "debug-booleanop")]
+    #[cfg(feature = "tracing")]
     match inter {
-        LineIntersection::Point(inter) => {
-            println!("{{\"intersection\": [{}, {}]}}", inter.x, inter.y);
-        }
+        LineIntersection::Point(p) => tracing::trace!(se1, se2, x = ?p.x, y = ?p.y, "intersection"),
         LineIntersection::Overlap(p1, p2) => {
-            println!(
-                "{{\"overlap1\": [{}, {}], \"overlap2\": [{}, {}]}}",
-                p1.x, p1.y, p2.x, p2.y
-            );
+            tracing::trace!(se1, se2, x1 = ?p1.x, y1 = ?p1.y, x2 = ?p2.x, y2 = ?p2.y, "overlap")
         }
-        _ => {}
+        LineIntersection::None => {}
     }
 
-    match inter {
+    let result = match inter {
         LineIntersection::None => 0, // No intersection
-        LineIntersection::Point(_) if se1.point == se2.point || other1.point == other2.point => {
+        LineIntersection::Point(_) if se1_point == se2_point || other1_point == other2_point => {
             // The line segments intersect at either the left or right endpoint.
             // In this case we ignore the result of intersection computation for numerical
             // stability (the computed intersection can slightly deviate from the endpoints).
@@ -46,77 +55,171 @@ where
             0
         }
         LineIntersection::Point(inter) => {
-            if se1.point != inter && other1.point != inter {
-                divide_segment(se1, inter, queue);
+            let inter = endpoint_on_other_segment(events, se1, se2)
+                .or_else(|| exact_edge_intersection(events, se1, se2))
+                .unwrap_or(inter);
+            if se1_point != inter && other1_point != inter {
+                divide_segment(events, se1, inter, se2, queue)?;
             }
-            if se2.point != inter && other2.point != inter {
-                divide_segment(se2, inter, queue);
+            if se2_point != inter && other2_point != inter {
+                divide_segment(events, se2, inter, se1, queue)?;
             }
             1
         }
-        LineIntersection::Overlap(_, _) if se1.is_subject == se2.is_subject => 0, // The line segments associated to se1 and se2 overlap
         LineIntersection::Overlap(_, _) => {
-            let mut events = Vec::new();
+            let mut sorted_events = Vec::new();
             let mut left_coincide = false;
             let mut right_coincide = false;
 
-            if se1.point == se2.point {
+            if se1_point == se2_point {
                 left_coincide = true
-            } else if se1 < se2 {
-                events.push((se2.clone(), other2.clone()));
-                events.push((se1.clone(), other1.clone()));
+            } else if events.is_after(se1, se2) {
+                sorted_events.push((se2, other2));
+                sorted_events.push((se1, other1));
             } else {
-                events.push((se1.clone(), other1.clone()));
-                events.push((se2.clone(), other2.clone()));
+                sorted_events.push((se1, other1));
+                sorted_events.push((se2, other2));
             }
 
-            if other1.point == other2.point {
+            if other1_point == other2_point {
                 right_coincide = true
-            } else if other1 < other2 {
-                events.push((other2, se2.clone()));
-                events.push((other1, se1.clone()));
+            } else if events.is_after(other1, other2) {
+                sorted_events.push((other2, se2));
+                sorted_events.push((other1, se1));
             } else {
-                events.push((other1, se1.clone()));
-                events.push((other2, se2.clone()));
+                sorted_events.push((other1, se1));
+                sorted_events.push((other2, se2));
             }
 
             if left_coincide {
                 // both line segments are equal or share the left endpoint
-                se2.set_edge_type(EdgeType::NonContributing);
-                if se1.is_in_out() == se2.is_in_out() {
-                    se1.set_edge_type(EdgeType::SameTransition)
-                } else {
-                    se1.set_edge_type(EdgeType::DifferentTransition)
+                if left_coincide && !right_coincide {
+                    let inter = events[sorted_events[0].0].point;
+                    divide_segment(events, sorted_events[1].1, inter, sorted_events[0].0, queue)?;
                 }
 
-                if left_coincide && !right_coincide {
-                    divide_segment(&events[1].1, events[0].0.point, queue)
+                // Only the upper segment of a coincident group contributes to the result. If
+                // it is non-contributing itself, it is already represented by a segment above.
+                // The order of the segments is determined after the division, because segments
+                // which have become coincident are ordered differently.
+                let (lower, upper) = if segment_order(events, se1, se2) == Ordering::Greater {
+                    (se2, se1)
+                } else {
+                    (se1, se2)
+                };
+                let upper_edge_type = events[upper].get_edge_type();
+                events[lower].set_edge_type(EdgeType::NonContributing);
+                if upper_edge_type != EdgeType::NonContributing {
+                    if events[lower].is_in_out() == events[upper].is_in_out() {
+                        events[upper].set_edge_type(EdgeType::SameTransition)
+                    } else {
+                        events[upper].set_edge_type(EdgeType::DifferentTransition)
+                    }
                 }
-                return 2;
+                return Ok(2);
             }
 
             if right_coincide {
                 // the line segments share the right endpoint
-                divide_segment(&events[0].0, events[1].0.point, queue);
-                return 3;
+                let inter = events[sorted_events[1].0].point;
+                divide_segment(events, sorted_events[0].0, inter, sorted_events[1].0, queue)?;
+                return Ok(3);
             }
 
-            if !Rc::ptr_eq(&events[0].0, &events[3].1) {
+            let inter_0 = events[sorted_events[1].0].point;
+            let inter_1 = events[sorted_events[2].0].point;
+            if sorted_events[0].0 != sorted_events[3].1 {
                 // no line segment includes totally the other one
-                divide_segment(&events[0].0, events[1].0.point, queue);
-                divide_segment(&events[1].0, events[2].0.point, queue);
-                return 3;
+                divide_segment(events, sorted_events[0].0, inter_0, sorted_events[1].0, queue)?;
+                divide_segment(events, sorted_events[1].0, inter_1, sorted_events[2].0, queue)?;
+                return Ok(3);
             }
 
             // one line segment includes the other one
-            // TODO: write this in a non-panicking way. Note that we must not access the "other event"
-            // via events[3].1 because that is only a static reference, and the first divide segment
-            // internally modifies the other event point (we must access the updated other event).
-            // Probably the best solution is to introduce explicit return types for divide_segment.
-            divide_segment(&events[0].0, events[1].0.point, queue);
-            divide_segment(&events[3].0.get_other_event().unwrap(), events[2].0.point, queue);
+            // Note that we must not access the "other event" via sorted_events[3].1 because that is
+            // only a static reference, and the first divide segment internally modifies the other
+            // event point (we must access the updated other event).
+            divide_segment(events, sorted_events[0].0, inter_0, sorted_events[1].0, queue)?;
+            let other = events[sorted_events[3].0]
+                .get_other_event()
+                .ok_or(BooleanOpError::MissingOtherEvent)?;
+            divide_segment(events, other, inter_1, sorted_events[2].0, queue)?;
 
             3
         }
+    };
+
+    Ok(result)
+}
+
+/// Intersection of segments of collinear input edges, which overlap wherever their ranges
+/// overlap.
+fn collinear_intersection<F, K>(segment1: [Coord<F>; 2], segment2: [Coord<F>; 2]) -> LineIntersection<F>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    let start = max_point::<F, K>(segment1[0], segment2[0]);
+    let end = min_point::<F, K>(segment1[1], segment2[1]);
+    match K::compare_points(start, end) {
+        Ordering::Less => LineIntersection::Overlap(start, end),
+        Ordering::Equal => LineIntersection::Point(start),
+        Ordering::Greater => LineIntersection::None,
+    }
+}
+
+/// The endpoint of one segment which lies on the other one, if any. It is the exact
+/// intersection point of the segments, whereas the computed one is rounded.
+fn endpoint_on_other_segment<F, K, P>(events: &EventArena<F, K, P>, se1: EventId, se2: EventId) -> Option<Coord<F>>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    let endpoints = |se: EventId| [Some(events[se].point), events.other(se).map(|other| other.point)];
+    let on_other = |se: EventId, other: EventId| {
+        endpoints(se)
+            .into_iter()
+            .flatten()
+            .find(|&p| events.is_on_edge(other, p))
+    };
+    on_other(se1, se2).or_else(|| on_other(se2, se1))
+}
+
+/// The intersection point of the input edges the segments are part of, if it is exact and
+/// lies on both segments. Segments which have been divided at a rounded point are not
+/// exactly on their edge, so intersecting them would miss an exact intersection point.
+fn exact_edge_intersection<F, K, P>(events: &EventArena<F, K, P>, se1: EventId, se2: EventId) -> Option<Coord<F>>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    let ([start1, end1], [start2, end2]) = (events.line(se1)?, events.line(se2)?);
+    match K::intersection(start1, end1, start2, end2) {
+        LineIntersection::Point(p) if events.is_on_edge(se1, p) && events.is_on_edge(se2, p) => Some(p),
+        _ => None,
+    }
+}
+
+fn max_point<F, K>(a: Coord<F>, b: Coord<F>) -> Coord<F>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    if K::compare_points(a, b) == Ordering::Less {
+        b
+    } else {
+        a
+    }
+}
+
+fn min_point<F, K>(a: Coord<F>, b: Coord<F>) -> Coord<F>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    if K::compare_points(a, b) == Ordering::Greater {
+        b
+    } else {
+        a
     }
 }
@@ -0,0 +1,390 @@
+use super::error::BooleanOpError;
+use super::fill_queue::fill_queue;
+use super::helper::Float;
+use super::subdivide_segments::subdivide;
+use super::sweep_event::EventArena;
+use super::{empty_bounding_box, FillRules, Operation};
+use geo_types::{Coord, CoordNum, LineString, MultiPolygon, Polygon};
+use std::cmp::Ordering;
+
+/// Precision of the coordinates of a result.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum PrecisionModel<F>
+where
+    F: CoordNum,
+{
+    /// The full precision of `F`. Intersection points are rounded to the nearest
+    /// representable coordinates, which are arbitrary.
+    #[default]
+    Floating,
+    /// Coordinates are multiples of `1 / scale`. The operation performs snap rounding, so
+    /// that the result is valid with coordinates on this grid.
+    Fixed { scale: F },
+}
+
+impl<F> PrecisionModel<F>
+where
+    F: Float,
+{
+    /// Coordinates are multiples of `size`, e.g. `0.01` for centimetres in a metric
+    /// coordinate system.
+    pub fn grid_size(size: F) -> PrecisionModel<F> {
+        PrecisionModel::Fixed { scale: size.recip() }
+    }
+
+    /// Coordinates have at most `decimals` decimal places. Negative values round to
+    /// multiples of powers of ten, e.g. `-2` to multiples of 100.
+    pub fn decimals(decimals: i32) -> PrecisionModel<F> {
+        PrecisionModel::Fixed {
+            scale: F::from(10.0).unwrap().powi(decimals),
+        }
+    }
+
+    /// Rounds `value` to the precision model. Ties are rounded up, so that every grid cell
+    /// covers the same half-open interval.
+    pub fn make_precise(self, value: F) -> F {
+        match self {
+            PrecisionModel::Floating => value,
+            PrecisionModel::Fixed { scale } => grid_index(value, scale) / scale,
+        }
+    }
+
+    pub fn make_precise_coord(self, coord: Coord<F>) -> Coord<F> {
+        Coord {
+            x: self.make_precise(coord.x),
+            y: self.make_precise(coord.y),
+        }
+    }
+}
+
+fn grid_index<F>(value: F, scale: F) -> F
+where
+    F: Float,
+{
+    (value * scale + F::from(0.5).unwrap()).floor()
+}
+
+pub(crate) fn is_valid_scale<F>(scale: F) -> bool
+where
+    F: Float,
+{
+    scale.is_finite() && scale > F::zero()
+}
+
+/// The subject and the clipping polygons after snap rounding.
+type SnappedOperands<F> = (Vec<Polygon<F>>, Vec<Polygon<F>>);
+
+/// Snap rounds the rings of both operands to the grid of `scale`.
+///
+/// Every input vertex and every intersection of input segments marks the grid cell it lies
+/// in as a hot pixel. Each segment is then replaced by the polyline through the centers of
+/// all hot pixels it passes, in the order it passes them. The resulting segments only meet
+/// at hot pixel centers, i.e., the sweep over them creates no new vertices.
+pub(crate) fn snap_round<F>(
+    subject: &[Polygon<F>],
+    clipping: &[Polygon<F>],
+    scale: F,
+) -> Result<SnappedOperands<F>, BooleanOpError>
+where
+    F: Float,
+{
+    let hot_pixels = HotPixels::new(subject, clipping, scale)?;
+    let snap = |polygons: &[Polygon<F>]| -> Vec<Polygon<F>> {
+        polygons
+            .iter()
+            .filter_map(|polygon| hot_pixels.snap_polygon(polygon))
+            .collect()
+    };
+    Ok((snap(subject), snap(clipping)))
+}
+
+/// Rounds the coordinates of a result to the grid of `scale`, which only moves vertices
+/// that are not on the grid already due to numerical inaccuracies of the sweep.
+pub(crate) fn make_precise_result<F>(result: MultiPolygon<F>, scale: F) -> MultiPolygon<F>
+where
+    F: Float,
+{
+    let precision = PrecisionModel::Fixed { scale };
+    let make_precise = |ring: &LineString<F>| {
+        let mut coords: Vec<Coord<F>> = ring.0.iter().map(|&c| precision.make_precise_coord(c)).collect();
+        coords.dedup();
+        LineString(coords)
+    };
+    MultiPolygon(
+        result
+            .0
+            .iter()
+            .map(|polygon| {
+                Polygon::new(
+                    make_precise(polygon.exterior()),
+                    polygon.interiors().iter().map(make_precise).collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Grid cells containing a vertex or an intersection, in scaled coordinates, i.e., the cell
+/// `(x, y)` covers `[x - 0.5, x + 0.5) × [y - 0.5, y + 0.5)`.
+struct HotPixels<F>
+where
+    F: Float,
+{
+    /// Sorted by x, then by y.
+    centers: Vec<Coord<F>>,
+    scale: F,
+}
+
+impl<F> HotPixels<F>
+where
+    F: Float,
+{
+    fn new(subject: &[Polygon<F>], clipping: &[Polygon<F>], scale: F) -> Result<HotPixels<F>, BooleanOpError> {
+        // The union sweep divides all segments at their intersections, so the arena
+        // afterwards contains all vertices and intersection points.
+        let mut sbbox = empty_bounding_box();
+        let mut cbbox = sbbox;
+        let mut events = EventArena::new();
+        let mut event_queue = fill_queue(subject, clipping, &mut sbbox, &mut cbbox, Operation::Union, &mut events);
+        subdivide(
+            &mut events,
+            &mut event_queue,
+            &sbbox,
+            &cbbox,
+            Operation::Union,
+            FillRules::default(),
+        )?;
+
+        let mut centers: Vec<Coord<F>> = (0..events.len())
+            .map(|id| {
+                let point = events[id].point;
+                Coord {
+                    x: grid_index(point.x, scale),
+                    y: grid_index(point.y, scale),
+                }
+            })
+            .collect();
+        centers.sort_by(|a, b| compare_coords(*a, *b));
+        centers.dedup();
+        Ok(HotPixels { centers, scale })
+    }
+
+    /// Snaps the rings of the polygon, or returns `None` if its exterior collapses.
+    fn snap_polygon(&self, polygon: &Polygon<F>) -> Option<Polygon<F>> {
+        let exterior = self.snap_ring(polygon.exterior())?;
+        let interiors = polygon
+            .interiors()
+            .iter()
+            .filter_map(|ring| self.snap_ring(ring))
+            .collect();
+        Some(Polygon::new(exterior, interiors))
+    }
+
+    /// Snaps the ring, or returns `None` if it collapses to less than three vertices.
+    fn snap_ring(&self, ring: &LineString<F>) -> Option<LineString<F>> {
+        let mut coords: Vec<Coord<F>> = Vec::new();
+        for line in ring.lines() {
+            let start = self.scaled(line.start);
+            let end = self.scaled(line.end);
+            let mut passed: Vec<(F, Coord<F>)> = self
+                .candidates(start, end)
+                .filter_map(|center| entry_parameter(start, end, center).map(|t| (t, center)))
+                .collect();
+            passed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+            coords.extend(passed.into_iter().map(|(_, center)| self.unscaled(center)));
+        }
+        coords.dedup();
+        if coords.len() > 1 && coords.first() == coords.last() {
+            coords.pop();
+        }
+        if coords.len() < 3 {
+            return None;
+        }
+        coords.push(coords[0]);
+        Some(LineString(coords))
+    }
+
+    /// The hot pixels within the bounding box of the segment.
+    fn candidates(&self, start: Coord<F>, end: Coord<F>) -> impl Iterator<Item = Coord<F>> + '_ {
+        let half = F::from(0.5).unwrap();
+        let (min_x, max_x) = (start.x.min(end.x) - half, start.x.max(end.x) + half);
+        let (min_y, max_y) = (start.y.min(end.y) - half, start.y.max(end.y) + half);
+        let first = self.centers.partition_point(|c| c.x < min_x);
+        self.centers[first..]
+            .iter()
+            .take_while(move |c| c.x <= max_x)
+            .filter(move |c| c.y >= min_y && c.y <= max_y)
+            .copied()
+    }
+
+    fn scaled(&self, coord: Coord<F>) -> Coord<F> {
+        Coord {
+            x: coord.x * self.scale,
+            y: coord.y * self.scale,
+        }
+    }
+
+    fn unscaled(&self, center: Coord<F>) -> Coord<F> {
+        Coord {
+            x: center.x / self.scale,
+            y: center.y / self.scale,
+        }
+    }
+}
+
+fn compare_coords<F>(a: Coord<F>, b: Coord<F>) -> Ordering
+where
+    F: Float,
+{
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+/// The parameter at which the segment from `start` to `end` enters the half-open cell around
+/// `center`, or `None` if it does not pass the cell. Since the cells are disjoint, ordering
+/// them by their entry parameter orders them along the segment.
+fn entry_parameter<F>(start: Coord<F>, end: Coord<F>, center: Coord<F>) -> Option<F>
+where
+    F: Float,
+{
+    let half = F::from(0.5).unwrap();
+    let min = Coord {
+        x: center.x - half,
+        y: center.y - half,
+    };
+    let max = Coord {
+        x: center.x + half,
+        y: center.y + half,
+    };
+    let delta = end - start;
+
+    // Liang-Barsky clipping against the closed cell.
+    let mut t0 = F::zero();
+    let mut t1 = F::one();
+    for (p, q) in [
+        (-delta.x, start.x - min.x),
+        (delta.x, max.x - start.x),
+        (-delta.y, start.y - min.y),
+        (delta.y, max.y - start.y),
+    ] {
+        if p == F::zero() {
+            if q < F::zero() {
+                return None;
+            }
+        } else {
+            let r = q / p;
+            if p < F::zero() {
+                t0 = t0.max(r);
+            } else {
+                t1 = t1.min(r);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+
+    // The cell excludes its upper and right boundary, so the segment has to pass it
+    // somewhere else than only there.
+    let enter = start + delta * t0;
+    let leave = start + delta * t1;
+    if (enter.x >= max.x && leave.x >= max.x) || (enter.y >= max.y && leave.y >= max.y) {
+        return None;
+    }
+    Some(t0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boolean::BooleanOp;
+    use geo_types::{coord, polygon};
+
+    #[test]
+    fn test_make_precise() {
+        let centimetres = PrecisionModel::decimals(2);
+        assert_eq!(centimetres.make_precise(1.234), 1.23);
+        assert_eq!(centimetres.make_precise(-1.236), -1.24);
+        assert_eq!(PrecisionModel::grid_size(0.5).make_precise(1.3), 1.5);
+        assert_eq!(PrecisionModel::decimals(-1).make_precise(14.), 10.);
+        assert_eq!(PrecisionModel::Floating.make_precise(1.234), 1.234);
+    }
+
+    #[test]
+    fn test_entry_parameter_of_half_open_cells() {
+        let start = coord! {x: 0., y: 0.};
+        let end = coord! {x: 4., y: 0.};
+        assert_eq!(entry_parameter(start, end, coord! {x: 2., y: 0.}), Some(0.375));
+        assert_eq!(entry_parameter(start, end, coord! {x: 5., y: 0.}), None);
+        // The segment runs along the lower boundary of the upper cell, which belongs to it,
+        // and the upper boundary of the lower cell, which does not.
+        let end = coord! {x: 4., y: 0.5};
+        let start = coord! {x: 0., y: 0.5};
+        assert_eq!(entry_parameter(start, end, coord! {x: 2., y: 1.}), Some(0.375));
+        assert_eq!(entry_parameter(start, end, coord! {x: 2., y: 0.}), None);
+        // Diagonal through the corner shared by four cells.
+        let start = coord! {x: 0., y: 0.};
+        let end = coord! {x: 1., y: 1.};
+        assert_eq!(entry_parameter(start, end, coord! {x: 0., y: 1.}), None);
+        assert_eq!(entry_parameter(start, end, coord! {x: 1., y: 0.}), None);
+        assert_eq!(entry_parameter(start, end, coord! {x: 1., y: 1.}), Some(0.5));
+    }
+
+    #[test]
+    fn test_segment_is_routed_through_hot_pixels() {
+        // The nearly horizontal edge of the subject passes the cell of a vertex of the
+        // clipping polygon, and is therefore bent to its center.
+        let subject = polygon![(x: 0., y: 0.), (x: 10., y: 0.4), (x: 10., y: 5.), (x: 0., y: 5.), (x: 0., y: 0.)];
+        let clipping = polygon![(x: 5., y: -3.), (x: 6., y: -3.), (x: 5.2, y: 0.1), (x: 5., y: -3.)];
+        let (snapped, _) = snap_round(&[subject], &[clipping], 1.).unwrap();
+        assert_eq!(
+            snapped[0].exterior().0,
+            vec![
+                coord! {x: 0., y: 0.},
+                coord! {x: 5., y: 0.},
+                coord! {x: 10., y: 0.},
+                coord! {x: 10., y: 5.},
+                coord! {x: 0., y: 5.},
+                coord! {x: 0., y: 0.},
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collapsed_rings_are_removed() {
+        let subject = polygon![(x: 0., y: 0.), (x: 0.2, y: 0.), (x: 0.2, y: 0.2), (x: 0., y: 0.)];
+        let clipping = polygon![(x: 0., y: 0.), (x: 3., y: 0.), (x: 3., y: 3.), (x: 0., y: 0.)];
+        let (subject, clipping) = snap_round(&[subject], &[clipping], 1.).unwrap();
+        assert!(subject.is_empty());
+        assert_eq!(clipping.len(), 1);
+    }
+
+    #[test]
+    fn test_result_is_on_grid() {
+        let subject = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)];
+        let clipping = polygon![(x: 0.3, y: -0.7), (x: 1.3, y: 0.6), (x: 0.05, y: 1.55), (x: 0.3, y: -0.7)];
+        let precision = PrecisionModel::decimals(1);
+        for operation in [
+            Operation::Intersection,
+            Operation::Union,
+            Operation::Difference,
+            Operation::Xor,
+        ] {
+            let result = subject
+                .try_boolean_with_precision(&clipping, operation, FillRules::default(), precision)
+                .unwrap();
+            assert!(!result.0.is_empty());
+            for polygon in &result.0 {
+                for c in polygon
+                    .exterior()
+                    .0
+                    .iter()
+                    .chain(polygon.interiors().iter().flat_map(|r| &r.0))
+                {
+                    assert_eq!(precision.make_precise_coord(*c), *c);
+                }
+            }
+        }
+    }
+}
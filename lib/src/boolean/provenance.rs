@@ -0,0 +1,160 @@
+use super::connect_edges::Contour;
+use super::helper::OrderedField;
+use geo_types::{CoordNum, LineString, MultiPolygon, Polygon};
+
+/// Location of an edge in the input of an operation.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct EdgeSource {
+    /// Index of the operand, i.e., 0 for the subject and 1 for the clipping polygons.
+    pub operand: usize,
+    /// Index of the polygon within the operand.
+    pub polygon: usize,
+    /// Index of the ring within the polygon, where 0 is the exterior ring and `i + 1` is the
+    /// interior ring `i`.
+    pub ring: usize,
+    /// Index of the edge within the ring, i.e., the edge from point `edge` to `edge + 1`.
+    pub edge: usize,
+}
+
+/// Result of an operation along with the input edge every output edge originates from.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TracedMultiPolygon<F>
+where
+    F: CoordNum,
+{
+    pub multi_polygon: MultiPolygon<F>,
+    /// `sources[p][r][e]` is the source of the edge `e` of the ring `r` of the polygon `p`,
+    /// with rings numbered like in `EdgeSource`. If several input edges coincide, the source
+    /// of one of them is reported.
+    pub sources: Vec<Vec<Vec<EdgeSource>>>,
+}
+
+pub(crate) fn contours_to_traced_multi_polygon<F>(contours: &[Contour<F>]) -> TracedMultiPolygon<F>
+where
+    F: OrderedField,
+{
+    let mut polygons = Vec::new();
+    let mut sources = Vec::new();
+    for contour in contours.iter().filter(|contour| contour.is_exterior()) {
+        let holes = contour.hole_ids.iter().map(|hole_id| &contours[*hole_id as usize]);
+        polygons.push(Polygon::new(
+            LineString(contour.points.clone()),
+            holes.clone().map(|hole| LineString(hole.points.clone())).collect(),
+        ));
+        sources.push(
+            std::iter::once(contour)
+                .chain(holes)
+                .map(|ring| ring.sources.clone())
+                .collect(),
+        );
+    }
+
+    TracedMultiPolygon {
+        multi_polygon: MultiPolygon(polygons),
+        sources,
+    }
+}
+
+/// Traces polygons which are passed through from the input unmodified.
+pub(crate) fn trace_input<'a, F, I>(polygons: I) -> TracedMultiPolygon<F>
+where
+    F: OrderedField + 'a,
+    I: IntoIterator<Item = (usize, &'a [Polygon<F>])>,
+{
+    let mut result = TracedMultiPolygon {
+        multi_polygon: MultiPolygon(vec![]),
+        sources: Vec::new(),
+    };
+    for (operand, operand_polygons) in polygons {
+        for (polygon_index, polygon) in operand_polygons.iter().enumerate() {
+            let rings = std::iter::once(polygon.exterior()).chain(polygon.interiors());
+            result.sources.push(
+                rings
+                    .enumerate()
+                    .map(|(ring, line_string)| {
+                        (0..line_string.lines().count())
+                            .map(|edge| EdgeSource {
+                                operand,
+                                polygon: polygon_index,
+                                ring,
+                                edge,
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            );
+            result.multi_polygon.0.push(polygon.clone());
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::boolean::{BooleanOp, Operation};
+
+    use super::*;
+    use geo_types::polygon;
+
+    fn source(operand: usize, edge: usize) -> EdgeSource {
+        EdgeSource {
+            operand,
+            polygon: 0,
+            ring: 0,
+            edge,
+        }
+    }
+
+    #[test]
+    fn test_divided_edges() {
+        let a = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)];
+        let b = polygon![(x: 1., y: 1.), (x: 3., y: 1.), (x: 3., y: 3.), (x: 1., y: 3.), (x: 1., y: 1.)];
+
+        let traced = a.boolean_traced(&b, Operation::Union);
+
+        assert_eq!(
+            traced.multi_polygon,
+            MultiPolygon(vec![polygon![
+                (x: 0., y: 0.),
+                (x: 2., y: 0.),
+                (x: 2., y: 1.),
+                (x: 3., y: 1.),
+                (x: 3., y: 3.),
+                (x: 1., y: 3.),
+                (x: 1., y: 2.),
+                (x: 0., y: 2.),
+                (x: 0., y: 0.),
+            ]])
+        );
+        assert_eq!(
+            traced.sources,
+            vec![vec![vec![
+                source(0, 0),
+                source(0, 1),
+                source(1, 0),
+                source(1, 1),
+                source(1, 2),
+                source(1, 3),
+                source(0, 2),
+                source(0, 3),
+            ]]]
+        );
+    }
+
+    #[test]
+    fn test_trivial_result() {
+        let a = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let b = polygon![(x: 2., y: 0.), (x: 3., y: 0.), (x: 3., y: 1.), (x: 2., y: 0.)];
+
+        let traced = a.boolean_traced(&b, Operation::Xor);
+
+        assert_eq!(traced.multi_polygon, MultiPolygon(vec![a, b]));
+        assert_eq!(
+            traced.sources,
+            vec![
+                vec![vec![source(0, 0), source(0, 1), source(0, 2)]],
+                vec![vec![source(1, 0), source(1, 1), source(1, 2)]],
+            ]
+        );
+    }
+}
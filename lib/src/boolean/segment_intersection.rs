@@ -1,11 +1,13 @@
-use super::helper::BoundingBox;
-use super::helper::Float;
+use super::expansion::Expansion;
+use super::helper::{partial_max, partial_min, BoundingBox, Float, OrderedField};
+use super::signed_area::signed_area;
 use geo_types::Coord;
+use std::cmp::Ordering;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LineIntersection<F>
 where
-    F: Float,
+    F: OrderedField,
 {
     None,
     Point(Coord<F>),
@@ -15,16 +17,16 @@ where
 #[inline]
 fn get_intersection_bounding_box<F>(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> Option<BoundingBox<F>>
 where
-    F: Float,
+    F: OrderedField,
 {
     let (a_start_x, a_end_x) = if a1.x < a2.x { (a1.x, a2.x) } else { (a2.x, a1.x) };
     let (a_start_y, a_end_y) = if a1.y < a2.y { (a1.y, a2.y) } else { (a2.y, a1.y) };
     let (b_start_x, b_end_x) = if b1.x < b2.x { (b1.x, b2.x) } else { (b2.x, b1.x) };
     let (b_start_y, b_end_y) = if b1.y < b2.y { (b1.y, b2.y) } else { (b2.y, b1.y) };
-    let interval_start_x = a_start_x.max(b_start_x);
-    let interval_start_y = a_start_y.max(b_start_y);
-    let interval_end_x = a_end_x.min(b_end_x);
-    let interval_end_y = a_end_y.min(b_end_y);
+    let interval_start_x = partial_max(a_start_x, b_start_x);
+    let interval_start_y = partial_max(a_start_y, b_start_y);
+    let interval_end_x = partial_min(a_end_x, b_end_x);
+    let interval_end_y = partial_min(a_end_y, b_end_y);
     if interval_start_x <= interval_end_x && interval_start_y <= interval_end_y {
         Some(BoundingBox {
             min: Coord {
@@ -44,7 +46,7 @@ where
 #[inline]
 fn constrain_to_bounding_box<F>(p: Coord<F>, bb: BoundingBox<F>) -> Coord<F>
 where
-    F: Float,
+    F: OrderedField,
 {
     Coord {
         x: if p.x < bb.min.x {
@@ -64,9 +66,30 @@ where
     }
 }
 
+/// Intersection of the segments from `a1` to `a2` and from `b1` to `b2`, as computed by
+/// `DefaultKernel`.
+///
+/// With the `exact-intersection` feature, the intersection is classified exactly and the
+/// intersection point is correctly rounded, see `exact_intersection`. Otherwise it is
+/// computed in plain floating point arithmetic, which is faster but may misclassify nearly
+/// parallel segments or intersections close to an endpoint.
 pub fn intersection<F>(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> LineIntersection<F>
 where
     F: Float,
+{
+    if cfg!(feature = "exact-intersection") {
+        exact_intersection(a1, a2, b1, b2)
+    } else {
+        inexact_intersection(a1, a2, b1, b2)
+    }
+}
+
+/// Intersection computed in the arithmetic of the scalar type, with the intersection points
+/// clamped to the common bounding box of the segments. For floating point numbers, this is
+/// subject to rounding errors, for exact types like rationals, the result is exact.
+pub(crate) fn inexact_intersection<F>(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> LineIntersection<F>
+where
+    F: OrderedField,
 {
     let bb = get_intersection_bounding_box(a1, a2, b1, b2);
     if let Some(bb) = bb {
@@ -85,7 +108,7 @@ where
 
 fn intersection_impl<F>(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> LineIntersection<F>
 where
-    F: Float,
+    F: OrderedField,
 {
     // println!("{:?} {:?} {:?} {:?}", a1, a2, b1, b2);
     let va = Coord {
@@ -133,8 +156,8 @@ where
 
     let sa = dot_product(va, e) / sqr_len_a;
     let sb = sa + dot_product(va, vb) / sqr_len_a;
-    let smin = sa.min(sb);
-    let smax = sa.max(sb);
+    let smin = partial_min(sa, sb);
+    let smax = partial_max(sa, sb);
 
     if smin <= F::one() && smax >= F::zero() {
         if smin == F::one() {
@@ -145,17 +168,151 @@ where
         }
 
         return LineIntersection::Overlap(
-            mid_point(a1, smin.max(F::zero()), va),
-            mid_point(a1, smax.min(F::one()), va),
+            mid_point(a1, partial_max(smin, F::zero()), va),
+            mid_point(a1, partial_min(smax, F::one()), va),
         );
     }
 
     LineIntersection::None
 }
 
-fn mid_point<F>(p: Coord<F>, s: F, d: Coord<F>) -> Coord<F>
+/// Exact variant of `intersection_impl`.
+///
+/// The classification is based on the exact orientation of the endpoints of each segment
+/// relative to the other one. Intersections at endpoints and the endpoints of overlaps are
+/// input points, and the intersection point of crossing segments is the representable point
+/// nearest to the exact one, which is computed with floating point expansions. If the
+/// expansions overflow, the point is computed in floating point arithmetic instead.
+pub(crate) fn exact_intersection<F>(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> LineIntersection<F>
 where
     F: Float,
+{
+    if get_intersection_bounding_box(a1, a2, b1, b2).is_none() {
+        return LineIntersection::None;
+    }
+
+    let side = |p, q, r| signed_area(p, q, r).partial_cmp(&0.).unwrap_or(Ordering::Equal);
+    let (b1_side, b2_side) = (side(a1, a2, b1), side(a1, a2, b2));
+    let (a1_side, a2_side) = (side(b1, b2, a1), side(b1, b2, a2));
+
+    if [b1_side, b2_side, a1_side, a2_side]
+        .iter()
+        .all(|s| *s == Ordering::Equal)
+    {
+        return collinear_intersection(a1, a2, b1, b2);
+    }
+    if (b1_side == b2_side && b1_side != Ordering::Equal) || (a1_side == a2_side && a1_side != Ordering::Equal) {
+        return LineIntersection::None;
+    }
+    if b1_side == Ordering::Equal {
+        return LineIntersection::Point(b1);
+    }
+    if b2_side == Ordering::Equal {
+        return LineIntersection::Point(b2);
+    }
+    if a1_side == Ordering::Equal {
+        return LineIntersection::Point(a1);
+    }
+    if a2_side == Ordering::Equal {
+        return LineIntersection::Point(a2);
+    }
+
+    // The intersection is at a1 + va * numerator / denominator.
+    let difference = |p: Coord<F>, q: Coord<F>| {
+        (
+            Expansion::difference(p.x.into(), q.x.into()),
+            Expansion::difference(p.y.into(), q.y.into()),
+        )
+    };
+    let va = difference(a2, a1);
+    let vb = difference(b2, b1);
+    let e = difference(b1, a1);
+    let mut denominator = va.0.mul(&vb.1).sub(&va.1.mul(&vb.0));
+    let mut numerator = e.0.mul(&vb.1).sub(&e.1.mul(&vb.0));
+    if denominator.signum() == Ordering::Less {
+        denominator = denominator.neg();
+        numerator = numerator.neg();
+    }
+    let coordinate = |start: F, delta: &Expansion| {
+        let scaled = Expansion::new(start.into())
+            .mul(&denominator)
+            .sum(&delta.mul(&numerator));
+        rounded_quotient(&scaled, &denominator)
+    };
+    match (coordinate(a1.x, &va.0), coordinate(a1.y, &va.1)) {
+        (Some(x), Some(y)) => LineIntersection::Point(Coord { x, y }),
+        _ => inexact_intersection(a1, a2, b1, b2),
+    }
+}
+
+/// Intersection of collinear segments, which consists of input points only.
+fn collinear_intersection<F>(a1: Coord<F>, a2: Coord<F>, b1: Coord<F>, b2: Coord<F>) -> LineIntersection<F>
+where
+    F: Float,
+{
+    // Points on a common line are ordered along it by their coordinates.
+    let compare = |p: &Coord<F>, q: &Coord<F>| {
+        p.x.partial_cmp(&q.x)
+            .unwrap_or(Ordering::Equal)
+            .then(p.y.partial_cmp(&q.y).unwrap_or(Ordering::Equal))
+    };
+    let ordered = |p, q| match compare(&p, &q) {
+        Ordering::Greater => (q, p),
+        _ => (p, q),
+    };
+    let (a_min, a_max) = ordered(a1, a2);
+    let (b_min, b_max) = ordered(b1, b2);
+    let start = std::cmp::max_by(a_min, b_min, compare);
+    let end = std::cmp::min_by(a_max, b_max, compare);
+
+    match compare(&start, &end) {
+        Ordering::Greater => LineIntersection::None,
+        Ordering::Equal => LineIntersection::Point(start),
+        // The overlap is reported in the direction of the first segment.
+        Ordering::Less if compare(&a1, &a2) == Ordering::Less => LineIntersection::Overlap(start, end),
+        Ordering::Less => LineIntersection::Overlap(end, start),
+    }
+}
+
+/// The value of `numerator / denominator` for a positive denominator, rounded to the nearest
+/// value of `F`, with ties rounded to even. Returns `None` if the computation overflows.
+fn rounded_quotient<F>(numerator: &Expansion, denominator: &Expansion) -> Option<F>
+where
+    F: Float,
+{
+    // Whether the exact quotient is above, at or below the midpoint between `q` and `neighbor`.
+    let twice_numerator = numerator.scale(2.);
+    let compare_to_midpoint = |q: F, neighbor: F| {
+        let difference = twice_numerator.sub(&denominator.scale(q.into()).sum(&denominator.scale(neighbor.into())));
+        Some(difference.signum()).filter(|_| difference.estimate().is_finite())
+    };
+    #[allow(clippy::manual_is_multiple_of)] // `is_multiple_of` requires Rust 1.87
+    let is_even = |q: F| q.integer_decode().0 % 2 == 0;
+
+    // The estimate is off by a few ulps at most, so only a few steps are needed.
+    let mut q = F::from(numerator.estimate() / denominator.estimate()).filter(|q| q.is_finite())?;
+    loop {
+        let up = q.nextafter(true);
+        match compare_to_midpoint(q, up)? {
+            Ordering::Greater => {
+                q = up;
+                continue;
+            }
+            Ordering::Equal if !is_even(q) => return Some(up),
+            _ => {}
+        }
+        let down = q.nextafter(false);
+        match compare_to_midpoint(q, down)? {
+            Ordering::Less => q = down,
+            Ordering::Equal if !is_even(q) => return Some(down),
+            _ => return Some(q),
+        }
+    }
+}
+
+fn mid_point<F>(p: Coord<F>, s: F, d: Coord<F>) -> Coord<F>
+where
+    F: OrderedField,
 {
     Coord {
         x: p.x + s * d.x,
@@ -166,7 +323,7 @@ where
 #[inline]
 fn cross_product<F>(a: Coord<F>, b: Coord<F>) -> F
 where
-    F: Float,
+    F: OrderedField,
 {
     a.x * b.y - a.y * b.x
 }
@@ -174,7 +331,7 @@ where
 #[inline]
 fn dot_product<F>(a: Coord<F>, b: Coord<F>) -> F
 where
-    F: Float,
+    F: OrderedField,
 {
     a.x * b.x + a.y * b.y
 }
@@ -182,6 +339,7 @@ where
 #[cfg(test)]
 mod test {
     use super::super::helper::test::xy;
+    use super::super::helper::NextAfter;
     use super::*;
 
     fn rect(min: Coord<f64>, max: Coord<f64>) -> BoundingBox<f64> {
@@ -332,4 +490,74 @@ mod test {
             LineIntersection::None
         );
     }
+
+    #[test]
+    fn test_exact_intersection() {
+        assert_eq!(
+            exact_intersection(xy(0, 0), xy(3, 3), xy(0, 1), xy(2, 0)),
+            LineIntersection::Point(xy(2. / 3., 2. / 3.))
+        );
+        assert_eq!(
+            exact_intersection(
+                Coord { x: 0_f32, y: 0. },
+                Coord { x: 3., y: 3. },
+                Coord { x: 0., y: 1. },
+                Coord { x: 2., y: 0. }
+            ),
+            LineIntersection::Point(Coord {
+                x: 2_f32 / 3.,
+                y: 2_f32 / 3.
+            })
+        );
+        assert_eq!(
+            exact_intersection(xy(0, 0), xy(1, 1), xy(0.5, 0.5), xy(1, 0)),
+            LineIntersection::Point(xy(0.5, 0.5))
+        );
+        assert_eq!(
+            exact_intersection(xy(1, 1), xy(0, 0), xy(0, 0), xy(1, 1)),
+            LineIntersection::Overlap(xy(1, 1), xy(0, 0))
+        );
+        assert_eq!(
+            exact_intersection(xy(3, 3), xy(10, 10), xy(0, 0), xy(5, 5)),
+            LineIntersection::Overlap(xy(3, 3), xy(5, 5))
+        );
+        assert_eq!(
+            exact_intersection(xy(0, 0), xy(1, 1), xy(1, 1), xy(2, 2)),
+            LineIntersection::Point(xy(1, 1))
+        );
+        assert_eq!(
+            exact_intersection(xy(0, 0), xy(1, 1), xy(2, 2), xy(4, 4)),
+            LineIntersection::None
+        );
+
+        // The second segment ends on the first one, or one ulp above it. The floating point
+        // computation misclassifies both cases.
+        let x = 0.2 + 0.013;
+        assert_eq!(
+            intersection_impl(xy(0, 0), xy(17.3, 17.3), xy(0.1, 300), xy(x, x)),
+            LineIntersection::None
+        );
+        assert_eq!(
+            exact_intersection(xy(0, 0), xy(17.3, 17.3), xy(0.1, 300), xy(x, x)),
+            LineIntersection::Point(xy(x, x))
+        );
+        let x = 0.1 + 0.013;
+        let above = x.nextafter(true);
+        assert!(matches!(
+            intersection_impl(xy(0, 0), xy(17.3, 17.3), xy(0.1, 300), xy(x, above)),
+            LineIntersection::Point(_)
+        ));
+        assert_eq!(
+            exact_intersection(xy(0, 0), xy(17.3, 17.3), xy(0.1, 300), xy(x, above)),
+            LineIntersection::None
+        );
+
+        // The product of the start coordinate and the squared length overflows, so the point
+        // is computed in floating point arithmetic.
+        let (a1, a2) = (xy(1e110, 0), xy(1e110 + 2e100, 2e100));
+        let (b1, b2) = (xy(1e110, 2e100), xy(1e110 + 2e100, 0));
+        let inter = exact_intersection(a1, a2, b1, b2);
+        assert_eq!(inter, inexact_intersection(a1, a2, b1, b2));
+        assert!(matches!(inter, LineIntersection::Point(p) if p.x.is_finite() && p.y.is_finite()));
+    }
 }
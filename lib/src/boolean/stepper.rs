@@ -0,0 +1,325 @@
+use super::connect_edges::connect_edges;
+use super::error::BooleanOpError;
+use super::event_queue::EventQueue;
+use super::fill_queue::fill_queue;
+use super::helper::Float;
+use super::observer::SweepObserver;
+use super::subdivide_segments::Sweep;
+use super::sweep_event::{EdgeType, EventArena, EventId, ResultTransition};
+use super::sweep_line::SplaySweepLine;
+use super::{all_coords_finite, contours_to_multi_polygon, empty_bounding_box, FillRules, Operation};
+use geo_types::{Coord, MultiPolygon, Polygon};
+
+/// Runs the sweep of a boolean operation one event at a time, e.g. to inspect or visualize
+/// how the algorithm arrives at its result.
+///
+/// Every item describes the processing of one event, see `SweepStep`. The iteration ends
+/// when all events which can contribute to the result have been processed, or after the
+/// first error. In contrast to `BooleanOp`, the sweep is performed even if the bounding
+/// boxes of the operands are disjoint.
+pub struct SweepStepper<F>
+where
+    F: Float,
+{
+    events: EventArena<F>,
+    event_queue: EventQueue,
+    sweep: Sweep<F, SplaySweepLine>,
+    done: bool,
+}
+
+/// Snapshot of the sweep after processing one event.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SweepStep<F>
+where
+    F: Float,
+{
+    /// Id of the processed event in the arena, see `SweepStepper::events`.
+    pub event: EventId,
+    pub point: Coord<F>,
+    pub is_left: bool,
+    /// The segment the event belongs to, after the divisions of the step.
+    pub segment: StepSegment<F>,
+    /// The segments intersecting the sweep line after the step, from bottom to top.
+    pub sweep_line: Vec<StepSegment<F>>,
+    /// The fields computed during the step, in the order of computation. A segment appears
+    /// more than once if its fields are recomputed after an overlap has been found.
+    pub fields: Vec<StepFields<F>>,
+    /// The segments divided during the step, at intersections or at the endpoints of
+    /// overlapping segments.
+    pub splits: Vec<StepSplit<F>>,
+}
+
+/// A segment, identified by its left event.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StepSegment<F>
+where
+    F: Float,
+{
+    pub left_event: EventId,
+    pub start: Coord<F>,
+    pub end: Coord<F>,
+    pub is_subject: bool,
+    pub contour_id: u32,
+}
+
+/// The fields of a segment computed by `compute_fields`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StepFields<F>
+where
+    F: Float,
+{
+    pub segment: StepSegment<F>,
+    /// Whether the segment is an in-out transition of its own operand, seen from below.
+    pub in_out: bool,
+    /// Whether the closest segment of the other operand below is an in-out transition.
+    pub other_in_out: bool,
+    pub edge_type: EdgeType,
+    pub result_transition: ResultTransition,
+    pub in_result: bool,
+}
+
+/// Division of a segment at `point` into two parts.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StepSplit<F>
+where
+    F: Float,
+{
+    pub point: Coord<F>,
+    /// The part left of `point`, which keeps the left event of the divided segment.
+    pub before: StepSegment<F>,
+    /// The part right of `point`, which keeps the right event of the divided segment.
+    pub after: StepSegment<F>,
+}
+
+impl<F> SweepStepper<F>
+where
+    F: Float,
+{
+    pub fn new(
+        subject: &[Polygon<F>],
+        clipping: &[Polygon<F>],
+        operation: Operation,
+        fill_rules: FillRules,
+    ) -> Result<SweepStepper<F>, BooleanOpError> {
+        if !all_coords_finite(subject) || !all_coords_finite(clipping) {
+            return Err(BooleanOpError::NonFiniteCoordinate);
+        }
+        if operation.is_in_result(false, false) {
+            return Err(BooleanOpError::UnboundedResult);
+        }
+
+        let mut sbbox = empty_bounding_box();
+        let mut cbbox = sbbox;
+        let mut events = EventArena::new();
+        let event_queue = fill_queue(subject, clipping, &mut sbbox, &mut cbbox, operation, &mut events);
+
+        Ok(SweepStepper {
+            events,
+            event_queue,
+            sweep: Sweep::new(&sbbox, &cbbox, operation, fill_rules),
+            done: false,
+        })
+    }
+
+    /// All events created so far, including the ones created by dividing segments.
+    pub fn events(&self) -> &EventArena<F> {
+        &self.events
+    }
+
+    /// Performs the remaining steps and connects the resulting edges, which yields the
+    /// result of the operation.
+    pub fn finish(mut self) -> Result<MultiPolygon<F>, BooleanOpError> {
+        while !self.done {
+            self.step()?;
+        }
+        let contours = connect_edges(&mut self.events, &self.sweep.sorted_events)?;
+        Ok(contours_to_multi_polygon(&contours))
+    }
+
+    fn step(&mut self) -> Result<Option<SweepStep<F>>, BooleanOpError> {
+        let mut recorder = StepRecorder {
+            fields: Vec::new(),
+            splits: Vec::new(),
+        };
+        let result = self.sweep.step(&mut self.events, &mut self.event_queue, &mut recorder);
+        let event = match result {
+            Ok(Some(event)) => event,
+            Ok(None) => {
+                self.done = true;
+                return Ok(None);
+            }
+            Err(err) => {
+                self.done = true;
+                return Err(err);
+            }
+        };
+
+        let events = &self.events;
+        let splits = recorder
+            .splits
+            .into_iter()
+            .map(|(end, start)| StepSplit {
+                point: events[end].point,
+                before: segment_of(events, end),
+                after: segment_of(events, start),
+            })
+            .collect();
+
+        Ok(Some(SweepStep {
+            event,
+            point: events[event].point,
+            is_left: events[event].is_left(),
+            segment: segment_of(events, event),
+            sweep_line: self.sweep.sweep_line.iter().map(|id| segment_of(events, id)).collect(),
+            fields: recorder.fields,
+            splits,
+        }))
+    }
+}
+
+/// Observer recording the fields computed and the segments divided during one step.
+struct StepRecorder<F>
+where
+    F: Float,
+{
+    fields: Vec<StepFields<F>>,
+    /// The new events ending and starting the parts of the divided segments.
+    splits: Vec<(EventId, EventId)>,
+}
+
+impl<F> SweepObserver<F> for StepRecorder<F>
+where
+    F: Float,
+{
+    fn split(&mut self, _events: &EventArena<F>, end: EventId, start: EventId) {
+        self.splits.push((end, start));
+    }
+
+    fn compute_fields(&mut self, events: &EventArena<F>, event: EventId, _prev: Option<EventId>) {
+        let se = &events[event];
+        self.fields.push(StepFields {
+            segment: segment_of(events, event),
+            in_out: se.is_in_out(),
+            other_in_out: se.is_other_in_out(),
+            edge_type: se.get_edge_type(),
+            result_transition: se.get_result_transition(),
+            in_result: se.is_in_result(),
+        });
+    }
+}
+
+impl<F> Iterator for SweepStepper<F>
+where
+    F: Float,
+{
+    type Item = Result<SweepStep<F>, BooleanOpError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.step().transpose()
+    }
+}
+
+fn segment_of<F>(events: &EventArena<F>, event: EventId) -> StepSegment<F>
+where
+    F: Float,
+{
+    let (left, right) = match events[event].get_other_event() {
+        Some(other) if !events[event].is_left() => (other, event),
+        Some(other) => (event, other),
+        None => (event, event),
+    };
+    StepSegment {
+        left_event: left,
+        start: events[left].point,
+        end: events[right].point,
+        is_subject: events[left].is_subject,
+        contour_id: events[left].contour_id,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boolean::BooleanOp;
+    use geo_types::{coord, polygon};
+
+    fn squares() -> (Polygon<f64>, Polygon<f64>) {
+        let subject = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)];
+        let clipping = polygon![(x: 1., y: 1.), (x: 3., y: 1.), (x: 3., y: 3.), (x: 1., y: 3.), (x: 1., y: 1.)];
+        (subject, clipping)
+    }
+
+    #[test]
+    fn test_steps_of_crossing_squares() {
+        let (subject, clipping) = squares();
+        let stepper = SweepStepper::new(&[subject], &[clipping], Operation::Union, FillRules::default()).unwrap();
+        let steps: Vec<_> = stepper.collect::<Result<_, _>>().unwrap();
+
+        // 8 input segments, 4 of which are divided once by the two intersections.
+        assert_eq!(steps.len(), 24);
+        assert!(steps.iter().all(|step| step.is_left != step.fields.is_empty()));
+        assert!(steps.last().unwrap().sweep_line.is_empty());
+
+        let split_points: Vec<_> = steps
+            .iter()
+            .flat_map(|step| &step.splits)
+            .map(|split| split.point)
+            .collect();
+        assert_eq!(split_points.len(), 4);
+        assert!(split_points
+            .iter()
+            .all(|p| *p == coord! {x: 1., y: 2.} || *p == coord! {x: 2., y: 1.}));
+        for split in steps.iter().flat_map(|step| &step.splits) {
+            assert_eq!(split.before.end, split.point);
+            assert_eq!(split.after.start, split.point);
+        }
+
+        // The sweep line is ordered from bottom to top.
+        for step in &steps {
+            let at_x = |s: &StepSegment<f64>| {
+                if s.start.x == s.end.x {
+                    s.start.y
+                } else {
+                    let t = (step.point.x - s.start.x) / (s.end.x - s.start.x);
+                    s.start.y + t * (s.end.y - s.start.y)
+                }
+            };
+            assert!(step.sweep_line.windows(2).all(|w| at_x(&w[0]) <= at_x(&w[1])));
+        }
+    }
+
+    #[test]
+    fn test_finish_after_partial_iteration() {
+        let (subject, clipping) = squares();
+        for operation in [
+            Operation::Intersection,
+            Operation::Union,
+            Operation::Difference,
+            Operation::Xor,
+        ] {
+            let mut stepper = SweepStepper::new(
+                std::slice::from_ref(&subject),
+                std::slice::from_ref(&clipping),
+                operation,
+                FillRules::default(),
+            )
+            .unwrap();
+            stepper.by_ref().take(5).for_each(|step| assert!(step.is_ok()));
+
+            assert_eq!(stepper.finish().unwrap(), subject.boolean(&clipping, operation));
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        let (subject, clipping) = squares();
+        let unbounded = Operation::Custom(|subject_in, clipping_in| !(subject_in && clipping_in));
+        assert_eq!(
+            SweepStepper::new(&[subject], &[clipping], unbounded, FillRules::default()).err(),
+            Some(BooleanOpError::UnboundedResult)
+        );
+    }
+}
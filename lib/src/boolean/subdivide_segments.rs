@@ -1,103 +1,775 @@
-use super::compare_segments::compare_segments;
-use super::compute_fields::compute_fields;
-use super::helper::{BoundingBox, Float};
+use super::compare_segments::segment_order;
+use super::compute_fields::{compute_fields, is_coincident};
+use super::divide_segment::divide_segment;
+use super::error::BooleanOpError;
+use super::event_queue::EventQueue;
+use super::helper::{BoundingBox, OrderedField};
+use super::kernel::Kernel;
+use super::observer::SweepObserver;
+use super::payload::VertexPayload;
 use super::possible_intersection::possible_intersection;
-use super::sweep_event::SweepEvent;
-use super::Operation;
-use crate::splay::SplaySet;
-use std::collections::BinaryHeap;
-use std::rc::Rc;
+use super::provenance::EdgeSource;
+use super::sweep_event::{EdgeType, EventArena, EventId};
+use super::sweep_line::{SplaySweepLine, SweepLineStatus};
+use super::{FillRules, Operation};
+use geo_types::Coord;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
-#[cfg(feature = "debug-booleanop")]
-use super::sweep_event::JsonDebug;
+pub fn subdivide<F, K, P>(
+    events: &mut EventArena<F, K, P>,
+    event_queue: &mut EventQueue,
+    sbbox: &BoundingBox<F>,
+    cbbox: &BoundingBox<F>,
+    operation: Operation,
+    fill_rules: FillRules,
+) -> Result<Vec<EventId>, BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+{
+    subdivide_with_sweep_line::<F, K, P, SplaySweepLine>(events, event_queue, sbbox, cbbox, operation, fill_rules)
+}
 
-pub fn subdivide<F>(
-    event_queue: &mut BinaryHeap<Rc<SweepEvent<F>>>,
+/// Variant of `subdivide` which maintains the segments intersecting the sweep line in `S`.
+pub fn subdivide_with_sweep_line<F, K, P, S>(
+    events: &mut EventArena<F, K, P>,
+    event_queue: &mut EventQueue,
     sbbox: &BoundingBox<F>,
     cbbox: &BoundingBox<F>,
     operation: Operation,
-) -> Vec<Rc<SweepEvent<F>>>
+    fill_rules: FillRules,
+) -> Result<Vec<EventId>, BooleanOpError>
 where
-    F: Float,
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    S: SweepLineStatus,
 {
-    let mut sweep_line = SplaySet::<Rc<SweepEvent<F>>, _>::new(compare_segments);
-    let mut sorted_events: Vec<Rc<SweepEvent<F>>> = Vec::new();
-    let rightbound = sbbox.max.x.min(cbbox.max.x);
+    Sweep::<F, S>::new(sbbox, cbbox, operation, fill_rules).run(events, event_queue, &mut ())
+}
 
-    while let Some(event) = event_queue.pop() {
-        #[cfg(feature = "debug-booleanop")]
-        {
-            println!("\n{{\"processEvent\": {}}}", event.to_json_debug());
+/// Variant of `subdivide` which reports the progress of the sweep to `observer`.
+pub fn subdivide_with_observer<F, K, P, O>(
+    events: &mut EventArena<F, K, P>,
+    event_queue: &mut EventQueue,
+    sbbox: &BoundingBox<F>,
+    cbbox: &BoundingBox<F>,
+    operation: Operation,
+    fill_rules: FillRules,
+    observer: &mut O,
+) -> Result<Vec<EventId>, BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    O: SweepObserver<F, K, P>,
+{
+    Sweep::<F, SplaySweepLine>::new(sbbox, cbbox, operation, fill_rules).run(events, event_queue, observer)
+}
+
+/// Variant of `subdivide_with_observer` which processes all events, so that the fields
+/// reported to `observer` can be used to classify the events for any operation.
+pub fn subdivide_observing<F, K, P, O>(
+    events: &mut EventArena<F, K, P>,
+    event_queue: &mut EventQueue,
+    sbbox: &BoundingBox<F>,
+    cbbox: &BoundingBox<F>,
+    fill_rules: FillRules,
+    observer: &mut O,
+) -> Result<Vec<EventId>, BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    O: SweepObserver<F, K, P>,
+{
+    // In contrast to intersection and difference, a union never terminates the sweep early.
+    Sweep::<F, SplaySweepLine>::new(sbbox, cbbox, Operation::Union, fill_rules).run(events, event_queue, observer)
+}
+
+/// State of the sweep in between processing two events, which allows to process the
+/// events one by one.
+pub(crate) struct Sweep<F, S> {
+    pub sweep_line: S,
+    /// The processed events in the order of processing.
+    pub sorted_events: Vec<EventId>,
+    sweep_end: Option<F>,
+    operation: Operation,
+    fill_rules: FillRules,
+    division_counts: DivisionCounts,
+}
+
+/// Maximum number of intersection checks of segments of the same pair of input edges which
+/// divide the segments. A crossing divides both edges once, and the checks of the divided
+/// parts may divide them again where the rounded intersection point is not exactly on them,
+/// which takes at most two checks for the test cases. The number of events is thus at most
+/// linear in the number of intersecting pairs of input edges.
+const MAX_DIVISIONS_PER_EDGE_PAIR: usize = 16;
+
+/// Number of intersection checks which divided segments, per pair of input edges. Exceeding
+/// `MAX_DIVISIONS_PER_EDGE_PAIR` means that rounded intersection points keep dividing the same
+/// segments, which is reported as soon as it happens, rather than after the number of events
+/// has grown with the number of all pairs of segments.
+#[derive(Default)]
+struct DivisionCounts(HashMap<(EdgeSource, EdgeSource), usize>);
+
+impl DivisionCounts {
+    fn count(&mut self, a: EdgeSource, b: EdgeSource) -> Result<(), BooleanOpError> {
+        let count = self.0.entry((a.min(b), a.max(b))).or_insert(0);
+        *count += 1;
+        if *count > MAX_DIVISIONS_PER_EDGE_PAIR {
+            return Err(BooleanOpError::EventLimitExceeded);
+        }
+        Ok(())
+    }
+}
+
+impl<F, S> Sweep<F, S>
+where
+    F: OrderedField,
+    S: SweepLineStatus,
+{
+    pub fn new(sbbox: &BoundingBox<F>, cbbox: &BoundingBox<F>, operation: Operation, fill_rules: FillRules) -> Self {
+        Sweep {
+            // The order of the segments depends on the arena, which is modified during the
+            // sweep, so the comparator has to be passed to every operation on the sweep line.
+            sweep_line: S::default(),
+            sorted_events: Vec::new(),
+            sweep_end: operation.sweep_end(sbbox, cbbox),
+            operation,
+            fill_rules,
+            division_counts: DivisionCounts::default(),
         }
-        sorted_events.push(event.clone());
+    }
+
+    /// Processes all events which can contribute to the result and returns them in the order
+    /// of processing.
+    pub fn run<K, P, O>(
+        mut self,
+        events: &mut EventArena<F, K, P>,
+        event_queue: &mut EventQueue,
+        observer: &mut O,
+    ) -> Result<Vec<EventId>, BooleanOpError>
+    where
+        K: Kernel<F>,
+        P: VertexPayload<F>,
+        O: SweepObserver<F, K, P>,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("subdivide").entered();
+
+        while self.step(events, event_queue, observer)?.is_some() {}
+        Ok(self.sorted_events)
+    }
+
+    /// Processes the next event of the queue and returns it, or `None` if all events which
+    /// can contribute to the result have been processed.
+    pub fn step<K, P, O>(
+        &mut self,
+        events: &mut EventArena<F, K, P>,
+        event_queue: &mut EventQueue,
+        observer: &mut O,
+    ) -> Result<Option<EventId>, BooleanOpError>
+    where
+        K: Kernel<F>,
+        P: VertexPayload<F>,
+        O: SweepObserver<F, K, P>,
+    {
+        let Sweep {
+            sweep_line,
+            sorted_events,
+            sweep_end,
+            operation,
+            fill_rules,
+            division_counts,
+        } = self;
+
+        let event = match event_queue.pop(events) {
+            Some(event) => event,
+            None => return Ok(None),
+        };
 
-        if operation == Operation::Intersection && event.point.x > rightbound
-            || operation == Operation::Difference && event.point.x > sbbox.max.x
+        #[cfg(feature = "tracing")]
         {
-            break;
+            let se = &events[event];
+            tracing::trace!(
+                event,
+                x = ?se.point.x,
+                y = ?se.point.y,
+                is_left = se.is_left(),
+                is_subject = se.is_subject,
+                "process event"
+            );
         }
+        observer.process_event(events, event);
+        sorted_events.push(event);
 
-        if event.is_left() {
-            sweep_line.insert(event.clone());
+        if sweep_end.is_some_and(|end| events[event].point.x > end) {
+            return Ok(None);
+        }
+
+        let other_event = events[event]
+            .get_other_event()
+            .ok_or(BooleanOpError::MissingOtherEvent)?;
+
+        if events[event].is_left() {
+            divide_collinear_at_start(events, event, event_queue, observer)?;
 
-            let maybe_prev = sweep_line.prev(&event);
-            let maybe_next = sweep_line.next(&event);
+            let (maybe_prev, maybe_next) = {
+                let comparator = |a: &EventId, b: &EventId| segment_order(events, *a, *b);
+                sweep_line.insert(event, comparator);
+                (sweep_line.prev(event, comparator), sweep_line.next(event, comparator))
+            };
 
-            compute_fields(&event, maybe_prev, operation);
+            compute_and_observe(events, event, maybe_prev, *operation, *fill_rules, observer);
 
             if let Some(next) = maybe_next {
-                #[cfg(feature = "debug-booleanop")]
+                if observed_intersection(events, sweep_line, event, next, event_queue, division_counts, observer)? == 2
                 {
-                    println!("{{\"seNextEvent\": {}}}", next.to_json_debug());
-                }
-                if possible_intersection(&event, next, event_queue) == 2 {
-                    // Recompute fields for current segment and the one above (in bottom to top order)
-                    compute_fields(&event, maybe_prev, operation);
-                    compute_fields(next, Some(&event), operation);
+                    compute_fields_of_group(events, sweep_line, event, *operation, *fill_rules, observer)?;
                 }
             }
 
             if let Some(prev) = maybe_prev {
-                #[cfg(feature = "debug-booleanop")]
+                if observed_intersection(events, sweep_line, prev, event, event_queue, division_counts, observer)? == 2
                 {
-                    println!("{{\"sePrevEvent\": {}}}", prev.to_json_debug());
-                }
-                if possible_intersection(prev, &event, event_queue) == 2 {
-                    let maybe_prev_prev = sweep_line.prev(prev);
-                    // Recompute fields for current segment and the one below (in bottom to top order)
-                    compute_fields(prev, maybe_prev_prev, operation);
-                    compute_fields(&event, Some(prev), operation);
+                    compute_fields_of_group(events, sweep_line, event, *operation, *fill_rules, observer)?;
                 }
             }
-        } else if let Some(other_event) = event.get_other_event() {
-            // This debug assert is only true, if we compare segments in the sweep line
-            // based on identity (curently), and not by value (done previously).
-            debug_assert!(
-                sweep_line.contains(&other_event),
-                "Sweep line misses event to be removed"
-            );
-            if sweep_line.contains(&other_event) {
-                let maybe_prev = sweep_line.prev(&other_event).cloned();
-                let maybe_next = sweep_line.next(&other_event).cloned();
-
-                if let (Some(prev), Some(next)) = (maybe_prev, maybe_next) {
-                    #[cfg(feature = "debug-booleanop")]
-                    {
-                        println!("Possible post intersection");
-                        println!("{{\"sePostNextEvent\": {}}}", next.to_json_debug());
-                        println!("{{\"sePostPrevEvent\": {}}}", prev.to_json_debug());
-                    }
-                    possible_intersection(&prev, &next, event_queue);
-                }
 
-                #[cfg(feature = "debug-booleanop")]
-                {
-                    println!("{{\"removing\": {}}}", other_event.to_json_debug());
-                }
-                sweep_line.remove(&other_event);
+            compute_fields_starting_above(events, sweep_line, event, *operation, *fill_rules, observer)?;
+        } else {
+            let comparator = |a: &EventId, b: &EventId| segment_order(events, *a, *b);
+
+            // The sweep line is guaranteed to contain the left event only if we compare
+            // segments in the sweep line based on identity (curently), and not by value
+            // (done previously).
+            if !sweep_line.contains(other_event, comparator) {
+                return Err(BooleanOpError::SweepLineInconsistency);
             }
+
+            let maybe_prev = sweep_line.prev(other_event, comparator);
+            let maybe_next = sweep_line.next(other_event, comparator);
+
+            if let (Some(prev), Some(next)) = (maybe_prev, maybe_next) {
+                observed_intersection(events, sweep_line, prev, next, event_queue, division_counts, observer)?;
+            }
+
+            #[cfg(feature = "tracing")]
+            tracing::trace!(event = other_event, "remove segment");
+            observer.remove(events, other_event);
+            sweep_line.remove(other_event, |a, b| segment_order(events, *a, *b));
+        }
+
+        Ok(Some(event))
+    }
+}
+
+/// Checks the neighboring segments `below` and `above` for an intersection, and reports
+/// the check and the resulting divisions to `observer`.
+#[allow(clippy::too_many_arguments)]
+fn observed_intersection<F, K, P, S, O>(
+    events: &mut EventArena<F, K, P>,
+    sweep_line: &mut S,
+    below: EventId,
+    above: EventId,
+    event_queue: &mut EventQueue,
+    division_counts: &mut DivisionCounts,
+    observer: &mut O,
+) -> Result<u8, BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    S: SweepLineStatus,
+    O: SweepObserver<F, K, P>,
+{
+    observer.check_intersection(events, below, above);
+    // The groups have to be determined before the division, which changes the position of
+    // a divided segment with respect to the segments it was coincident with.
+    let groups = [
+        coincident_group(events, sweep_line, below)?,
+        coincident_group(events, sweep_line, above)?,
+    ];
+    // Segments starting at the same point become coincident if one of them is divided at the
+    // end of the other one. Coincident segments are ordered differently, so the segment is
+    // taken out of the sweep line while it may change.
+    let may_coincide = events[below].point == events[above].point;
+    if may_coincide {
+        sweep_line.remove(above, |a, b| segment_order(events, *a, *b));
+    }
+    let right_events = [events[below].get_other_event(), events[above].get_other_event()];
+    let first_new_event = events.len();
+    let result = possible_intersection(events, below, above, event_queue);
+    if may_coincide {
+        sweep_line.insert(above, |a, b| segment_order(events, *a, *b));
+    }
+    let result = result?;
+    // A segment divided at a rounded point may be ordered differently with respect to its
+    // neighbors, so it is moved to its new position in the sweep line.
+    for (segment, right_event) in [below, above].into_iter().zip(right_events) {
+        if (may_coincide && segment == above) || events[segment].get_other_event() == right_event {
+            continue;
         }
+        let divided_end = events[segment].get_other_event();
+        events[segment].set_other_event(right_event.ok_or(BooleanOpError::MissingOtherEvent)?);
+        let removed = sweep_line.remove(segment, |a, b| segment_order(events, *a, *b));
+        events[segment].set_other_event(divided_end.ok_or(BooleanOpError::MissingOtherEvent)?);
+        if !removed {
+            return Err(BooleanOpError::SweepLineInconsistency);
+        }
+        sweep_line.insert(segment, |a, b| segment_order(events, *a, *b));
+    }
+    // Every division adds the right event ending the part before the division point,
+    // directly followed by the left event starting the part after it.
+    let end_of_divisions = events.len();
+    if end_of_divisions > first_new_event {
+        division_counts.count(events[below].get_source(), events[above].get_source())?;
+    }
+    for end in (first_new_event..end_of_divisions).step_by(2) {
+        observer.split(events, end, end + 1);
+        let divided = events[end].get_other_event().ok_or(BooleanOpError::MissingOtherEvent)?;
+        let group = if divided == below {
+            groups[0].as_slice()
+        } else if divided == above {
+            groups[1].as_slice()
+        } else {
+            &[]
+        };
+        divide_coincident(events, group, end, event_queue, observer)?;
+    }
+    Ok(result)
+}
+
+/// The other segments of the group of coincident segments `segment` belongs to.
+fn coincident_group<F, K, P, S>(
+    events: &EventArena<F, K, P>,
+    sweep_line: &S,
+    segment: EventId,
+) -> Result<Vec<EventId>, BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    S: SweepLineStatus,
+{
+    let mut group = Vec::new();
+    // Only the overlap of coincident segments changes their edge type.
+    if events[segment].get_edge_type() == EdgeType::Normal {
+        return Ok(group);
+    }
+    // Coincident segments are stacked, so the group is the range of such segments around
+    // the given one.
+    let mut below = segment;
+    while let Some(prev) =
+        segment_below(events, sweep_line, below)?.filter(|&prev| is_coincident(events, prev, segment))
+    {
+        group.push(prev);
+        below = prev;
+    }
+    let mut above = segment;
+    while let Some(next) =
+        segment_above(events, sweep_line, above)?.filter(|&next| is_coincident(events, next, segment))
+    {
+        group.push(next);
+        above = next;
     }
+    Ok(group)
+}
+
+/// Divides the segments of `group`, which were coincident with a segment before it has
+/// been divided, at the same point. The division is given by the right event `end` of the
+/// first part of the divided segment.
+///
+/// Only neighboring segments are checked for intersections, so a division of a segment
+/// would otherwise not reach the other segments of its group. Their windings are only
+/// accumulated as a group if their end points stay identical. Segments whose left events
+/// have not been processed yet are divided as well, because a division point which is not
+/// exactly on the segment would prevent detecting them as coincident later.
+fn divide_coincident<F, K, P, O>(
+    events: &mut EventArena<F, K, P>,
+    group: &[EventId],
+    end: EventId,
+    event_queue: &mut EventQueue,
+    observer: &mut O,
+) -> Result<(), BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    O: SweepObserver<F, K, P>,
+{
+    let divided = events[end].get_other_event().ok_or(BooleanOpError::MissingOtherEvent)?;
+    let start = end + 1;
+    let previous_end = events.other(start).ok_or(BooleanOpError::MissingOtherEvent)?.point;
+
+    // Segments starting at the current point of the sweep may still be queued.
+    let queued = take_events_at(events, event_queue, events[divided].point);
+    let queued_coincident = queued
+        .iter()
+        .copied()
+        .filter(|&id| events[id].is_left() && events.other(id).is_some_and(|other| other.point == previous_end));
+    let group: Vec<EventId> = group.iter().copied().chain(queued_coincident).collect();
 
-    sorted_events
+    let inter = events[end].point;
+    for segment in group {
+        let first_new_event = events.len();
+        divide_segment(events, segment, inter, divided, event_queue)?;
+        observer.split(events, first_new_event, first_new_event + 1);
+    }
+    for id in queued {
+        event_queue.push(events, id);
+    }
+    Ok(())
+}
+
+/// Divides the segments starting at the point of the left event `event` which are collinear
+/// with it at the end of the shortest of them, so that they are coincident up to that point.
+///
+/// Overlaps are otherwise only resolved when the segments are checked as neighbors. If one
+/// of them has been divided at an intersection point by then, which is rounded and thus not
+/// exactly on the segment, they are no longer recognized as collinear.
+fn divide_collinear_at_start<F, K, P, O>(
+    events: &mut EventArena<F, K, P>,
+    event: EventId,
+    event_queue: &mut EventQueue,
+    observer: &mut O,
+) -> Result<(), BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    O: SweepObserver<F, K, P>,
+{
+    let start = events[event].point;
+    let end = events.other(event).ok_or(BooleanOpError::MissingOtherEvent)?.point;
+    let queued = take_events_at(events, event_queue, start);
+
+    // Both end points lie on the same side of the start point, since they are after it.
+    let mut collinear = vec![event];
+    collinear.extend(queued.iter().copied().filter(|&id| {
+        events[id].is_left()
+            && events
+                .other(id)
+                .is_some_and(|other| K::orientation(start, end, other.point) == Ordering::Equal)
+    }));
+    if collinear.len() > 1 {
+        let ends = collinear
+            .iter()
+            .map(|&id| events.other(id).map(|other| other.point))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(BooleanOpError::MissingOtherEvent)?;
+        let shortest = ends
+            .iter()
+            .copied()
+            .min_by(|a, b| K::compare_points(*a, *b))
+            .unwrap_or(end);
+        for (&segment, &segment_end) in collinear.iter().zip(&ends) {
+            if segment_end != shortest {
+                let first_new_event = events.len();
+                divide_segment(events, segment, shortest, event, event_queue)?;
+                observer.split(events, first_new_event, first_new_event + 1);
+            }
+        }
+    }
+
+    for id in queued {
+        event_queue.push(events, id);
+    }
+    Ok(())
+}
+
+/// Takes the events at `point` out of the queue, if it is the point of the next event.
+///
+/// The position of a queued left event depends on its right event, so events have to be
+/// taken out of the queue while their segments are divided.
+fn take_events_at<F, K, P>(events: &EventArena<F, K, P>, event_queue: &mut EventQueue, point: Coord<F>) -> Vec<EventId>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    let mut taken = Vec::new();
+    while let Some(next) = event_queue.peek().filter(|&next| events[next].point == point) {
+        event_queue.pop(events);
+        taken.push(next);
+    }
+    taken
+}
+
+/// Recomputes the fields of the group of coincident segments `event` belongs to, from the
+/// bottom to the top, after segments have become coincident.
+fn compute_fields_of_group<F, K, P, S, O>(
+    events: &mut EventArena<F, K, P>,
+    sweep_line: &S,
+    event: EventId,
+    operation: Operation,
+    fill_rules: FillRules,
+    observer: &mut O,
+) -> Result<(), BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    S: SweepLineStatus,
+    O: SweepObserver<F, K, P>,
+{
+    let mut bottom = event;
+    while let Some(prev) =
+        segment_below(events, sweep_line, bottom)?.filter(|&prev| is_coincident(events, prev, bottom))
+    {
+        bottom = prev;
+    }
+    let maybe_prev = segment_below(events, sweep_line, bottom)?;
+    compute_and_observe(events, bottom, maybe_prev, operation, fill_rules, observer);
+    compute_fields_of_coincident_above(events, sweep_line, bottom, operation, fill_rules, observer)
+}
+
+/// Recomputes the fields of the segments above `event` which start at the same point.
+///
+/// Segments starting at the same point are usually processed from the bottom to the top.
+/// A segment passing the point is only divided there when the segment starting above it
+/// is checked for intersections, which is after the fields of that segment have been
+/// computed with the undivided one below. The part after the division is then inserted
+/// below segments which depend on it. The fields are recomputed after the intersection
+/// checks of `event`, which may make it non-contributing.
+fn compute_fields_starting_above<F, K, P, S, O>(
+    events: &mut EventArena<F, K, P>,
+    sweep_line: &S,
+    event: EventId,
+    operation: Operation,
+    fill_rules: FillRules,
+    observer: &mut O,
+) -> Result<(), BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    S: SweepLineStatus,
+    O: SweepObserver<F, K, P>,
+{
+    let mut below = event;
+    while let Some(next) =
+        segment_above(events, sweep_line, below)?.filter(|&next| events[next].point == events[event].point)
+    {
+        compute_and_observe(events, next, Some(below), operation, fill_rules, observer);
+        below = next;
+    }
+    Ok(())
+}
+
+/// Recomputes the fields of all segments stacked on top of `event` which coincide with it,
+/// because their winding numbers depend on all coincident segments below them.
+fn compute_fields_of_coincident_above<F, K, P, S, O>(
+    events: &mut EventArena<F, K, P>,
+    sweep_line: &S,
+    event: EventId,
+    operation: Operation,
+    fill_rules: FillRules,
+    observer: &mut O,
+) -> Result<(), BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    S: SweepLineStatus,
+    O: SweepObserver<F, K, P>,
+{
+    let mut below = event;
+    while let Some(next) = segment_above(events, sweep_line, below)? {
+        if !is_coincident(events, next, below) {
+            break;
+        }
+        compute_and_observe(events, next, Some(below), operation, fill_rules, observer);
+        below = next;
+    }
+    Ok(())
+}
+
+/// Returns the segment above `event` in the sweep line.
+///
+/// The sweep line finds `event` by comparing it with the segments it contains, so if the
+/// predicates of the kernel are not exact, e.g. with `FastKernel`, comparisons contradicting
+/// each other make it return a neighbor of another segment. Walks along the sweep line would
+/// then go back and forth between the same segments, which is reported as an inconsistency
+/// instead.
+fn segment_above<F, K, P, S>(
+    events: &EventArena<F, K, P>,
+    sweep_line: &S,
+    event: EventId,
+) -> Result<Option<EventId>, BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    S: SweepLineStatus,
+{
+    match sweep_line.next(event, |a, b| segment_order(events, *a, *b)) {
+        Some(next) if !is_ordered(events, event, next) => Err(BooleanOpError::SweepLineInconsistency),
+        maybe_next => Ok(maybe_next),
+    }
+}
+
+/// Returns the segment below `event` in the sweep line, see `segment_above`.
+fn segment_below<F, K, P, S>(
+    events: &EventArena<F, K, P>,
+    sweep_line: &S,
+    event: EventId,
+) -> Result<Option<EventId>, BooleanOpError>
+where
+    F: OrderedField,
+    K: Kernel<F>,
+    S: SweepLineStatus,
+{
+    match sweep_line.prev(event, |a, b| segment_order(events, *a, *b)) {
+        Some(prev) if !is_ordered(events, prev, event) => Err(BooleanOpError::SweepLineInconsistency),
+        maybe_prev => Ok(maybe_prev),
+    }
+}
+
+/// Whether the segment `below` is ordered below `above`, comparing them in both directions.
+fn is_ordered<F, K, P>(events: &EventArena<F, K, P>, below: EventId, above: EventId) -> bool
+where
+    F: OrderedField,
+    K: Kernel<F>,
+{
+    segment_order(events, below, above) == Ordering::Less && segment_order(events, above, below) == Ordering::Greater
+}
+
+fn compute_and_observe<F, K, P, O>(
+    events: &mut EventArena<F, K, P>,
+    event: EventId,
+    maybe_prev: Option<EventId>,
+    operation: Operation,
+    fill_rules: FillRules,
+    observer: &mut O,
+) where
+    F: OrderedField,
+    K: Kernel<F>,
+    P: VertexPayload<F>,
+    O: SweepObserver<F, K, P>,
+{
+    compute_fields(events, event, maybe_prev, operation, fill_rules);
+    observer.compute_fields(events, event, maybe_prev);
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::helper::test::xy;
+    use super::super::sweep_event::SweepEvent;
+    use super::*;
+
+    /// Adds the segment from `left` to `right` and queues both of its events.
+    fn segment(
+        events: &mut EventArena<f64>,
+        queue: &mut EventQueue,
+        left: Coord<f64>,
+        right: Coord<f64>,
+        is_subject: bool,
+    ) -> EventId {
+        let (se, other) = events.push_pair(
+            SweepEvent::new(0, left, true, None, is_subject, true),
+            SweepEvent::new(0, right, false, None, is_subject, true),
+        );
+        queue.push(events, se);
+        queue.push(events, other);
+        se
+    }
+
+    fn end_point(events: &EventArena<f64>, se: EventId) -> Coord<f64> {
+        events.other(se).unwrap().point
+    }
+
+    #[test]
+    fn test_divide_collinear_at_start() {
+        let (mut events, mut queue) = (EventArena::new(), EventQueue::new());
+        let long = segment(&mut events, &mut queue, xy(0, 0), xy(3, 3), true);
+        let short = segment(&mut events, &mut queue, xy(0, 0), xy(1, 1), false);
+        let other = segment(&mut events, &mut queue, xy(0, 0), xy(1, 2), false);
+        let queued = queue.len();
+
+        let event = queue.pop(&events).unwrap();
+        divide_collinear_at_start(&mut events, event, &mut queue, &mut ()).unwrap();
+
+        // Only the long segment is divided, at the end of the short one.
+        assert_eq!(end_point(&events, long), xy(1, 1));
+        assert_eq!(end_point(&events, short), xy(1, 1));
+        assert_eq!(end_point(&events, other), xy(1, 2));
+        assert_eq!(queue.len(), queued - 1 + 2);
+    }
+
+    #[test]
+    fn test_coincident_group() {
+        let (mut events, mut queue) = (EventArena::new(), EventQueue::new());
+        let coincident = [
+            segment(&mut events, &mut queue, xy(0, 0), xy(2, 2), true),
+            segment(&mut events, &mut queue, xy(0, 0), xy(2, 2), false),
+            segment(&mut events, &mut queue, xy(0, 0), xy(2, 2), true),
+        ];
+        let below = segment(&mut events, &mut queue, xy(0, 0), xy(2, 0), true);
+        let above = segment(&mut events, &mut queue, xy(0, 0), xy(0, 2), false);
+        events[coincident[1]].set_edge_type(EdgeType::NonContributing);
+
+        let mut sweep_line = SplaySweepLine::default();
+        for se in coincident.iter().copied().chain([below, above]) {
+            sweep_line.insert(se, |a, b| segment_order(&events, *a, *b));
+        }
+
+        let mut group = coincident_group(&events, &sweep_line, coincident[1]).unwrap();
+        group.sort();
+        assert_eq!(group, vec![coincident[0], coincident[2]]);
+        // The group is only determined for segments whose edge type shows an overlap.
+        assert_eq!(coincident_group(&events, &sweep_line, coincident[0]).unwrap(), vec![]);
+        assert_eq!(coincident_group(&events, &sweep_line, below).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_divide_coincident() {
+        let (mut events, mut queue) = (EventArena::new(), EventQueue::new());
+        let divided = segment(&mut events, &mut queue, xy(0, 0), xy(2, 2), true);
+        let group = segment(&mut events, &mut queue, xy(0, 0), xy(2, 2), false);
+        let queued = segment(&mut events, &mut queue, xy(0, 0), xy(2, 2), true);
+        let other = segment(&mut events, &mut queue, xy(0, 0), xy(2, 0), false);
+        for _ in 0..2 {
+            queue.pop(&events);
+        }
+
+        let end = events.len();
+        divide_segment(&mut events, divided, xy(1, 1), group, &mut queue).unwrap();
+        divide_coincident(&mut events, &[group], end, &mut queue, &mut ()).unwrap();
+
+        // The queued segment coincident with the divided one is divided as well.
+        assert_eq!(end_point(&events, group), xy(1, 1));
+        assert_eq!(end_point(&events, queued), xy(1, 1));
+        assert_eq!(end_point(&events, other), xy(2, 0));
+    }
+
+    #[test]
+    fn test_compute_fields_starting_above() {
+        let (mut events, mut queue) = (EventArena::new(), EventQueue::new());
+        let lower = segment(&mut events, &mut queue, xy(0, 0), xy(2, 0), true);
+        let upper = segment(&mut events, &mut queue, xy(0, 0), xy(2, 2), true);
+        let elsewhere = segment(&mut events, &mut queue, xy(0, 1), xy(2, 3), true);
+        for se in [lower, upper, elsewhere] {
+            events[se].set_winding(1);
+        }
+
+        let mut sweep_line = SplaySweepLine::default();
+        for se in [lower, upper, elsewhere] {
+            sweep_line.insert(se, |a, b| segment_order(&events, *a, *b));
+        }
+        compute_fields(&mut events, lower, None, Operation::Union, FillRules::default());
+        compute_fields_starting_above(
+            &mut events,
+            &sweep_line,
+            lower,
+            Operation::Union,
+            FillRules::default(),
+            &mut (),
+        )
+        .unwrap();
+
+        // Only the segment starting at the same point is recomputed.
+        assert_eq!(events[upper].get_winding_below(), 1);
+        assert_eq!(events[elsewhere].get_winding_below(), 0);
+    }
 }
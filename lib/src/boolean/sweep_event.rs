@@ -1,11 +1,15 @@
-use super::helper::Float;
+use super::helper::OrderedField;
 use geo_types::Coord;
-use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::rc::{Rc, Weak};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
 
 use super::helper::less_if;
-use super::signed_area::signed_area;
+use super::kernel::{DefaultKernel, Kernel};
+use super::provenance::EdgeSource;
+
+/// Index of a sweep event in its `EventArena`.
+pub type EventId = usize;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum EdgeType {
@@ -22,266 +26,457 @@ pub enum ResultTransition {
     OutIn,
 }
 
+/// Event at an endpoint of a segment. `P` is the payload of the vertex at `point`, which is
+/// `()` unless the operation carries user data per vertex, see `try_boolean_with_payload`.
 #[derive(Clone, Debug)]
-struct MutablePart<F>
+pub struct SweepEvent<F, P = ()>
 where
-    F: Float,
+    F: OrderedField,
 {
     left: bool,
-    other_event: Weak<SweepEvent<F>>,
-    prev_in_result: Weak<SweepEvent<F>>,
+    other_event: Option<EventId>,
+    prev_in_result: Option<EventId>,
     edge_type: EdgeType,
+    source: EdgeSource,
+    line: Option<[Coord<F>; 2]>,
+    winding: i32,
+    winding_below: i32,
+    other_winding: i32,
+    group_winding_below: i32,
+    group_other_winding: i32,
     in_out: bool,
     other_in_out: bool,
     result_transition: ResultTransition,
     other_pos: i32,
     output_contour_id: i32,
-}
-
-#[derive(Clone, Debug)]
-pub struct SweepEvent<F>
-where
-    F: Float,
-{
-    mutable: RefCell<MutablePart<F>>,
     pub contour_id: u32,
     pub point: Coord<F>,
     pub is_subject: bool,
     pub is_exterior_ring: bool,
+    pub payload: P,
 }
 
 impl<F> SweepEvent<F>
 where
-    F: Float,
+    F: OrderedField,
 {
-    pub fn new_rc(
+    pub fn new(
         contour_id: u32,
         point: Coord<F>,
         left: bool,
-        other_event: Weak<SweepEvent<F>>,
+        other_event: Option<EventId>,
         is_subject: bool,
         is_exterior_ring: bool,
-    ) -> Rc<SweepEvent<F>> {
-        Rc::new(SweepEvent {
-            mutable: RefCell::new(MutablePart {
-                left,
-                other_event,
-                prev_in_result: Weak::new(),
-                edge_type: EdgeType::Normal,
-                in_out: false,
-                other_in_out: false,
-                result_transition: ResultTransition::None,
-                other_pos: 0,
-                output_contour_id: -1,
-            }),
+    ) -> SweepEvent<F> {
+        SweepEvent {
+            left,
+            other_event,
+            prev_in_result: None,
+            edge_type: EdgeType::Normal,
+            source: EdgeSource::default(),
+            line: None,
+            winding: 1,
+            winding_below: 0,
+            other_winding: 0,
+            group_winding_below: 0,
+            group_other_winding: 0,
+            in_out: false,
+            other_in_out: false,
+            result_transition: ResultTransition::None,
+            other_pos: 0,
+            output_contour_id: -1,
             contour_id,
             point,
             is_subject,
             is_exterior_ring,
-        })
+            payload: (),
+        }
     }
 
+    /// The event with the given vertex payload instead of `()`.
+    pub fn with_payload<P>(self, payload: P) -> SweepEvent<F, P> {
+        SweepEvent {
+            left: self.left,
+            other_event: self.other_event,
+            prev_in_result: self.prev_in_result,
+            edge_type: self.edge_type,
+            source: self.source,
+            line: self.line,
+            winding: self.winding,
+            winding_below: self.winding_below,
+            other_winding: self.other_winding,
+            group_winding_below: self.group_winding_below,
+            group_other_winding: self.group_other_winding,
+            in_out: self.in_out,
+            other_in_out: self.other_in_out,
+            result_transition: self.result_transition,
+            other_pos: self.other_pos,
+            output_contour_id: self.output_contour_id,
+            contour_id: self.contour_id,
+            point: self.point,
+            is_subject: self.is_subject,
+            is_exterior_ring: self.is_exterior_ring,
+            payload,
+        }
+    }
+}
+
+impl<F, P> SweepEvent<F, P>
+where
+    F: OrderedField,
+{
     pub fn is_left(&self) -> bool {
-        self.mutable.borrow().left
+        self.left
     }
 
-    pub fn set_left(&self, left: bool) {
-        self.mutable.borrow_mut().left = left
+    pub fn set_left(&mut self, left: bool) {
+        self.left = left
     }
 
-    pub fn get_other_event(&self) -> Option<Rc<SweepEvent<F>>> {
-        self.mutable.borrow().other_event.upgrade()
+    pub fn get_other_event(&self) -> Option<EventId> {
+        self.other_event
     }
 
-    pub fn set_other_event(&self, other_event: &Rc<SweepEvent<F>>) {
-        self.mutable.borrow_mut().other_event = Rc::downgrade(other_event);
+    pub fn set_other_event(&mut self, other_event: EventId) {
+        self.other_event = Some(other_event);
     }
 
-    pub fn get_prev_in_result(&self) -> Option<Rc<SweepEvent<F>>> {
-        self.mutable.borrow().prev_in_result.upgrade()
+    pub fn get_prev_in_result(&self) -> Option<EventId> {
+        self.prev_in_result
     }
 
-    pub fn set_prev_in_result(&self, prev_in_result: &Rc<SweepEvent<F>>) {
-        self.mutable.borrow_mut().prev_in_result = Rc::downgrade(prev_in_result);
+    pub fn set_prev_in_result(&mut self, prev_in_result: EventId) {
+        self.prev_in_result = Some(prev_in_result);
     }
 
-    pub fn unset_prev_in_result(&self) {
-        self.mutable.borrow_mut().prev_in_result = Weak::new();
+    pub fn unset_prev_in_result(&mut self) {
+        self.prev_in_result = None;
     }
 
     pub fn get_edge_type(&self) -> EdgeType {
-        self.mutable.borrow().edge_type
+        self.edge_type
     }
 
-    pub fn set_edge_type(&self, edge_type: EdgeType) {
-        self.mutable.borrow_mut().edge_type = edge_type
+    pub fn set_edge_type(&mut self, edge_type: EdgeType) {
+        self.edge_type = edge_type
     }
 
-    pub fn is_in_out(&self) -> bool {
-        self.mutable.borrow().in_out
+    /// Input edge the segment is part of.
+    pub fn get_source(&self) -> EdgeSource {
+        self.source
     }
 
-    pub fn is_other_in_out(&self) -> bool {
-        self.mutable.borrow().other_in_out
+    pub fn set_source(&mut self, source: EdgeSource) {
+        self.source = source
     }
 
-    pub fn is_in_result(&self) -> bool {
-        self.mutable.borrow().result_transition != ResultTransition::None
+    pub fn set_line(&mut self, line: [Coord<F>; 2]) {
+        self.line = Some(line)
     }
 
-    pub fn set_result_transition(&self, result_transition: ResultTransition) {
-        self.mutable.borrow_mut().result_transition = result_transition
+    /// Index of the input operand the segment belongs to. The subject of a boolean
+    /// operation is operand 0, the clipping polygons are operand 1.
+    pub fn get_operand(&self) -> usize {
+        self.source.operand
     }
 
-    pub fn get_result_transition(&self) -> ResultTransition {
-        self.mutable.borrow().result_transition
+    /// Change of the winding number of the own polygon when crossing the segment
+    /// from below to above.
+    pub fn get_winding(&self) -> i32 {
+        self.winding
     }
 
-    pub fn set_in_out(&self, in_out: bool, other_in_out: bool) {
-        let mut mutable = self.mutable.borrow_mut();
+    pub fn set_winding(&mut self, winding: i32) {
+        self.winding = winding
+    }
 
-        mutable.in_out = in_out;
-        mutable.other_in_out = other_in_out;
+    /// Winding number of the own polygon directly below the segment.
+    pub fn get_winding_below(&self) -> i32 {
+        self.winding_below
     }
 
-    pub fn get_other_pos(&self) -> i32 {
-        self.mutable.borrow().other_pos
+    /// Winding number of the other polygon at the segment.
+    pub fn get_other_winding(&self) -> i32 {
+        self.other_winding
     }
 
-    pub fn set_other_pos(&self, other_pos: i32) {
-        self.mutable.borrow_mut().other_pos = other_pos
+    pub fn set_winding_numbers(&mut self, winding_below: i32, other_winding: i32) {
+        self.winding_below = winding_below;
+        self.other_winding = other_winding;
     }
 
-    pub fn get_output_contour_id(&self) -> i32 {
-        self.mutable.borrow().output_contour_id
+    /// Winding numbers of the own and the other polygon below the group of coincident
+    /// segments this segment belongs to. Equal to the windings below the segment itself
+    /// if it does not coincide with the segment below.
+    pub fn get_group_windings(&self) -> (i32, i32) {
+        (self.group_winding_below, self.group_other_winding)
     }
 
-    pub fn set_output_contour_id(&self, output_contour_id: i32) {
-        self.mutable.borrow_mut().output_contour_id = output_contour_id
+    pub fn set_group_windings(&mut self, winding_below: i32, other_winding: i32) {
+        self.group_winding_below = winding_below;
+        self.group_other_winding = other_winding;
     }
 
-    pub fn is_below(&self, p: Coord<F>) -> bool {
-        if let Some(ref other_event) = self.get_other_event() {
-            if self.is_left() {
-                signed_area(self.point, other_event.point, p) > 0.
-            } else {
-                signed_area(other_event.point, self.point, p) > 0.
-            }
-        } else {
-            false
-        }
+    pub fn is_in_out(&self) -> bool {
+        self.in_out
     }
 
-    pub fn is_above(&self, p: Coord<F>) -> bool {
-        !self.is_below(p)
+    pub fn is_other_in_out(&self) -> bool {
+        self.other_in_out
     }
 
-    pub fn is_vertical(&self) -> bool {
-        match self.get_other_event() {
-            Some(ref other_event) => self.point.x == other_event.point.x,
-            None => false,
-        }
+    pub fn is_in_result(&self) -> bool {
+        self.result_transition != ResultTransition::None
     }
 
-    /// Helper function to avoid confusion by inverted ordering
-    pub fn is_before(&self, other: &SweepEvent<F>) -> bool {
-        self > other
+    pub fn set_result_transition(&mut self, result_transition: ResultTransition) {
+        self.result_transition = result_transition
     }
 
-    /// Helper function to avoid confusion by inverted ordering
-    pub fn is_after(&self, other: &SweepEvent<F>) -> bool {
-        self < other
+    pub fn get_result_transition(&self) -> ResultTransition {
+        self.result_transition
+    }
+
+    pub fn set_in_out(&mut self, in_out: bool, other_in_out: bool) {
+        self.in_out = in_out;
+        self.other_in_out = other_in_out;
+    }
+
+    pub fn get_other_pos(&self) -> i32 {
+        self.other_pos
+    }
+
+    pub fn set_other_pos(&mut self, other_pos: i32) {
+        self.other_pos = other_pos
+    }
+
+    pub fn get_output_contour_id(&self) -> i32 {
+        self.output_contour_id
+    }
+
+    pub fn set_output_contour_id(&mut self, output_contour_id: i32) {
+        self.output_contour_id = output_contour_id
     }
 }
 
-impl<F> PartialEq for SweepEvent<F>
+/// Storage of all sweep events of a boolean operation. Events refer to each other by their
+/// `EventId`, i.e., their index in the arena, so that the event queue, the sweep line and the
+/// result connection can work on plain indices without reference counting.
+///
+/// The events are ordered and intersected with the predicates of the kernel `K`, and carry
+/// vertex payloads of type `P`.
+#[derive(Clone, Debug)]
+pub struct EventArena<F, K = DefaultKernel, P = ()>
+where
+    F: OrderedField,
+{
+    events: Vec<SweepEvent<F, P>>,
+    kernel: PhantomData<fn() -> K>,
+}
+
+impl<F> EventArena<F>
 where
-    F: Float,
+    F: OrderedField,
 {
-    fn eq(&self, other: &Self) -> bool {
-        self.contour_id == other.contour_id
-            && self.is_left() == other.is_left()
-            && self.point == other.point
-            && self.is_subject == other.is_subject
+    /// Creates an arena using the `DefaultKernel`, see `default` for other kernels.
+    pub fn new() -> EventArena<F> {
+        EventArena::default()
     }
 }
 
-impl<F> Eq for SweepEvent<F> where F: Float {}
+impl<F, K> EventArena<F, K>
+where
+    F: OrderedField,
+{
+    /// Converts the arena into one whose events carry the payload returned by `payload` for
+    /// each event.
+    pub fn with_payloads<P>(self, mut payload: impl FnMut(EventId, &SweepEvent<F>) -> P) -> EventArena<F, K, P> {
+        EventArena {
+            events: self
+                .events
+                .into_iter()
+                .enumerate()
+                .map(|(id, event)| {
+                    let payload = payload(id, &event);
+                    event.with_payload(payload)
+                })
+                .collect(),
+            kernel: PhantomData,
+        }
+    }
+}
 
-impl<F> PartialOrd for SweepEvent<F>
+impl<F, K, P> EventArena<F, K, P>
 where
-    F: Float,
+    F: OrderedField,
 {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Moves the event into the arena and returns its id.
+    pub fn push(&mut self, event: SweepEvent<F, P>) -> EventId {
+        self.events.push(event);
+        self.events.len() - 1
+    }
+
+    /// Adds both events of a segment, linked to each other, and returns their ids.
+    pub fn push_pair(&mut self, event: SweepEvent<F, P>, other: SweepEvent<F, P>) -> (EventId, EventId) {
+        let event_id = self.push(event);
+        let other_id = self.push(other);
+        self.events[event_id].set_other_event(other_id);
+        self.events[other_id].set_other_event(event_id);
+        (event_id, other_id)
+    }
+
+    /// The event at the other end of the segment.
+    pub fn other(&self, id: EventId) -> Option<&SweepEvent<F, P>> {
+        self.events[id].other_event.map(|other| &self.events[other])
+    }
+
+    /// Endpoints of the input edge the segment is part of. Intersection points are rounded,
+    /// so a divided segment is not necessarily exactly on this line.
+    pub fn line(&self, id: EventId) -> Option<[Coord<F>; 2]> {
+        let event = &self.events[id];
+        event
+            .line
+            .or_else(|| self.other(id).map(|other| [event.point, other.point]))
+    }
+
+    /// Whether `p` lies within the bounding box of the segment.
+    pub fn is_in_bounding_box(&self, id: EventId, p: Coord<F>) -> bool {
+        let (point, Some(other)) = (self.events[id].point, self.other(id)) else {
+            return false;
+        };
+        let within = |a: F, b: F, x: F| if a < b { a <= x && x <= b } else { b <= x && x <= a };
+        within(point.x, other.point.x, p.x) && within(point.y, other.point.y, p.y)
+    }
+
+    pub fn is_vertical(&self, id: EventId) -> bool {
+        match self.other(id) {
+            Some(other_event) => self.events[id].point.x == other_event.point.x,
+            None => false,
+        }
     }
 }
 
-impl<F> Ord for SweepEvent<F>
+impl<F, K, P> EventArena<F, K, P>
 where
-    F: Float,
+    F: OrderedField,
+    K: Kernel<F>,
 {
-    #[inline]
-    fn cmp(&self, other: &Self) -> Ordering {
-        // Ord is exactly the other way round as in the js implementation as BinaryHeap sorts decending
-        let p1 = self.point;
-        let p2 = other.point;
+    pub fn is_below(&self, id: EventId, p: Coord<F>) -> bool {
+        let event = &self.events[id];
+        if let Some(other_event) = self.other(id) {
+            if event.is_left() {
+                K::orientation(event.point, other_event.point, p) == Ordering::Greater
+            } else {
+                K::orientation(other_event.point, event.point, p) == Ordering::Greater
+            }
+        } else {
+            false
+        }
+    }
 
-        if p1.x > p2.x {
-            return Ordering::Less;
+    /// Whether `p` lies on the input edge of the segment and within the bounding box of the
+    /// segment, i.e., on the segment except for the rounding of its endpoints, see `line`.
+    pub fn is_on_edge(&self, id: EventId, p: Coord<F>) -> bool {
+        match self.line(id) {
+            Some([start, end]) => self.is_in_bounding_box(id, p) && K::orientation(start, end, p) == Ordering::Equal,
+            None => false,
         }
-        if p1.x < p2.x {
-            return Ordering::Greater;
+    }
+
+    /// Whether the segments are parts of collinear input edges, even if they are not exactly
+    /// collinear because of rounded intersection points, see `line`.
+    pub fn are_on_same_line(&self, a: EventId, b: EventId) -> bool {
+        // Parts of the same edge cannot overlap, even if rounding moves their endpoints.
+        if self.events[a].source == self.events[b].source {
+            return false;
         }
-        if p1.y > p2.y {
-            return Ordering::Less;
+        match (self.line(a), self.line(b)) {
+            (Some([start, end]), Some(other_line)) => other_line
+                .iter()
+                .all(|&p| K::orientation(start, end, p) == Ordering::Equal),
+            _ => false,
         }
-        if p1.y < p2.y {
-            return Ordering::Greater;
+    }
+
+    pub fn is_above(&self, id: EventId, p: Coord<F>) -> bool {
+        !self.is_below(id, p)
+    }
+
+    /// Order of the events in the event queue. The order is inverted compared to the
+    /// processing order, i.e., the event to be processed first is the greatest.
+    #[inline]
+    pub fn cmp_events(&self, a: EventId, b: EventId) -> Ordering {
+        let (event_a, event_b) = (&self.events[a], &self.events[b]);
+        let p1 = event_a.point;
+        let p2 = event_b.point;
+
+        match K::compare_points(p1, p2) {
+            Ordering::Greater => return Ordering::Less,
+            Ordering::Less => return Ordering::Greater,
+            Ordering::Equal => {}
         }
 
-        if self.is_left() != other.is_left() {
-            return less_if(self.is_left());
+        if event_a.is_left() != event_b.is_left() {
+            return less_if(event_a.is_left());
         }
 
-        if let (Some(other1), Some(other2)) = (self.get_other_event(), other.get_other_event()) {
-            if signed_area(p1, other1.point, other2.point) != 0. {
-                return less_if(!self.is_below(other2.point));
+        if let (Some(other1), Some(other2)) = (self.other(a), self.other(b)) {
+            if K::orientation(p1, other1.point, other2.point) != Ordering::Equal {
+                return less_if(!self.is_below(a, other2.point));
             }
         }
 
-        less_if(!self.is_subject && other.is_subject)
+        less_if(!event_a.is_subject && event_b.is_subject)
+    }
+
+    /// Helper function to avoid confusion by inverted ordering
+    pub fn is_before(&self, a: EventId, b: EventId) -> bool {
+        self.cmp_events(a, b) == Ordering::Greater
+    }
+
+    /// Helper function to avoid confusion by inverted ordering
+    pub fn is_after(&self, a: EventId, b: EventId) -> bool {
+        self.cmp_events(a, b) == Ordering::Less
     }
 }
 
-#[cfg(feature = "debug-booleanop")]
-pub trait JsonDebug {
-    fn to_json_debug(&self) -> String;
-    fn to_json_debug_short(&self) -> String;
+impl<F, K, P> Default for EventArena<F, K, P>
+where
+    F: OrderedField,
+{
+    fn default() -> EventArena<F, K, P> {
+        EventArena {
+            events: Vec::new(),
+            kernel: PhantomData,
+        }
+    }
 }
 
-#[cfg(feature = "debug-booleanop")]
-impl<F> JsonDebug for Rc<SweepEvent<F>>
+impl<F, K, P> Index<EventId> for EventArena<F, K, P>
 where
-    F: Float,
+    F: OrderedField,
 {
-    fn to_json_debug(&self) -> String {
-        format!(
-            "{{\"self\": {}, \"other\": {}}}",
-            self.to_json_debug_short(),
-            self.get_other_event().unwrap().to_json_debug_short(),
-        )
-    }
-
-    fn to_json_debug_short(&self) -> String {
-        format!(
-            "{{\"addr\": \"{:p}\", \"point\": [{}, {}], \"type\": \"{}\", \"poly\": \"{}\"}}",
-            *self,
-            self.point.x,
-            self.point.y,
-            if self.is_left() { "L" } else { "R" },
-            if self.is_subject { "A" } else { "B" },
-        )
+    type Output = SweepEvent<F, P>;
+
+    fn index(&self, id: EventId) -> &SweepEvent<F, P> {
+        &self.events[id]
+    }
+}
+
+impl<F, K, P> IndexMut<EventId> for EventArena<F, K, P>
+where
+    F: OrderedField,
+{
+    fn index_mut(&mut self, id: EventId) -> &mut SweepEvent<F, P> {
+        &mut self.events[id]
     }
 }
 
@@ -291,78 +486,86 @@ mod test {
     use super::*;
 
     pub fn se_pair(
+        events: &mut EventArena<f64>,
         contour_id: u32,
         x: f64,
         y: f64,
         other_x: f64,
         other_y: f64,
         is_subject: bool,
-    ) -> (Rc<SweepEvent<f64>>, Rc<SweepEvent<f64>>) {
-        let other = SweepEvent::new_rc(
-            contour_id,
-            Coord { x: other_x, y: other_y },
-            false,
-            Weak::new(),
-            is_subject,
-            true,
-        );
-        let event = SweepEvent::new_rc(
-            contour_id,
-            Coord { x, y },
-            true,
-            Rc::downgrade(&other),
-            is_subject,
-            true,
+    ) -> (EventId, EventId) {
+        let (event, other) = events.push_pair(
+            SweepEvent::new(contour_id, Coord { x, y }, true, None, is_subject, true),
+            SweepEvent::new(
+                contour_id,
+                Coord { x: other_x, y: other_y },
+                false,
+                None,
+                is_subject,
+                true,
+            ),
         );
         // Make sure test cases fulfill the invariant of left/right relationship.
-        assert!(event.is_before(&other));
+        assert!(events.is_before(event, other));
 
         (event, other)
     }
 
+    #[test]
+    fn test_sweep_state_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<SweepEvent<f64>>();
+        assert_send_sync::<EventArena<f64>>();
+        assert_send_sync::<super::super::event_queue::EventQueue>();
+    }
+
     #[test]
     pub fn test_is_below() {
-        let other_s1 = SweepEvent::new_rc(0, xy(1, 1), false, Weak::new(), false, true);
-        let s1 = SweepEvent::new_rc(0, xy(0, 0), true, Rc::downgrade(&other_s1), false, true);
-        let s2 = SweepEvent::new_rc(0, xy(0, 0), false, Rc::downgrade(&s1), false, true);
+        let mut events = EventArena::new();
+        let other_s1 = events.push(SweepEvent::new(0, xy(1, 1), false, None, false, true));
+        let s1 = events.push(SweepEvent::new(0, xy(0, 0), true, Some(other_s1), false, true));
+        let s2 = events.push(SweepEvent::new(0, xy(0, 0), false, Some(s1), false, true));
 
-        assert!(s1.is_below(xy(0, 1)));
-        assert!(s1.is_below(xy(1, 2)));
-        assert!(!s1.is_below(xy(0, 0)));
-        assert!(!s1.is_below(xy(5, -1)));
+        assert!(events.is_below(s1, xy(0, 1)));
+        assert!(events.is_below(s1, xy(1, 2)));
+        assert!(!events.is_below(s1, xy(0, 0)));
+        assert!(!events.is_below(s1, xy(5, -1)));
 
-        assert!(!s2.is_below(xy(0, 1)));
-        assert!(!s2.is_below(xy(1, 2)));
-        assert!(!s2.is_below(xy(0, 0)));
-        assert!(!s2.is_below(xy(5, -1)));
+        assert!(!events.is_below(s2, xy(0, 1)));
+        assert!(!events.is_below(s2, xy(1, 2)));
+        assert!(!events.is_below(s2, xy(0, 0)));
+        assert!(!events.is_below(s2, xy(5, -1)));
     }
 
     #[test]
     pub fn test_is_above() {
-        let other_s1 = SweepEvent::new_rc(0, xy(1, 1), false, Weak::new(), false, true);
-        let s1 = SweepEvent::new_rc(0, xy(0, 0), true, Rc::downgrade(&other_s1), false, true);
-        let s2 = SweepEvent::new_rc(0, xy(0, 1), false, Rc::downgrade(&s1), false, true);
+        let mut events = EventArena::new();
+        let other_s1 = events.push(SweepEvent::new(0, xy(1, 1), false, None, false, true));
+        let s1 = events.push(SweepEvent::new(0, xy(0, 0), true, Some(other_s1), false, true));
+        let s2 = events.push(SweepEvent::new(0, xy(0, 1), false, Some(s1), false, true));
 
-        assert!(!s1.is_above(xy(0, 1)));
-        assert!(!s1.is_above(xy(1, 2)));
-        assert!(s1.is_above(xy(0, 0)));
-        assert!(s1.is_above(xy(5, -1)));
+        assert!(!events.is_above(s1, xy(0, 1)));
+        assert!(!events.is_above(s1, xy(1, 2)));
+        assert!(events.is_above(s1, xy(0, 0)));
+        assert!(events.is_above(s1, xy(5, -1)));
 
-        assert!(s2.is_above(xy(0, 1)));
-        assert!(s2.is_above(xy(1, 2)));
-        assert!(s2.is_above(xy(0, 0)));
-        assert!(s2.is_above(xy(5, -1)));
+        assert!(events.is_above(s2, xy(0, 1)));
+        assert!(events.is_above(s2, xy(1, 2)));
+        assert!(events.is_above(s2, xy(0, 0)));
+        assert!(events.is_above(s2, xy(5, -1)));
     }
 
     #[test]
     pub fn test_is_vertical() {
-        let other_s1 = SweepEvent::new_rc(0, xy(0, 1), false, Weak::new(), false, true);
-        let s1 = SweepEvent::new_rc(0, xy(0, 0), true, Rc::downgrade(&other_s1), false, true);
-        let other_s2 = SweepEvent::new_rc(0, xy(0.0001, 1), false, Weak::new(), false, true);
-        let s2 = SweepEvent::new_rc(0, xy(0, 0), true, Rc::downgrade(&other_s2), false, true);
+        let mut events = EventArena::new();
+        let other_s1 = events.push(SweepEvent::new(0, xy(0, 1), false, None, false, true));
+        let s1 = events.push(SweepEvent::new(0, xy(0, 0), true, Some(other_s1), false, true));
+        let other_s2 = events.push(SweepEvent::new(0, xy(0.0001, 1), false, None, false, true));
+        let s2 = events.push(SweepEvent::new(0, xy(0, 0), true, Some(other_s2), false, true));
 
-        assert!(s1.is_vertical());
-        assert!(!s2.is_vertical());
+        assert!(events.is_vertical(s1));
+        assert!(!events.is_vertical(s2));
     }
 
     #[rustfmt::skip]
@@ -375,28 +578,29 @@ mod test {
         // - L events in counter-clockwise order
         let id = 0;
         let z = 0.;
+        let e = &mut EventArena::new();
 
         // Group 'a' which have their right event at (0, 0), clockwise
-        let (_av_l, av_r) = se_pair(id,  0., -1., z, z, true);   // vertical comes first
-        let (_a1_l, a1_r) = se_pair(id, -2., -6., z, z, true);
-        let (_a2_l, a2_r) = se_pair(id, -1., -2., z, z, true);
-        let (_a3_l, a3_r) = se_pair(id, -1., -1., z, z, true);
-        let (_a4_l, a4_r) = se_pair(id, -2., -1., z, z, true);
-        let (_a5_l, a5_r) = se_pair(id, -2.,  1., z, z, true);
-        let (_a6_l, a6_r) = se_pair(id, -1.,  1., z, z, true);
-        let (_a7_l, a7_r) = se_pair(id, -1.,  2., z, z, true);
-        let (_a8_l, a8_r) = se_pair(id, -2.,  6., z, z, true);
+        let (_av_l, av_r) = se_pair(e, id,  0., -1., z, z, true);   // vertical comes first
+        let (_a1_l, a1_r) = se_pair(e, id, -2., -6., z, z, true);
+        let (_a2_l, a2_r) = se_pair(e, id, -1., -2., z, z, true);
+        let (_a3_l, a3_r) = se_pair(e, id, -1., -1., z, z, true);
+        let (_a4_l, a4_r) = se_pair(e, id, -2., -1., z, z, true);
+        let (_a5_l, a5_r) = se_pair(e, id, -2.,  1., z, z, true);
+        let (_a6_l, a6_r) = se_pair(e, id, -1.,  1., z, z, true);
+        let (_a7_l, a7_r) = se_pair(e, id, -1.,  2., z, z, true);
+        let (_a8_l, a8_r) = se_pair(e, id, -2.,  6., z, z, true);
 
         // Group 'b' which have their left event at (0, 0), counter clockwise
-        let (b1_l, _b1_r) = se_pair(id, z, z, 2., -6., true);
-        let (b2_l, _b2_r) = se_pair(id, z, z, 1., -2., true);
-        let (b3_l, _b3_r) = se_pair(id, z, z, 1., -1., true);
-        let (b4_l, _b4_r) = se_pair(id, z, z, 2., -1., true);
-        let (b5_l, _b5_r) = se_pair(id, z, z, 2.,  1., true);
-        let (b6_l, _b6_r) = se_pair(id, z, z, 1.,  1., true);
-        let (b7_l, _b7_r) = se_pair(id, z, z, 1.,  2., true);
-        let (b8_l, _b8_r) = se_pair(id, z, z, 2.,  6., true);
-        let (bv_l, _bv_r) = se_pair(id, z, z, 0.,  1., true);    // vertical comes last
+        let (b1_l, _b1_r) = se_pair(e, id, z, z, 2., -6., true);
+        let (b2_l, _b2_r) = se_pair(e, id, z, z, 1., -2., true);
+        let (b3_l, _b3_r) = se_pair(e, id, z, z, 1., -1., true);
+        let (b4_l, _b4_r) = se_pair(e, id, z, z, 2., -1., true);
+        let (b5_l, _b5_r) = se_pair(e, id, z, z, 2.,  1., true);
+        let (b6_l, _b6_r) = se_pair(e, id, z, z, 1.,  1., true);
+        let (b7_l, _b7_r) = se_pair(e, id, z, z, 1.,  2., true);
+        let (b8_l, _b8_r) = se_pair(e, id, z, z, 2.,  6., true);
+        let (bv_l, _bv_r) = se_pair(e, id, z, z, 0.,  1., true);    // vertical comes last
 
         let events_expected_order = [
             av_r, a1_r, a2_r, a3_r, a4_r, a5_r, a6_r, a7_r, a8_r,
@@ -405,7 +609,7 @@ mod test {
 
         for i in 0 .. events_expected_order.len() - 1 {
             for j in i + 1 .. events_expected_order.len() {
-                assert!(events_expected_order[i].is_before(&events_expected_order[j]));
+                assert!(e.is_before(events_expected_order[i], events_expected_order[j]));
             }
         }
 
@@ -0,0 +1,256 @@
+use super::SweepLineStatus;
+use crate::boolean::sweep_event::EventId;
+use std::cmp::Ordering;
+
+/// Minimum degree of the tree: every node except the root holds between `MIN_DEGREE - 1`
+/// and `2 * MIN_DEGREE - 1` events, like the nodes of `std::collections::BTreeSet`.
+const MIN_DEGREE: usize = 6;
+const MAX_EVENTS: usize = 2 * MIN_DEGREE - 1;
+
+/// Sweep line backed by a B-tree.
+///
+/// Storing several events per node keeps the tree shallow and the events of a node close
+/// together in memory. In contrast to the splay tree, lookups don't modify the tree.
+#[derive(Default)]
+pub struct BTreeSweepLine {
+    root: Node,
+}
+
+#[derive(Default)]
+struct Node {
+    events: Vec<EventId>,
+    /// Empty for leaves, otherwise one more than the number of events.
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    fn search<C>(&self, event: EventId, comparator: &C) -> Result<usize, usize>
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        self.events.binary_search_by(|e| comparator(e, &event))
+    }
+
+    fn min(&self) -> EventId {
+        let mut node = self;
+        while !node.is_leaf() {
+            node = &node.children[0];
+        }
+        node.events[0]
+    }
+
+    fn max(&self) -> EventId {
+        let mut node = self;
+        while !node.is_leaf() {
+            node = &node.children[node.children.len() - 1];
+        }
+        node.events[node.events.len() - 1]
+    }
+
+    /// Splits the full child `i` into two, moving its median event into this node.
+    fn split_child(&mut self, i: usize) {
+        let child = &mut self.children[i];
+        let events = child.events.split_off(MIN_DEGREE);
+        let median = child.events.pop().unwrap();
+        let children = if child.is_leaf() {
+            Vec::new()
+        } else {
+            child.children.split_off(MIN_DEGREE)
+        };
+        self.events.insert(i, median);
+        self.children.insert(i + 1, Node { events, children });
+    }
+
+    /// Inserts into the subtree of a node which is not full. Full children are split
+    /// on the way down, so the insertion never has to propagate upwards.
+    fn insert_non_full<C>(&mut self, event: EventId, comparator: &C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let mut i = match self.search(event, comparator) {
+            Ok(_) => return false,
+            Err(i) => i,
+        };
+        if self.is_leaf() {
+            self.events.insert(i, event);
+            return true;
+        }
+        if self.children[i].events.len() == MAX_EVENTS {
+            self.split_child(i);
+            match comparator(&event, &self.events[i]) {
+                Ordering::Less => {}
+                Ordering::Equal => return false,
+                Ordering::Greater => i += 1,
+            }
+        }
+        self.children[i].insert_non_full(event, comparator)
+    }
+
+    /// Merges child `i + 1` and the event separating it from child `i` into child `i`.
+    fn merge_children(&mut self, i: usize) {
+        let right = self.children.remove(i + 1);
+        let median = self.events.remove(i);
+        let left = &mut self.children[i];
+        left.events.push(median);
+        left.events.extend(right.events);
+        left.children.extend(right.children);
+    }
+
+    /// Makes sure that child `i` holds at least `MIN_DEGREE` events, so that removing from
+    /// its subtree never has to propagate upwards. Returns the index of the child which
+    /// covers the events of child `i` afterwards.
+    fn fill_child(&mut self, i: usize) -> usize {
+        if self.children[i].events.len() >= MIN_DEGREE {
+            return i;
+        }
+        if i > 0 && self.children[i - 1].events.len() >= MIN_DEGREE {
+            // Rotate an event from the left sibling through this node.
+            let (left, right) = self.children.split_at_mut(i);
+            let (left, child) = (&mut left[i - 1], &mut right[0]);
+            child.events.insert(0, self.events[i - 1]);
+            self.events[i - 1] = left.events.pop().unwrap();
+            if let Some(grandchild) = left.children.pop() {
+                child.children.insert(0, grandchild);
+            }
+            i
+        } else if i + 1 < self.children.len() && self.children[i + 1].events.len() >= MIN_DEGREE {
+            // Rotate an event from the right sibling through this node.
+            let (left, right) = self.children.split_at_mut(i + 1);
+            let (child, right) = (&mut left[i], &mut right[0]);
+            child.events.push(self.events[i]);
+            self.events[i] = right.events.remove(0);
+            if !right.is_leaf() {
+                child.children.push(right.children.remove(0));
+            }
+            i
+        } else if i + 1 < self.children.len() {
+            self.merge_children(i);
+            i
+        } else {
+            self.merge_children(i - 1);
+            i - 1
+        }
+    }
+
+    fn remove<C>(&mut self, event: EventId, comparator: &C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        match self.search(event, comparator) {
+            Ok(i) if self.is_leaf() => {
+                self.events.remove(i);
+                true
+            }
+            Ok(i) => {
+                // Replace the event by its predecessor or successor, if the corresponding
+                // child can spare an event, otherwise merge both children around it.
+                if self.children[i].events.len() >= MIN_DEGREE {
+                    let predecessor = self.children[i].max();
+                    self.events[i] = predecessor;
+                    self.children[i].remove(predecessor, comparator)
+                } else if self.children[i + 1].events.len() >= MIN_DEGREE {
+                    let successor = self.children[i + 1].min();
+                    self.events[i] = successor;
+                    self.children[i + 1].remove(successor, comparator)
+                } else {
+                    self.merge_children(i);
+                    self.children[i].remove(event, comparator)
+                }
+            }
+            Err(_) if self.is_leaf() => false,
+            Err(i) => {
+                let i = self.fill_child(i);
+                self.children[i].remove(event, comparator)
+            }
+        }
+    }
+}
+
+impl SweepLineStatus for BTreeSweepLine {
+    fn insert<C>(&mut self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        if self.root.events.len() == MAX_EVENTS {
+            let old_root = std::mem::take(&mut self.root);
+            self.root.children.push(old_root);
+            self.root.split_child(0);
+        }
+        self.root.insert_non_full(event, &comparator)
+    }
+
+    fn remove<C>(&mut self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let removed = self.root.remove(event, &comparator);
+        if self.root.events.is_empty() && !self.root.is_leaf() {
+            self.root = self.root.children.pop().unwrap();
+        }
+        removed
+    }
+
+    fn contains<C>(&self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let mut node = &self.root;
+        loop {
+            match node.search(event, &comparator) {
+                Ok(_) => return true,
+                Err(_) if node.is_leaf() => return false,
+                Err(i) => node = &node.children[i],
+            }
+        }
+    }
+
+    fn prev<C>(&self, event: EventId, comparator: C) -> Option<EventId>
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let mut node = &self.root;
+        let mut candidate = None;
+        loop {
+            match node.search(event, &comparator) {
+                Ok(i) if node.is_leaf() => return if i > 0 { Some(node.events[i - 1]) } else { candidate },
+                Ok(i) => return Some(node.children[i].max()),
+                Err(i) => {
+                    if i > 0 {
+                        candidate = Some(node.events[i - 1]);
+                    }
+                    if node.is_leaf() {
+                        return candidate;
+                    }
+                    node = &node.children[i];
+                }
+            }
+        }
+    }
+
+    fn next<C>(&self, event: EventId, comparator: C) -> Option<EventId>
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let mut node = &self.root;
+        let mut candidate = None;
+        loop {
+            match node.search(event, &comparator) {
+                Ok(i) if node.is_leaf() => return node.events.get(i + 1).copied().or(candidate),
+                Ok(i) => return Some(node.children[i + 1].min()),
+                Err(i) => {
+                    if i < node.events.len() {
+                        candidate = Some(node.events[i]);
+                    }
+                    if node.is_leaf() {
+                        return candidate;
+                    }
+                    node = &node.children[i];
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Data structures holding the segments which intersect the sweep line.
+//!
+//! The segments are ordered by `compare_segments`, which depends on the event arena. Since
+//! the arena is modified during the sweep, the structures have no comparator of their own,
+//! and the comparator is passed to every operation instead.
+
+mod btree;
+mod skip_list;
+
+pub use self::btree::BTreeSweepLine;
+pub use self::skip_list::SkipListSweepLine;
+
+use super::sweep_event::EventId;
+use crate::splay::SplaySet;
+use std::cmp::Ordering;
+
+/// Ordered set of the left events of the segments intersecting the sweep line.
+///
+/// `comparator` defines a total order of the events, which must not change for events
+/// contained in the set. Operations on an event look it up by the comparator, so events
+/// which compare as equal are considered identical.
+pub trait SweepLineStatus: Default {
+    /// Inserts the event, returning false if it is already contained.
+    fn insert<C>(&mut self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering;
+
+    /// Removes the event, returning false if it is not contained.
+    fn remove<C>(&mut self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering;
+
+    fn contains<C>(&self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering;
+
+    /// The greatest event less than `event`, i.e., the segment below.
+    fn prev<C>(&self, event: EventId, comparator: C) -> Option<EventId>
+    where
+        C: Fn(&EventId, &EventId) -> Ordering;
+
+    /// The least event greater than `event`, i.e., the segment above.
+    fn next<C>(&self, event: EventId, comparator: C) -> Option<EventId>
+    where
+        C: Fn(&EventId, &EventId) -> Ordering;
+}
+
+/// Sweep line backed by the crate's `SplaySet`, which is the default.
+pub struct SplaySweepLine {
+    set: SplaySet<EventId, fn(&EventId, &EventId) -> Ordering>,
+}
+
+impl Default for SplaySweepLine {
+    fn default() -> SplaySweepLine {
+        SplaySweepLine {
+            set: SplaySet::with_external_order(),
+        }
+    }
+}
+
+impl SplaySweepLine {
+    /// Iterates over the events from bottom to top. In contrast to the other operations,
+    /// iterating over all events does not need the comparator.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = EventId> + '_ {
+        self.set.iter().copied()
+    }
+}
+
+impl SweepLineStatus for SplaySweepLine {
+    fn insert<C>(&mut self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        self.set.insert_by(event, comparator)
+    }
+
+    fn remove<C>(&mut self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        self.set.remove_by(&event, comparator)
+    }
+
+    fn contains<C>(&self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        self.set.contains_by(&event, comparator)
+    }
+
+    fn prev<C>(&self, event: EventId, comparator: C) -> Option<EventId>
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        self.set.prev_by(&event, comparator).copied()
+    }
+
+    fn next<C>(&self, event: EventId, comparator: C) -> Option<EventId>
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        self.set.next_by(&event, comparator).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::BTreeSet;
+
+    /// Orders the ids in reverse, to make sure the implementations don't rely on the order
+    /// of the ids themselves.
+    fn comparator(a: &EventId, b: &EventId) -> Ordering {
+        b.cmp(a)
+    }
+
+    fn check_against_btree_set<S: SweepLineStatus>() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut sweep_line = S::default();
+        let mut expected = BTreeSet::new();
+
+        for _ in 0..10000 {
+            let event: EventId = rng.gen_range(0..500);
+            if rng.gen_bool(0.55) {
+                assert_eq!(sweep_line.insert(event, comparator), expected.insert(event));
+            } else {
+                assert_eq!(sweep_line.remove(event, comparator), expected.remove(&event));
+            }
+
+            let probe: EventId = rng.gen_range(0..500);
+            assert_eq!(sweep_line.contains(probe, comparator), expected.contains(&probe));
+            // The comparator is reversed, so the previous event has the next greater id.
+            assert_eq!(
+                sweep_line.prev(probe, comparator),
+                expected.range(probe + 1..).next().copied()
+            );
+            assert_eq!(
+                sweep_line.next(probe, comparator),
+                expected.range(..probe).next_back().copied()
+            );
+        }
+
+        for event in 0..500 {
+            assert_eq!(sweep_line.remove(event, comparator), expected.remove(&event));
+        }
+        assert_eq!(sweep_line.next(250, comparator), None);
+        assert_eq!(sweep_line.prev(250, comparator), None);
+    }
+
+    #[test]
+    fn test_splay_sweep_line() {
+        check_against_btree_set::<SplaySweepLine>();
+    }
+
+    #[test]
+    fn test_btree_sweep_line() {
+        check_against_btree_set::<BTreeSweepLine>();
+    }
+
+    #[test]
+    fn test_skip_list_sweep_line() {
+        check_against_btree_set::<SkipListSweepLine>();
+    }
+}
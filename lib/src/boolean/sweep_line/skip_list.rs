@@ -0,0 +1,170 @@
+use super::SweepLineStatus;
+use crate::boolean::sweep_event::EventId;
+use std::cmp::Ordering;
+
+/// Maximum number of levels, sufficient for about 4^16 segments in the sweep line.
+const MAX_LEVEL: usize = 16;
+
+/// Index of the head node, which precedes all events.
+const HEAD: usize = 0;
+const NIL: usize = usize::MAX;
+
+/// Sweep line backed by a skip list.
+///
+/// The nodes are stored in a vector and linked by their indices. Node levels are drawn from
+/// a geometric distribution with p = 1/4 using a fixed seed, so the structure of the list,
+/// and therefore the number of comparisons, is reproducible.
+pub struct SkipListSweepLine {
+    nodes: Vec<SkipNode>,
+    /// Indices of removed nodes, which are reused by later insertions.
+    free: Vec<usize>,
+    level: usize,
+    rng_state: u64,
+}
+
+struct SkipNode {
+    event: EventId,
+    /// Index of the next node on each level of this node.
+    next: Vec<usize>,
+}
+
+impl Default for SkipListSweepLine {
+    fn default() -> SkipListSweepLine {
+        SkipListSweepLine {
+            nodes: vec![SkipNode {
+                event: 0,
+                next: vec![NIL; MAX_LEVEL],
+            }],
+            free: Vec::new(),
+            level: 1,
+            rng_state: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+}
+
+impl SkipListSweepLine {
+    fn random_level(&mut self) -> usize {
+        // xorshift64
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 7;
+        self.rng_state ^= self.rng_state << 17;
+        (self.rng_state.trailing_zeros() as usize / 2 + 1).min(MAX_LEVEL)
+    }
+
+    /// Finds the last node less than `event` on each level, and the node following it on
+    /// the lowest level, which is the node of `event` if it is contained.
+    fn find<C>(&self, event: EventId, comparator: &C) -> ([usize; MAX_LEVEL], usize)
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let mut predecessors = [HEAD; MAX_LEVEL];
+        let mut node = HEAD;
+        for level in (0..self.level).rev() {
+            loop {
+                let next = self.nodes[node].next[level];
+                if next == NIL || comparator(&self.nodes[next].event, &event) != Ordering::Less {
+                    break;
+                }
+                node = next;
+            }
+            predecessors[level] = node;
+        }
+        (predecessors, self.nodes[node].next[0])
+    }
+
+    fn is_event<C>(&self, node: usize, event: EventId, comparator: &C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        node != NIL && comparator(&self.nodes[node].event, &event) == Ordering::Equal
+    }
+}
+
+impl SweepLineStatus for SkipListSweepLine {
+    fn insert<C>(&mut self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let (predecessors, found) = self.find(event, &comparator);
+        if self.is_event(found, event, &comparator) {
+            return false;
+        }
+
+        // Levels above the current level of the list are preceded by the head node, which
+        // `find` already stored as their predecessor.
+        let level = self.random_level();
+        self.level = self.level.max(level);
+
+        let next = predecessors[..level]
+            .iter()
+            .enumerate()
+            .map(|(l, &p)| self.nodes[p].next[l])
+            .collect();
+        let node = match self.free.pop() {
+            Some(node) => {
+                self.nodes[node] = SkipNode { event, next };
+                node
+            }
+            None => {
+                self.nodes.push(SkipNode { event, next });
+                self.nodes.len() - 1
+            }
+        };
+        for (l, &p) in predecessors[..level].iter().enumerate() {
+            self.nodes[p].next[l] = node;
+        }
+        true
+    }
+
+    fn remove<C>(&mut self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let (predecessors, found) = self.find(event, &comparator);
+        if !self.is_event(found, event, &comparator) {
+            return false;
+        }
+
+        for (l, &p) in predecessors[..self.nodes[found].next.len()].iter().enumerate() {
+            self.nodes[p].next[l] = self.nodes[found].next[l];
+        }
+        self.free.push(found);
+        while self.level > 1 && self.nodes[HEAD].next[self.level - 1] == NIL {
+            self.level -= 1;
+        }
+        true
+    }
+
+    fn contains<C>(&self, event: EventId, comparator: C) -> bool
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let (_, found) = self.find(event, &comparator);
+        self.is_event(found, event, &comparator)
+    }
+
+    fn prev<C>(&self, event: EventId, comparator: C) -> Option<EventId>
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let (predecessors, _) = self.find(event, &comparator);
+        Some(predecessors[0])
+            .filter(|&node| node != HEAD)
+            .map(|node| self.nodes[node].event)
+    }
+
+    fn next<C>(&self, event: EventId, comparator: C) -> Option<EventId>
+    where
+        C: Fn(&EventId, &EventId) -> Ordering,
+    {
+        let (_, found) = self.find(event, &comparator);
+        let next = if self.is_event(found, event, &comparator) {
+            self.nodes[found].next[0]
+        } else {
+            found
+        };
+        Some(next)
+            .filter(|&node| node != NIL)
+            .map(|node| self.nodes[node].event)
+    }
+}
@@ -0,0 +1,136 @@
+use super::helper::Float;
+use super::make_valid::self_overlay;
+use super::{BooleanOpError, FillRule};
+use geo_types::{LineString, MultiPolygon, Polygon};
+use std::borrow::Borrow;
+
+/// Computes the union of an arbitrary number of polygons in a single sweep.
+///
+/// In contrast to folding `BooleanOp::union` over the polygons, all rings are processed
+/// at once. Overlapping polygons are merged based on how many polygons cover a region,
+/// independent of the orientation of their rings.
+///
+/// Panics if the operation fails, see `try_unary_union` for details.
+pub fn unary_union<F>(polygons: &[Polygon<F>]) -> MultiPolygon<F>
+where
+    F: Float,
+{
+    unary_union_iter(polygons)
+}
+
+/// Variant of `unary_union` accepting any iterator over (references to) polygons.
+pub fn unary_union_iter<F, I>(polygons: I) -> MultiPolygon<F>
+where
+    F: Float,
+    I: IntoIterator,
+    I::Item: Borrow<Polygon<F>>,
+{
+    match try_unary_union_iter(polygons) {
+        Ok(result) => result,
+        Err(err) => panic!("Unary union failed: {}", err),
+    }
+}
+
+/// Fallible variant of `unary_union`.
+pub fn try_unary_union<F>(polygons: &[Polygon<F>]) -> Result<MultiPolygon<F>, BooleanOpError>
+where
+    F: Float,
+{
+    try_unary_union_iter(polygons)
+}
+
+/// Fallible variant of `unary_union_iter`.
+pub fn try_unary_union_iter<F, I>(polygons: I) -> Result<MultiPolygon<F>, BooleanOpError>
+where
+    F: Float,
+    I: IntoIterator,
+    I::Item: Borrow<Polygon<F>>,
+{
+    // Normalizing the orientation (exterior rings counter-clockwise, holes clockwise) turns
+    // the winding number of any region into the number of polygons covering it.
+    let polygons: Vec<Polygon<F>> = polygons
+        .into_iter()
+        .map(|p| normalize_orientation(p.borrow()))
+        .collect();
+
+    self_overlay(&polygons, FillRule::NonZero)
+}
+
+fn normalize_orientation<F>(polygon: &Polygon<F>) -> Polygon<F>
+where
+    F: Float,
+{
+    Polygon::new(
+        oriented_ring(polygon.exterior(), true),
+        polygon
+            .interiors()
+            .iter()
+            .map(|ring| oriented_ring(ring, false))
+            .collect(),
+    )
+}
+
+fn oriented_ring<F>(ring: &LineString<F>, counter_clockwise: bool) -> LineString<F>
+where
+    F: Float,
+{
+    let origin = match ring.0.first() {
+        Some(origin) => *origin,
+        None => return ring.clone(),
+    };
+    // Shoelace formula relative to the first point for better numerical accuracy.
+    let twice_area = ring.lines().fold(F::zero(), |area, line| {
+        let (start, end) = (line.start - origin, line.end - origin);
+        area + start.x * end.y - end.x * start.y
+    });
+    if twice_area == F::zero() || (twice_area > F::zero()) == counter_clockwise {
+        ring.clone()
+    } else {
+        LineString(ring.0.iter().rev().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo_types::polygon;
+
+    #[test]
+    fn test_oriented_ring() {
+        let ccw = LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 0.)]);
+        let cw = LineString::from(vec![(0., 0.), (1., 1.), (1., 0.), (0., 0.)]);
+
+        assert_eq!(oriented_ring(&ccw, true), ccw);
+        assert_eq!(oriented_ring(&cw, true), ccw);
+        assert_eq!(oriented_ring(&ccw, false), cw);
+        assert_eq!(oriented_ring(&cw, false), cw);
+    }
+
+    #[test]
+    fn test_unary_union_merges_overlapping_and_adjacent() {
+        let a = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)];
+        // Overlaps `a` and has clockwise orientation.
+        let b = polygon![(x: 1., y: 1.), (x: 1., y: 3.), (x: 3., y: 3.), (x: 3., y: 1.), (x: 1., y: 1.)];
+        // Shares an edge with `a`.
+        let c = polygon![(x: 0., y: -1.), (x: 2., y: -1.), (x: 2., y: 0.), (x: 0., y: 0.), (x: 0., y: -1.)];
+
+        let result = unary_union(&[a, b, c]);
+
+        assert_eq!(
+            result,
+            MultiPolygon(vec![polygon![
+                (x: 0., y: -1.),
+                (x: 2., y: -1.),
+                (x: 2., y: 0.),
+                (x: 2., y: 1.),
+                (x: 3., y: 1.),
+                (x: 3., y: 3.),
+                (x: 1., y: 3.),
+                (x: 1., y: 2.),
+                (x: 0., y: 2.),
+                (x: 0., y: 0.),
+                (x: 0., y: -1.),
+            ]])
+        );
+    }
+}
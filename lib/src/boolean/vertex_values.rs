@@ -0,0 +1,288 @@
+use super::error::BooleanOpError;
+use super::helper::Float;
+use super::kernel::DefaultKernel;
+use super::provenance::EdgeSource;
+use super::sweep_line::SplaySweepLine;
+use super::{traced_boolean_operation, FillRules, Operation};
+use geo_types::{Coord, CoordNum, LineString, MultiPolygon};
+use std::cmp::Ordering;
+
+/// Value attached to the vertices of a polygon, like a Z or M coordinate, which can be
+/// interpolated linearly along an edge.
+pub trait Interpolate<F>: Clone {
+    /// The value at the fraction `t` of the way from `self` to `other`, where `t` is in `[0, 1]`.
+    fn interpolate(&self, other: &Self, t: F) -> Self;
+}
+
+impl<F> Interpolate<F> for F
+where
+    F: Float,
+{
+    fn interpolate(&self, other: &F, t: F) -> F {
+        *self + (*other - *self) * t
+    }
+}
+
+/// Several values per vertex, e.g. `[z, m]`, which are interpolated independently.
+impl<F, Z, const N: usize> Interpolate<F> for [Z; N]
+where
+    F: Float,
+    Z: Interpolate<F>,
+{
+    fn interpolate(&self, other: &[Z; N], t: F) -> [Z; N] {
+        std::array::from_fn(|i| self[i].interpolate(&other[i], t))
+    }
+}
+
+/// Polygons along with a value per vertex, e.g. the Z coordinate.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ValuedMultiPolygon<F, Z>
+where
+    F: CoordNum,
+{
+    pub multi_polygon: MultiPolygon<F>,
+    /// `values[p][r][i]` is the value of the point `i` of the ring `r` of the polygon `p`,
+    /// with rings numbered like in `EdgeSource`. Like the points, the values of a ring are
+    /// closed, i.e., the last value belongs to the repeated first point.
+    pub values: Vec<Vec<Vec<Z>>>,
+}
+
+impl<F, Z> ValuedMultiPolygon<F, Z>
+where
+    F: CoordNum,
+{
+    pub(crate) fn is_consistent(&self) -> bool {
+        self.values.len() == self.multi_polygon.0.len()
+            && self.multi_polygon.0.iter().zip(&self.values).all(|(polygon, values)| {
+                values.len() == polygon.interiors().len() + 1
+                    && std::iter::once(polygon.exterior())
+                        .chain(polygon.interiors())
+                        .zip(values)
+                        .all(|(ring, ring_values)| ring.0.len() == ring_values.len())
+            })
+    }
+
+    fn edge(&self, source: &EdgeSource) -> (Coord<F>, Coord<F>, &Z, &Z) {
+        let polygon = &self.multi_polygon.0[source.polygon];
+        let ring = match source.ring {
+            0 => polygon.exterior(),
+            r => &polygon.interiors()[r - 1],
+        };
+        let values = &self.values[source.polygon][source.ring];
+        (
+            ring.0[source.edge],
+            ring.0[source.edge + 1],
+            &values[source.edge],
+            &values[source.edge + 1],
+        )
+    }
+
+    /// Value of the endpoint `point` of the input edge `source`.
+    pub(crate) fn vertex_value(&self, source: EdgeSource, point: Coord<F>) -> Z
+    where
+        Z: Clone,
+    {
+        let (start, _, start_value, end_value) = self.edge(&source);
+        if point == start {
+            start_value.clone()
+        } else {
+            end_value.clone()
+        }
+    }
+}
+
+/// Computes a boolean operation like `BooleanOp::try_boolean_with_fill_rules`, preserving the
+/// values attached to the vertices of the operands.
+///
+/// Output vertices which are input vertices keep their value. Vertices created at the
+/// intersection of two edges get the value interpolated linearly along the input edge the
+/// following output edge originates from, see `TracedMultiPolygon`. Where the operands have
+/// coinciding vertices or edges with different values, the value of either is used.
+pub fn try_boolean_with_values<F, Z>(
+    subject: &ValuedMultiPolygon<F, Z>,
+    clipping: &ValuedMultiPolygon<F, Z>,
+    operation: Operation,
+    fill_rules: FillRules,
+) -> Result<ValuedMultiPolygon<F, Z>, BooleanOpError>
+where
+    F: Float,
+    Z: Interpolate<F>,
+{
+    if !subject.is_consistent() || !clipping.is_consistent() {
+        return Err(BooleanOpError::MismatchedVertexValues);
+    }
+
+    let traced = traced_boolean_operation::<F, SplaySweepLine, DefaultKernel>(
+        &subject.multi_polygon.0,
+        &clipping.multi_polygon.0,
+        operation,
+        fill_rules,
+    )?;
+
+    let operands = [subject, clipping];
+    let input_vertices = sorted_vertices(&operands);
+    let values = traced
+        .multi_polygon
+        .0
+        .iter()
+        .zip(&traced.sources)
+        .map(|(polygon, sources)| {
+            std::iter::once(polygon.exterior())
+                .chain(polygon.interiors())
+                .zip(sources)
+                .map(|(ring, ring_sources)| ring_values(&operands, &input_vertices, ring, ring_sources))
+                .collect()
+        })
+        .collect();
+
+    Ok(ValuedMultiPolygon {
+        multi_polygon: traced.multi_polygon,
+        values,
+    })
+}
+
+fn compare_coords<F>(a: &Coord<F>, b: &Coord<F>) -> Ordering
+where
+    F: Float,
+{
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+/// All input vertices with their values, sorted by their coordinates.
+fn sorted_vertices<'a, F, Z>(operands: &[&'a ValuedMultiPolygon<F, Z>; 2]) -> Vec<(Coord<F>, &'a Z)>
+where
+    F: Float,
+{
+    let mut vertices: Vec<_> = operands
+        .iter()
+        .flat_map(|operand| operand.multi_polygon.0.iter().zip(&operand.values))
+        .flat_map(|(polygon, values)| {
+            std::iter::once(polygon.exterior())
+                .chain(polygon.interiors())
+                .zip(values)
+                .flat_map(|(ring, ring_values)| ring.0.iter().copied().zip(ring_values))
+        })
+        .collect();
+    vertices.sort_by(|a, b| compare_coords(&a.0, &b.0));
+    vertices
+}
+
+fn ring_values<F, Z>(
+    operands: &[&ValuedMultiPolygon<F, Z>; 2],
+    input_vertices: &[(Coord<F>, &Z)],
+    ring: &LineString<F>,
+    sources: &[EdgeSource],
+) -> Vec<Z>
+where
+    F: Float,
+    Z: Interpolate<F>,
+{
+    let mut values: Vec<Z> = sources
+        .iter()
+        .enumerate()
+        .map(|(i, outgoing)| {
+            let incoming = &sources[(i + sources.len() - 1) % sources.len()];
+            vertex_value(operands, input_vertices, ring.0[i], incoming, outgoing)
+        })
+        .collect();
+    if let Some(first) = values.first().cloned() {
+        values.push(first);
+    }
+    values
+}
+
+fn vertex_value<F, Z>(
+    operands: &[&ValuedMultiPolygon<F, Z>; 2],
+    input_vertices: &[(Coord<F>, &Z)],
+    point: Coord<F>,
+    incoming: &EdgeSource,
+    outgoing: &EdgeSource,
+) -> Z
+where
+    F: Float,
+    Z: Interpolate<F>,
+{
+    // An input vertex is usually an endpoint of the source of one of its edges. If several
+    // input vertices coincide, this prefers the one on the boundary of the result.
+    for source in [outgoing, incoming] {
+        let (start, end, start_value, end_value) = operands[source.operand].edge(source);
+        if point == start {
+            return start_value.clone();
+        }
+        if point == end {
+            return end_value.clone();
+        }
+    }
+    // Otherwise, it may touch the interior of the edges of the other operand.
+    if let Ok(i) = input_vertices.binary_search_by(|(c, _)| compare_coords(c, &point)) {
+        return input_vertices[i].1.clone();
+    }
+
+    // The intersection point may be rounded off the source edge, so it is projected onto it.
+    let (start, end, start_value, end_value) = operands[outgoing.operand].edge(outgoing);
+    let (d, p) = (end - start, point - start);
+    let t = (p.x * d.x + p.y * d.y) / (d.x * d.x + d.y * d.y);
+    start_value.interpolate(end_value, t.max(F::zero()).min(F::one()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo_types::polygon;
+
+    fn valued<Z>(multi_polygon: MultiPolygon<f64>, f: impl Fn(Coord<f64>) -> Z) -> ValuedMultiPolygon<f64, Z> {
+        let values = multi_polygon
+            .0
+            .iter()
+            .map(|p| {
+                std::iter::once(p.exterior())
+                    .chain(p.interiors())
+                    .map(|ring| ring.0.iter().map(|c| f(*c)).collect())
+                    .collect()
+            })
+            .collect();
+        ValuedMultiPolygon { multi_polygon, values }
+    }
+
+    #[test]
+    fn test_interpolated_values() {
+        let a = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)];
+        let b = polygon![(x: 1., y: 1.), (x: 3., y: 1.), (x: 3., y: 3.), (x: 1., y: 3.), (x: 1., y: 1.)];
+        let subject = valued(MultiPolygon(vec![a]), |c| [c.x * 10., 1.]);
+        let clipping = valued(MultiPolygon(vec![b]), |c| [c.y * 100., 2.]);
+
+        let result = try_boolean_with_values(&subject, &clipping, Operation::Union, FillRules::default()).unwrap();
+
+        // The points are (0, 0), (2, 0), (2, 1), (3, 1), (3, 3), (1, 3), (1, 2), (0, 2), (0, 0).
+        // The intersection (2, 1) is followed by a clipping edge, (1, 2) by a subject edge.
+        assert_eq!(
+            result.values,
+            vec![vec![vec![
+                [0., 1.],
+                [20., 1.],
+                [100., 2.],
+                [100., 2.],
+                [300., 2.],
+                [300., 2.],
+                [10., 1.],
+                [0., 1.],
+                [0., 1.],
+            ]]]
+        );
+    }
+
+    #[test]
+    fn test_mismatched_values() {
+        let a = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let mut subject = valued(MultiPolygon(vec![a]), |c| c.x);
+        let clipping = subject.clone();
+        subject.values[0][0].pop();
+
+        assert_eq!(
+            try_boolean_with_values(&subject, &clipping, Operation::Union, FillRules::default()),
+            Err(BooleanOpError::MismatchedVertexValues)
+        );
+    }
+}
@@ -1,21 +1,39 @@
 mod node;
-mod set;
-mod tree;
+pub mod set;
+// The lints are raised by newer versions of clippy on the original code of the tree.
+#[allow(
+    clippy::needless_lifetimes,
+    clippy::question_mark,
+    clippy::mem_replace_option_with_some
+)]
+pub mod tree;
 
 pub use self::set::SplaySet;
-pub use self::tree::SplayTree;
+pub use self::tree::{Entry, SplayTree};
 
 #[cfg(test)]
+#[allow(clippy::legacy_numeric_constants, clippy::explicit_counter_loop)]
 mod test {
     use super::*;
-    use rand::random;
+    use rand::rngs::StdRng;
+    use rand::{random, Rng, SeedableRng};
     use std::cmp::Ordering;
+    use std::collections::{BTreeMap, BTreeSet};
     use std::i32;
+    use std::ops::Bound;
 
     fn int_comparator(a: &i32, b: &i32) -> Ordering {
         a.cmp(b)
     }
 
+    #[test]
+    fn trees_are_send() {
+        fn assert_send<T: Send>() {}
+
+        assert_send::<SplayTree<i32, i32, fn(&i32, &i32) -> Ordering>>();
+        assert_send::<SplaySet<i32, fn(&i32, &i32) -> Ordering>>();
+    }
+
     #[test]
     fn insert_simple() {
         let mut t = SplayTree::new(int_comparator);
@@ -212,4 +230,228 @@ mod test {
         assert_eq!(m.min(), Some(&min));
         assert_eq!(m.max(), Some(&max));
     }
+
+    fn reverse_comparator(a: &i32, b: &i32) -> Ordering {
+        b.cmp(a)
+    }
+
+    type IntTree = SplayTree<i32, i32, fn(&i32, &i32) -> Ordering>;
+
+    fn random_tree(rng: &mut StdRng, n: usize) -> (IntTree, BTreeMap<i32, i32>) {
+        let mut tree = SplayTree::new(int_comparator as fn(&i32, &i32) -> Ordering);
+        let mut map = BTreeMap::new();
+        for _ in 0..n {
+            let (k, v) = (rng.gen_range(-100..100), rng.gen());
+            assert_eq!(tree.insert(k, v), map.insert(k, v));
+        }
+        (tree, map)
+    }
+
+    #[test]
+    fn iter_matches_btree_map() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (tree, map) = random_tree(&mut rng, 150);
+
+        assert!(tree.iter().eq(map.iter()));
+        assert!(tree.iter().rev().eq(map.iter().rev()));
+        assert!((&tree).into_iter().eq(&map));
+        assert_eq!(tree.iter().len(), map.len());
+
+        // Lookups restructure the tree, but must not affect an ongoing iteration.
+        let mut iter = tree.iter();
+        let first = iter.next();
+        assert!(tree.contains(&50) == map.contains_key(&50));
+        assert_eq!(tree.next(&-50), map.range(-49..).next());
+        assert!(first.into_iter().chain(iter).eq(map.iter()));
+    }
+
+    #[test]
+    fn iter_respects_comparator() {
+        let mut tree = SplayTree::new(reverse_comparator);
+        tree.extend(vec![(1, 'a'), (3, 'c'), (2, 'b')]);
+
+        assert_eq!(
+            tree.iter().collect::<Vec<_>>(),
+            vec![(&3, &'c'), (&2, &'b'), (&1, &'a')]
+        );
+        assert_eq!(tree.range(&2..).collect::<Vec<_>>(), vec![(&2, &'b'), (&1, &'a')]);
+        assert_eq!(tree.pop_min(), Some((3, 'c')));
+        assert_eq!(tree.pop_max(), Some((1, 'a')));
+    }
+
+    #[test]
+    fn range_matches_btree_map() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let (tree, map) = random_tree(&mut rng, 100);
+
+        let bound = |rng: &mut StdRng| {
+            let key = rng.gen_range(-110..110);
+            match rng.gen_range(0..3) {
+                0 => Bound::Included(key),
+                1 => Bound::Excluded(key),
+                _ => Bound::Unbounded,
+            }
+        };
+        for _ in 0..1000 {
+            let (lower, upper) = (bound(&mut rng), bound(&mut rng));
+            let valid = match (lower, upper) {
+                (Bound::Excluded(a), Bound::Excluded(b)) => a < b,
+                (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b)) => a <= b,
+                _ => true,
+            };
+            if valid {
+                assert!(tree.range((lower, upper)).eq(map.range((lower, upper))));
+                assert!(tree.range((lower, upper)).rev().eq(map.range((lower, upper)).rev()));
+            }
+        }
+
+        assert!(tree.range(-10..10).eq(map.range(-10..10)));
+        assert!(tree.range(-10..=10).eq(map.range(-10..=10)));
+        assert!(tree.range(..10).eq(map.range(..10)));
+        assert!(tree.range(5..5).eq(map.range(5..5)));
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn range_panics_on_decreasing_range() {
+        let tree = SplayTree::<i32, i32, _>::new(int_comparator);
+        tree.range((Bound::Included(2), Bound::Included(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "range start and end are equal and excluded")]
+    fn range_panics_on_empty_excluded_range() {
+        let tree = SplayTree::<i32, i32, _>::new(int_comparator);
+        tree.range((Bound::Excluded(1), Bound::Excluded(1)));
+    }
+
+    #[test]
+    fn pop_min_max_match_btree_map() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let (mut tree, mut map) = random_tree(&mut rng, 100);
+
+        while !map.is_empty() {
+            if rng.gen_bool(0.5) {
+                assert_eq!(tree.pop_min(), map.pop_first());
+            } else {
+                assert_eq!(tree.pop_max(), map.pop_last());
+            }
+            assert_eq!(tree.len(), map.len());
+            assert!(tree.iter().eq(map.iter()));
+        }
+        assert_eq!(tree.pop_min(), None);
+        assert_eq!(tree.pop_max(), None);
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn entry_matches_btree_map() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut tree = SplayTree::new(int_comparator);
+        let mut map = BTreeMap::new();
+
+        for _ in 0..2000 {
+            let key = rng.gen_range(0..50);
+            match rng.gen_range(0..4) {
+                0 => {
+                    *tree.entry(key).or_insert(0) += 1;
+                    *map.entry(key).or_insert(0) += 1;
+                }
+                1 => {
+                    tree.entry(key).and_modify(|v| *v *= 2).or_default();
+                    map.entry(key).and_modify(|v| *v *= 2).or_default();
+                }
+                2 => {
+                    let removed = match tree.entry(key) {
+                        Entry::Occupied(entry) => Some(entry.remove_entry()),
+                        Entry::Vacant(entry) => {
+                            assert_eq!(entry.key(), &key);
+                            None
+                        }
+                    };
+                    let expected = match map.entry(key) {
+                        std::collections::btree_map::Entry::Occupied(entry) => Some(entry.remove_entry()),
+                        std::collections::btree_map::Entry::Vacant(_) => None,
+                    };
+                    assert_eq!(removed, expected);
+                }
+                _ => {
+                    if let Entry::Occupied(mut entry) = tree.entry(key) {
+                        assert_eq!(entry.key(), &key);
+                        assert_eq!(Some(entry.get()), map.get(&key));
+                        assert_eq!(Some(entry.insert(-1)), map.insert(key, -1));
+                    }
+                    assert_eq!(
+                        tree.entry(key).or_insert_with_key(|k| k * 10),
+                        map.entry(key).or_insert_with_key(|k| k * 10)
+                    );
+                }
+            }
+            assert_eq!(tree.len(), map.len());
+        }
+        assert!(tree.iter().eq(map.iter()));
+    }
+
+    #[test]
+    fn retain_matches_btree_map() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let (mut tree, mut map) = random_tree(&mut rng, 150);
+
+        let mut visited = Vec::new();
+        tree.retain(|k, v| {
+            visited.push(*k);
+            *v = v.wrapping_add(1);
+            k % 3 != 0
+        });
+        map.retain(|k, v| {
+            *v = v.wrapping_add(1);
+            k % 3 != 0
+        });
+
+        assert!(visited.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(tree.len(), map.len());
+        assert!(tree.iter().eq(map.iter()));
+        assert_eq!(tree.next(&0), map.range(1..).next());
+        assert_eq!(tree.insert(3, 3), None);
+        assert_eq!(tree.get(&3), Some(&3));
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let tree: SplayTree<_, _, _> = vec![(2, 'b'), (1, 'a'), (2, 'c')].into_iter().collect();
+        let map: BTreeMap<_, _> = vec![(2, 'b'), (1, 'a'), (2, 'c')].into_iter().collect();
+        assert!(tree.iter().eq(map.iter()));
+
+        let mut tree = tree;
+        let other: BTreeMap<_, _> = vec![(0, 'z'), (1, 'y')].into_iter().collect();
+        tree.extend(other.iter());
+        assert_eq!(
+            tree.iter().collect::<Vec<_>>(),
+            vec![(&0, &'z'), (&1, &'y'), (&2, &'c')]
+        );
+    }
+
+    #[test]
+    fn set_matches_btree_set() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let values: Vec<i32> = (0..100).map(|_| rng.gen_range(-50..50)).collect();
+        let mut set: SplaySet<_, _> = values.iter().copied().collect();
+        let mut expected: BTreeSet<_> = values.iter().copied().collect();
+
+        assert_eq!(set.len(), expected.len());
+        assert!(set.iter().eq(expected.iter()));
+        assert!(set.iter().rev().eq(expected.iter().rev()));
+        assert!((&set).into_iter().eq(&expected));
+        assert!(set.range(-10..=10).eq(expected.range(-10..=10)));
+
+        set.extend(&[100, -100]);
+        expected.extend(&[100, -100]);
+        set.retain(|v| v % 2 == 0);
+        expected.retain(|v| v % 2 == 0);
+        assert!(set.iter().eq(expected.iter()));
+
+        assert_eq!(set.pop_min(), expected.pop_first());
+        assert_eq!(set.pop_max(), expected.pop_last());
+        assert!(set.into_iter().eq(expected.into_iter()));
+    }
 }
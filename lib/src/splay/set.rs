@@ -1,6 +1,8 @@
 use super::tree;
 use super::SplayTree;
 use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
 
 pub struct SplaySet<T, C>
 where
@@ -60,6 +62,104 @@ where
     pub fn max(&self) -> Option<&T> {
         self.tree.max()
     }
+
+    /// Removes and returns the least element.
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.tree.pop_min().map(|(t, _)| t)
+    }
+
+    /// Removes and returns the greatest element.
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.tree.pop_max().map(|(t, _)| t)
+    }
+
+    /// Iterates over the elements in the order of the comparator, see `SplayTree::iter`.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.tree.iter(),
+        }
+    }
+
+    /// Iterates over the elements within `range`, see `SplayTree::range`.
+    pub fn range<R>(&self, range: R) -> Iter<'_, T>
+    where
+        R: RangeBounds<T>,
+    {
+        Iter {
+            inner: self.tree.range(range),
+        }
+    }
+
+    /// Keeps only the elements for which `f` returns true, visiting them in ascending order.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.tree.retain(|t, _| f(t))
+    }
+
+    pub fn contains_by<D>(&self, t: &T, comparator: D) -> bool
+    where
+        D: Fn(&T, &T) -> Ordering,
+    {
+        self.tree.contains_by(t, comparator)
+    }
+
+    pub fn find_by<D>(&self, t: &T, comparator: D) -> Option<&T>
+    where
+        D: Fn(&T, &T) -> Ordering,
+    {
+        self.tree.find_key_by(t, comparator)
+    }
+
+    pub fn next_by<D>(&self, t: &T, comparator: D) -> Option<&T>
+    where
+        D: Fn(&T, &T) -> Ordering,
+    {
+        self.tree.next_by(t, comparator).map(|kv| kv.0)
+    }
+
+    pub fn prev_by<D>(&self, t: &T, comparator: D) -> Option<&T>
+    where
+        D: Fn(&T, &T) -> Ordering,
+    {
+        self.tree.prev_by(t, comparator).map(|kv| kv.0)
+    }
+
+    pub fn insert_by<D>(&mut self, t: T, comparator: D) -> bool
+    where
+        D: Fn(&T, &T) -> Ordering,
+    {
+        self.tree.insert_by(t, (), comparator).is_none()
+    }
+
+    pub fn remove_by<D>(&mut self, t: &T, comparator: D) -> bool
+    where
+        D: Fn(&T, &T) -> Ordering,
+    {
+        self.tree.remove_by(t, comparator).is_some()
+    }
+}
+
+impl<T> SplaySet<T, fn(&T, &T) -> Ordering> {
+    /// Creates a set whose order depends on external state, e.g. on an arena the elements
+    /// refer to. Such a set has no comparator of its own, so it must only be accessed via
+    /// the `*_by` methods.
+    pub(crate) fn with_external_order() -> SplaySet<T, fn(&T, &T) -> Ordering> {
+        SplaySet::new(|_, _| panic!("SplaySet with external order accessed without comparator"))
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a SplaySet<T, C>
+where
+    C: Fn(&T, &T) -> Ordering,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl<T, C> IntoIterator for SplaySet<T, C>
@@ -76,6 +176,30 @@ where
     }
 }
 
+/// Iterator over the elements of a `SplaySet`, see `SplaySet::iter`.
+#[derive(Clone)]
+pub struct Iter<'a, T> {
+    inner: tree::Iter<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|(t, _)| t)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.inner.next_back().map(|(t, _)| t)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
 pub struct IntoIter<T> {
     inner: tree::IntoIter<T, ()>,
 }
@@ -106,3 +230,25 @@ where
         }
     }
 }
+
+impl<'a, T, C> Extend<&'a T> for SplaySet<T, C>
+where
+    T: Copy,
+    C: Fn(&T, &T) -> Ordering,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, i: I) {
+        self.extend(i.into_iter().copied());
+    }
+}
+
+/// Collects into a set ordered by `Ord`.
+impl<T> FromIterator<T> for SplaySet<T, fn(&T, &T) -> Ordering>
+where
+    T: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(i: I) -> Self {
+        let mut set = SplaySet::new(T::cmp as fn(&T, &T) -> Ordering);
+        set.extend(i);
+        set
+    }
+}
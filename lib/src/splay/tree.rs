@@ -2,8 +2,9 @@ use super::node::Node;
 use std::cell::UnsafeCell;
 use std::cmp::Ordering;
 use std::fmt;
+use std::iter::FromIterator;
 use std::mem;
-use std::ops::{Index, IndexMut};
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
 
 pub struct SplayTree<K, V, C>
 where
@@ -39,7 +40,7 @@ where
     }
 
     pub fn contains(&self, key: &K) -> bool {
-        self.find_key(key).is_some()
+        self.contains_by(key, &self.comparator)
     }
 
     pub fn get(&self, key: &K) -> Option<&V> {
@@ -74,11 +75,48 @@ where
     }
 
     pub fn find_key(&self, key: &K) -> Option<&K> {
+        self.find_key_by(key, &self.comparator)
+    }
+
+    pub fn next(&self, key: &K) -> Option<(&K, &V)> {
+        self.next_by(key, &self.comparator)
+    }
+
+    pub fn prev(&self, key: &K) -> Option<(&K, &V)> {
+        self.prev_by(key, &self.comparator)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let SplayTree { comparator, root, size } = self;
+        insert(root.get_mut(), size, key, value, comparator)
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let SplayTree { comparator, root, size } = self;
+        remove(root.get_mut(), size, key, comparator)
+    }
+
+    // The `*_by` variants use the given comparator instead of the one of the tree. This is
+    // necessary if the order of the keys depends on state which cannot be borrowed by the
+    // comparator for the lifetime of the tree. The given comparator must order the keys in
+    // the same way as all others used on the tree.
+
+    pub fn contains_by<D>(&self, key: &K, comparator: D) -> bool
+    where
+        D: Fn(&K, &K) -> Ordering,
+    {
+        self.find_key_by(key, comparator).is_some()
+    }
+
+    pub fn find_key_by<D>(&self, key: &K, comparator: D) -> Option<&K>
+    where
+        D: Fn(&K, &K) -> Ordering,
+    {
         // Splay trees are self-modifying, which is the cause of this ugly mess
         match self.root_mut() {
             Some(ref mut root) => {
-                splay(key, root, &self.comparator);
-                if (self.comparator)(key, &root.key) == Ordering::Equal {
+                splay(key, root, &comparator);
+                if comparator(key, &root.key) == Ordering::Equal {
                     Some(&root.key)
                 } else {
                     None
@@ -88,11 +126,14 @@ where
         }
     }
 
-    pub fn next(&self, key: &K) -> Option<(&K, &V)> {
+    pub fn next_by<D>(&self, key: &K, comparator: D) -> Option<(&K, &V)>
+    where
+        D: Fn(&K, &K) -> Ordering,
+    {
         // Splay trees are self-modifying, which is the cause of this ugly mess
         let mut node: &Node<K, V> = match self.root_mut() {
             Some(ref mut root) => {
-                splay(key, root, &self.comparator);
+                splay(key, root, &comparator);
                 root
             }
             None => return None,
@@ -101,7 +142,7 @@ where
         let mut successor: Option<(&K, &V)> = None;
 
         loop {
-            match (self.comparator)(key, &node.key) {
+            match comparator(key, &node.key) {
                 Ordering::Less => {
                     successor = Some((&node.key, &node.value));
                     match node.left {
@@ -119,11 +160,14 @@ where
         successor
     }
 
-    pub fn prev(&self, key: &K) -> Option<(&K, &V)> {
+    pub fn prev_by<D>(&self, key: &K, comparator: D) -> Option<(&K, &V)>
+    where
+        D: Fn(&K, &K) -> Ordering,
+    {
         // Splay trees are self-modifying, which is the cause of this ugly mess
         let mut node: &Node<K, V> = match self.root_mut() {
             Some(ref mut root) => {
-                splay(key, root, &self.comparator);
+                splay(key, root, &comparator);
                 root
             }
             None => return None,
@@ -132,7 +176,7 @@ where
         let mut predecessor: Option<(&K, &V)> = None;
 
         loop {
-            match (self.comparator)(key, &node.key) {
+            match comparator(key, &node.key) {
                 Ordering::Equal | Ordering::Less => match node.left {
                     Some(ref left) => node = left,
                     None => break,
@@ -150,72 +194,115 @@ where
         predecessor
     }
 
-    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        match self.root_mut() {
-            Some(ref mut root) => {
-                splay(&key, root, &self.comparator);
+    pub fn insert_by<D>(&mut self, key: K, value: V, comparator: D) -> Option<V>
+    where
+        D: Fn(&K, &K) -> Ordering,
+    {
+        insert(self.root.get_mut(), &mut self.size, key, value, &comparator)
+    }
 
-                match (self.comparator)(&key, &root.key) {
-                    Ordering::Equal => {
-                        let old = mem::replace(&mut root.value, value);
-                        return Some(old);
-                    }
-                    Ordering::Less => {
-                        let left = root.pop_left();
-                        let new = Node::new_boxed(key, value, left, None);
-                        let prev = mem::replace(root, new);
-                        root.right = Some(prev);
-                    }
-                    Ordering::Greater => {
-                        let right = root.pop_right();
-                        let new = Node::new_boxed(key, value, None, right);
-                        let prev = mem::replace(root, new);
-                        root.left = Some(prev);
-                    }
-                }
-            }
-            slot => {
-                *slot = Some(Node::new_boxed(key, value, None, None));
-            }
-        }
-        self.size += 1;
-        None
+    pub fn remove_by<D>(&mut self, key: &K, comparator: D) -> Option<V>
+    where
+        D: Fn(&K, &K) -> Ordering,
+    {
+        remove(self.root.get_mut(), &mut self.size, key, &comparator)
     }
 
-    pub fn remove(&mut self, key: &K) -> Option<V> {
-        match *self.root_mut() {
-            None => {
-                return None;
-            }
-            Some(ref mut root) => {
-                splay(key, root, &self.comparator);
-                if (self.comparator)(key, &root.key) != Ordering::Equal {
-                    return None;
-                }
-            }
-        }
+    pub fn min(&self) -> Option<&K> {
+        self.min_node().map(|node| &node.key)
+    }
+
+    pub fn max(&self) -> Option<&K> {
+        self.max_node().map(|node| &node.key)
+    }
 
-        let Node { left, right, value, .. } = *self.root_mut().take().unwrap();
+    /// Removes and returns the entry with the least key.
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        self.pop_towards(Ordering::Less)
+    }
 
-        *self.root_mut() = match left {
-            None => right,
-            Some(mut node) => {
-                splay(key, &mut node, &self.comparator);
-                node.right = right;
-                Some(node)
-            }
-        };
+    /// Removes and returns the entry with the greatest key.
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        self.pop_towards(Ordering::Greater)
+    }
 
+    fn pop_towards(&mut self, target: Ordering) -> Option<(K, V)> {
+        let root = self.root.get_mut();
+        splay_towards(root.as_mut()?, |_| target);
         self.size -= 1;
-        Some(value)
+        Some(remove_root(root))
     }
 
-    pub fn min(&self) -> Option<&K> {
-        self.min_node().map(|node| &node.key)
+    /// Iterates over the entries in the order of the comparator.
+    ///
+    /// Lookups restructure the tree even though they take `&self`, so the entries are
+    /// collected up front, and the iterator is unaffected by lookups during the iteration.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.range(..)
     }
 
-    pub fn max(&self) -> Option<&K> {
-        self.max_node().map(|node| &node.key)
+    /// Iterates over the entries with keys within `range`, in the order of the comparator.
+    ///
+    /// Panics if the start of the range is greater than its end, or if both are equal
+    /// and excluded, like `BTreeMap::range`.
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let (lower, upper) = (range.start_bound(), range.end_bound());
+        if let (Bound::Included(start) | Bound::Excluded(start), Bound::Included(end) | Bound::Excluded(end)) =
+            (lower, upper)
+        {
+            match (self.comparator)(start, end) {
+                Ordering::Greater => panic!("range start is greater than range end in SplayTree"),
+                Ordering::Equal if matches!((lower, upper), (Bound::Excluded(_), Bound::Excluded(_))) => {
+                    panic!("range start and end are equal and excluded in SplayTree")
+                }
+                _ => {}
+            }
+        }
+        Iter {
+            inner: collect_range(self.root_ref(), lower, upper, &self.comparator).into_iter(),
+        }
+    }
+
+    /// Gets the entry of `key` for in-place manipulation. If the key is present already,
+    /// the given key is dropped and the key in the tree is kept, like in `BTreeMap`.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let SplayTree { comparator, root, size } = self;
+        let root = root.get_mut();
+        let ordering = match root {
+            Some(ref mut root) => {
+                splay(&key, root, comparator);
+                Some(comparator(&key, &root.key))
+            }
+            None => None,
+        };
+        match ordering {
+            Some(Ordering::Equal) => Entry::Occupied(OccupiedEntry { root, size }),
+            ordering => Entry::Vacant(VacantEntry {
+                key,
+                ordering,
+                root,
+                size,
+            }),
+        }
+    }
+
+    /// Keeps only the entries for which `f` returns true, visiting them in ascending order.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let entries = IntoIter {
+            cur: self.root.get_mut().take(),
+            remaining: self.size,
+        };
+        let retained: Vec<(K, V)> = entries
+            .filter_map(|(key, mut value)| if f(&key, &mut value) { Some((key, value)) } else { None })
+            .collect();
+        self.size = retained.len();
+        *self.root.get_mut() = build_balanced(&mut retained.into_iter(), self.size);
     }
 
     fn min_node(&self) -> Option<&Node<K, V>> {
@@ -287,6 +374,41 @@ where
     }
 }
 
+impl<'a, K, V, C> Extend<(&'a K, &'a V)> for SplayTree<K, V, C>
+where
+    K: Copy,
+    V: Copy,
+    C: Fn(&K, &K) -> Ordering,
+{
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, i: I) {
+        self.extend(i.into_iter().map(|(k, v)| (*k, *v)));
+    }
+}
+
+/// Collects into a tree ordered by `Ord`. If a key occurs several times, the last value wins.
+impl<K, V> FromIterator<(K, V)> for SplayTree<K, V, fn(&K, &K) -> Ordering>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(i: I) -> Self {
+        let mut tree = SplayTree::new(K::cmp as fn(&K, &K) -> Ordering);
+        tree.extend(i);
+        tree
+    }
+}
+
+impl<'a, K, V, C> IntoIterator for &'a SplayTree<K, V, C>
+where
+    C: Fn(&K, &K) -> Ordering,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<K, V, C> IntoIterator for SplayTree<K, V, C>
 where
     C: Fn(&K, &K) -> Ordering,
@@ -322,6 +444,159 @@ where
     }
 }
 
+/// Iterator over the entries of a `SplayTree`, see `SplayTree::iter`.
+#[derive(Clone)]
+pub struct Iter<'a, K, V> {
+    inner: std::vec::IntoIter<(&'a K, &'a V)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+/// A view into a single entry of a `SplayTree`, see `SplayTree::entry`.
+pub enum Entry<'a, K, V> {
+    Vacant(VacantEntry<'a, K, V>),
+    Occupied(OccupiedEntry<'a, K, V>),
+}
+
+/// An entry whose key is not in the tree. The closest key has been splayed to the root.
+pub struct VacantEntry<'a, K, V> {
+    key: K,
+    /// Order of the key relative to the root, `None` for an empty tree.
+    ordering: Option<Ordering>,
+    root: &'a mut Option<Box<Node<K, V>>>,
+    size: &'a mut usize,
+}
+
+/// An entry whose key is in the tree. The key has been splayed to the root.
+pub struct OccupiedEntry<'a, K, V> {
+    root: &'a mut Option<Box<Node<K, V>>>,
+    size: &'a mut usize,
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Vacant(entry) => entry.key(),
+            Entry::Occupied(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        self.or_insert_with_key(|_| default())
+    }
+
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+            Entry::Occupied(entry) => entry.into_mut(),
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(ref mut entry) = self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    V: Default,
+{
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts the value, which makes its node the root of the tree.
+    pub fn insert(self, value: V) -> &'a mut V {
+        let VacantEntry {
+            key,
+            ordering,
+            root,
+            size,
+        } = self;
+        *size += 1;
+        match (root, ordering) {
+            (Some(node), Some(ordering)) => {
+                insert_at_root(node, key, value, ordering);
+                &mut node.value
+            }
+            (root, _) => &mut root.insert(Node::new_boxed(key, value, None, None)).value,
+        }
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    fn node(&self) -> &Node<K, V> {
+        self.root.as_ref().unwrap()
+    }
+
+    pub fn key(&self) -> &K {
+        &self.node().key
+    }
+
+    pub fn get(&self) -> &V {
+        &self.node().value
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.root.as_mut().unwrap().value
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.root.as_mut().unwrap().value
+    }
+
+    /// Replaces the value, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    pub fn remove_entry(self) -> (K, V) {
+        *self.size -= 1;
+        remove_root(self.root)
+    }
+}
+
 pub struct IntoIter<K, V> {
     cur: Option<Box<Node<K, V>>>,
     remaining: usize,
@@ -390,6 +665,145 @@ impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
 
 impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
 
+fn insert<K, V, C>(root: &mut Option<Box<Node<K, V>>>, size: &mut usize, key: K, value: V, comparator: &C) -> Option<V>
+where
+    C: Fn(&K, &K) -> Ordering,
+{
+    match root {
+        Some(ref mut root) => {
+            splay(&key, root, comparator);
+
+            match comparator(&key, &root.key) {
+                Ordering::Equal => {
+                    let old = mem::replace(&mut root.value, value);
+                    return Some(old);
+                }
+                ordering => insert_at_root(root, key, value, ordering),
+            }
+        }
+        slot => {
+            *slot = Some(Node::new_boxed(key, value, None, None));
+        }
+    }
+    *size += 1;
+    None
+}
+
+/// Makes a new node the root, where `ordering` is the order of `key` relative to the key
+/// of the current root, which has to be the closest key in the tree.
+fn insert_at_root<K, V>(root: &mut Box<Node<K, V>>, key: K, value: V, ordering: Ordering) {
+    if ordering == Ordering::Less {
+        let left = root.pop_left();
+        let new = Node::new_boxed(key, value, left, None);
+        let prev = mem::replace(root, new);
+        root.right = Some(prev);
+    } else {
+        let right = root.pop_right();
+        let new = Node::new_boxed(key, value, None, right);
+        let prev = mem::replace(root, new);
+        root.left = Some(prev);
+    }
+}
+
+fn remove<K, V, C>(root: &mut Option<Box<Node<K, V>>>, size: &mut usize, key: &K, comparator: &C) -> Option<V>
+where
+    C: Fn(&K, &K) -> Ordering,
+{
+    match root {
+        None => {
+            return None;
+        }
+        Some(ref mut root) => {
+            splay(key, root, comparator);
+            if comparator(key, &root.key) != Ordering::Equal {
+                return None;
+            }
+        }
+    }
+
+    *size -= 1;
+    Some(remove_root(root).1)
+}
+
+/// Removes the root node, joining its subtrees by splaying the maximum of the left one.
+fn remove_root<K, V>(root: &mut Option<Box<Node<K, V>>>) -> (K, V) {
+    let Node {
+        key,
+        value,
+        left,
+        right,
+    } = *root.take().unwrap();
+
+    *root = match left {
+        None => right,
+        Some(mut node) => {
+            splay_towards(&mut node, |_| Ordering::Greater);
+            node.right = right;
+            Some(node)
+        }
+    };
+
+    (key, value)
+}
+
+/// Builds a balanced tree from `len` entries in ascending order.
+fn build_balanced<K, V, I>(entries: &mut I, len: usize) -> Option<Box<Node<K, V>>>
+where
+    I: Iterator<Item = (K, V)>,
+{
+    if len == 0 {
+        return None;
+    }
+    let left = build_balanced(entries, len / 2);
+    let (key, value) = entries.next().unwrap();
+    let right = build_balanced(entries, len - len / 2 - 1);
+    Some(Node::new_boxed(key, value, left, right))
+}
+
+/// Collects the entries within the bounds in ascending order, without splaying.
+fn collect_range<'a, K, V, C>(
+    root: &'a Option<Box<Node<K, V>>>,
+    lower: Bound<&K>,
+    upper: Bound<&K>,
+    comparator: &C,
+) -> Vec<(&'a K, &'a V)>
+where
+    C: Fn(&K, &K) -> Ordering,
+{
+    let above_lower = |key: &K| match lower {
+        Bound::Included(lower) => comparator(key, lower) != Ordering::Less,
+        Bound::Excluded(lower) => comparator(key, lower) == Ordering::Greater,
+        Bound::Unbounded => true,
+    };
+    let below_upper = |key: &K| match upper {
+        Bound::Included(upper) => comparator(key, upper) != Ordering::Greater,
+        Bound::Excluded(upper) => comparator(key, upper) == Ordering::Less,
+        Bound::Unbounded => true,
+    };
+
+    let mut entries = Vec::new();
+    let mut stack: Vec<&Node<K, V>> = Vec::new();
+    let mut node = root.as_deref();
+    loop {
+        // Descend to the least key of the subtree within the lower bound.
+        while let Some(current) = node {
+            if above_lower(&current.key) {
+                stack.push(current);
+                node = current.left.as_deref();
+            } else {
+                node = current.right.as_deref();
+            }
+        }
+        match stack.pop() {
+            Some(current) if below_upper(&current.key) => {
+                entries.push((&current.key, &current.value));
+                node = current.right.as_deref();
+            }
+            _ => return entries,
+        }
+    }
+}
+
 /// Performs a top-down splay operation on a tree rooted at `node`. This will
 /// modify the pointer to contain the new root of the tree once the splay
 /// operation is done. When finished, if `key` is in the tree, it will be at the
@@ -398,6 +812,17 @@ impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
 fn splay<K, V, C>(key: &K, node: &mut Box<Node<K, V>>, comparator: &C)
 where
     C: Fn(&K, &K) -> Ordering,
+{
+    splay_towards(node, |node_key| comparator(key, node_key))
+}
+
+/// Splay operation towards a target given by its order relative to the keys of the
+/// tree, which allows to splay the minimum (`|_| Ordering::Less`) or the maximum
+/// (`|_| Ordering::Greater`) to the root without knowing their keys.
+#[allow(clippy::borrowed_box)]
+fn splay_towards<K, V, D>(node: &mut Box<Node<K, V>>, target: D)
+where
+    D: Fn(&K) -> Ordering,
 {
     let mut newleft = None;
     let mut newright = None;
@@ -410,7 +835,7 @@ where
         let mut r = &mut newleft;
 
         loop {
-            match comparator(key, &node.key) {
+            match target(&node.key) {
                 // Found it, yay!
                 Ordering::Equal => break,
 
@@ -420,7 +845,7 @@ where
                         None => break,
                     };
                     // rotate this node right if necessary
-                    if comparator(key, &left.key) == Ordering::Less {
+                    if target(&left.key) == Ordering::Less {
                         // A bit odd, but avoids drop glue
                         mem::swap(&mut node.left, &mut left.right);
                         mem::swap(&mut left, node);
@@ -446,7 +871,7 @@ where
                         None => break,
                     };
 
-                    if comparator(key, &right.key) == Ordering::Greater {
+                    if target(&right.key) == Ordering::Greater {
                         mem::swap(&mut node.right, &mut right.left);
                         mem::swap(&mut right, node);
                         let none = mem::replace(&mut node.left, Some(right));
@@ -3,13 +3,19 @@ use std::time::Duration;
 
 use geo::MultiPolygon;
 
-use geo_booleanop::boolean::BooleanOp;
+use geo_booleanop::boolean::{
+    unary_union, BTreeSweepLine, BooleanOp, FillRules, Operation, SkipListSweepLine, SplaySweepLine, SweepLineStatus,
+};
 use geo_booleanop_tests::data_generators::{
-    generate_circles_vs_rects, generate_grid_polygons, generate_random_triangles_polygons,
+    generate_circles_vs_rects, generate_grid_polygons, generate_large_grid_polygons,
+    generate_many_random_triangles_polygons, generate_nearly_vertical_crossing, generate_overlapping_grid,
+    generate_random_triangles_polygons,
 };
 use geo_booleanop_tests::helper::load_test_case;
 
-fn load(filename: &str) -> (MultiPolygon<f64>, MultiPolygon<f64>) {
+type Operands = (MultiPolygon<f64>, MultiPolygon<f64>);
+
+fn load(filename: &str) -> Operands {
     let (_, p1, p2) = load_test_case(filename);
     (p1, p2)
 }
@@ -64,9 +70,33 @@ fn benchmarks(c: &mut Criterion) {
         BatchSize::LargeInput,
     ));
 
+    g.bench_function("grid/boolean_all", |b| b.iter_batched(
+        generate_grid_polygons,
+        |(p1, p2)| p1.boolean_all(&p2),
+        BatchSize::LargeInput,
+    ));
+
+    g.bench_function("grid/all_separately", |b| b.iter_batched(
+        generate_grid_polygons,
+        |(p1, p2)| (p1.intersection(&p2), p1.union(&p2), p1.difference(&p2), p1.xor(&p2)),
+        BatchSize::LargeInput,
+    ));
+
+    g.bench_function("overlapping_grid/unary_union", |b| b.iter_batched(
+        generate_overlapping_grid,
+        |p| unary_union(&p.0),
+        BatchSize::LargeInput,
+    ));
+
     // large benchmarks
     g.sample_size(10);
 
+    g.bench_function("overlapping_grid/union_fold", |b| b.iter_batched(
+        generate_overlapping_grid,
+        |p| p.0.iter().fold(MultiPolygon(vec![]), |acc, polygon| acc.union(polygon)),
+        BatchSize::LargeInput,
+    ));
+
     g.bench_function("asia/union", |b| b.iter_batched(
         || load("fixtures/benchmarks/asia.geojson"),
         |(p1, p2)| p1.union(&p2),
@@ -78,6 +108,59 @@ fn benchmarks(c: &mut Criterion) {
         |(p1, p2)| p1.xor(&p2),
         BatchSize::LargeInput,
     ));
+
+    // large outputs
+    g.bench_function("large_grid/xor", |b| b.iter_batched(
+        generate_large_grid_polygons,
+        |(p1, p2)| p1.xor(&p2),
+        BatchSize::LargeInput,
+    ));
+
+    g.bench_function("many_random_triangles/xor", |b| b.iter_batched(
+        generate_many_random_triangles_polygons,
+        |(p1, p2)| p1.xor(&p2),
+        BatchSize::LargeInput,
+    ));
+
+    g.bench_function("nearly_vertical_crossing/xor", |b| b.iter_batched(
+        generate_nearly_vertical_crossing,
+        |(p1, p2)| p1.xor(&p2),
+        BatchSize::LargeInput,
+    ));
+}
+
+fn bench_sweep_line<S: SweepLineStatus>(c: &mut Criterion, name: &str) {
+    let mut g = c.benchmark_group("sweep_line");
+    g.sample_size(10);
+
+    let mut bench = |case: &str, generate: fn() -> Operands, operation: Operation| {
+        g.bench_function(format!("{}/{}", case, name), |b| {
+            b.iter_batched(
+                generate,
+                |(p1, p2)| p1.try_boolean_with_sweep_line::<S>(&p2, operation, FillRules::default()),
+                BatchSize::LargeInput,
+            )
+        });
+    };
+
+    bench("grid/xor", generate_grid_polygons, Operation::Xor);
+    bench(
+        "asia/union",
+        || load("fixtures/benchmarks/asia.geojson"),
+        Operation::Union,
+    );
+    bench("circles_vs_rects/xor", generate_circles_vs_rects, Operation::Xor);
+    bench(
+        "many_random_triangles/xor",
+        generate_many_random_triangles_polygons,
+        Operation::Xor,
+    );
+}
+
+fn sweep_line_benchmarks(c: &mut Criterion) {
+    bench_sweep_line::<SplaySweepLine>(c, "splay");
+    bench_sweep_line::<BTreeSweepLine>(c, "btree");
+    bench_sweep_line::<SkipListSweepLine>(c, "skip_list");
 }
 
 fn config() -> Criterion {
@@ -89,6 +172,6 @@ fn config() -> Criterion {
 criterion_group! {
     name = benches;
     config = config();
-    targets = benchmarks
+    targets = benchmarks, sweep_line_benchmarks
 }
 criterion_main!(benches);
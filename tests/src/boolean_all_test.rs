@@ -0,0 +1,46 @@
+use glob::glob;
+
+use super::data_generators::generate_random_triangles_polygons;
+use super::helper::load_test_case;
+use geo::MultiPolygon;
+use geo_booleanop::boolean::{BooleanOp, BooleanOpError, BooleanResults, FillRule, FillRules, Operation};
+
+fn assert_matches_individual_operations(p1: &MultiPolygon<f64>, p2: &MultiPolygon<f64>, fill_rules: FillRules) {
+    let results = p1.boolean_all_with_fill_rules(p2, fill_rules);
+
+    assert_eq!(
+        results,
+        BooleanResults {
+            intersection: p1.boolean_with_fill_rules(p2, Operation::Intersection, fill_rules),
+            union: p1.boolean_with_fill_rules(p2, Operation::Union, fill_rules),
+            difference: p1.boolean_with_fill_rules(p2, Operation::Difference, fill_rules),
+            xor: p1.boolean_with_fill_rules(p2, Operation::Xor, fill_rules),
+        }
+    );
+}
+
+#[test]
+fn test_generic_test_cases() {
+    for entry in glob("./fixtures/generic_test_cases/*.geojson").expect("Failed to read glob pattern") {
+        let filename = entry.unwrap().to_str().unwrap().to_string();
+        println!("Running test case: {}", filename);
+        let (_, p1, p2) = load_test_case(&filename);
+
+        assert_matches_individual_operations(&p1, &p2, FillRules::default());
+    }
+}
+
+#[test]
+fn test_non_zero_fill_rule() {
+    let (p1, p2) = generate_random_triangles_polygons();
+
+    assert_matches_individual_operations(&p1, &p2, FillRules::both(FillRule::NonZero));
+}
+
+#[test]
+fn test_non_finite_coordinates() {
+    let (mut p1, p2) = generate_random_triangles_polygons();
+    p1.0[0].exterior_mut(|ring| ring.0[1].x = f64::INFINITY);
+
+    assert_eq!(p1.try_boolean_all(&p2), Err(BooleanOpError::NonFiniteCoordinate));
+}
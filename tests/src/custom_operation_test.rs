@@ -0,0 +1,67 @@
+use glob::glob;
+
+use super::data_generators::generate_random_triangles_polygons;
+use super::helper::load_test_case;
+use geo::polygon;
+use geo_booleanop::boolean::{BooleanOp, BooleanOpError, Operation};
+
+fn intersection(subject_in: bool, clipping_in: bool) -> bool {
+    subject_in && clipping_in
+}
+
+fn union(subject_in: bool, clipping_in: bool) -> bool {
+    subject_in || clipping_in
+}
+
+fn xor(subject_in: bool, clipping_in: bool) -> bool {
+    subject_in != clipping_in
+}
+
+fn difference(subject_in: bool, clipping_in: bool) -> bool {
+    subject_in && !clipping_in
+}
+
+fn reverse_difference(subject_in: bool, clipping_in: bool) -> bool {
+    clipping_in && !subject_in
+}
+
+#[test]
+fn test_generic_test_cases() {
+    for entry in glob("./fixtures/generic_test_cases/*.geojson").expect("Failed to read glob pattern") {
+        let filename = entry.unwrap().to_str().unwrap().to_string();
+        println!("Running test case: {}", filename);
+        let (_, p1, p2) = load_test_case(&filename);
+
+        assert_eq!(p1.boolean(&p2, Operation::Custom(intersection)), p1.intersection(&p2));
+        assert_eq!(p1.boolean(&p2, Operation::Custom(union)), p1.union(&p2));
+        assert_eq!(p1.boolean(&p2, Operation::Custom(xor)), p1.xor(&p2));
+        assert_eq!(p1.boolean(&p2, Operation::Custom(difference)), p1.difference(&p2));
+    }
+}
+
+#[test]
+fn test_reverse_difference() {
+    let (p1, p2) = generate_random_triangles_polygons();
+
+    assert_eq!(
+        p1.boolean(&p2, Operation::Custom(reverse_difference)),
+        p2.difference(&p1)
+    );
+}
+
+#[test]
+fn test_disjoint_operands() {
+    let p1 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+    let p2 = polygon![(x: 2., y: 0.), (x: 3., y: 0.), (x: 3., y: 1.), (x: 2., y: 0.)];
+
+    assert_eq!(p1.boolean(&p2, Operation::Custom(reverse_difference)).0, vec![p2]);
+}
+
+#[test]
+fn test_unbounded_result() {
+    let (p1, p2) = generate_random_triangles_polygons();
+
+    let neither = Operation::Custom(|subject_in, clipping_in| !subject_in && !clipping_in);
+
+    assert_eq!(p1.try_boolean(&p2, neither), Err(BooleanOpError::UnboundedResult));
+}
@@ -122,12 +122,77 @@ pub fn generate_random_triangles(num_polys: usize, seed: u64) -> MultiPolygon<f6
     )
 }
 
+/// Random axis-aligned rectangles with corners on an integer grid of the given size, which
+/// frequently share edges and corners.
+pub fn generate_random_grid_rects(num_polys: usize, size: i32, seed: u64) -> MultiPolygon<f64> {
+    let mut rng: StdRng = SeedableRng::seed_from_u64(seed);
+
+    MultiPolygon(
+        (0..num_polys)
+            .map(|_| {
+                let x = rng.gen_range(0..size);
+                let y = rng.gen_range(0..size);
+                let w = rng.gen_range(1..=size - x);
+                let h = rng.gen_range(1..=size - y);
+                let center = xy(x as f64 + w as f64 / 2.0, y as f64 + h as f64 / 2.0);
+                generate_rect_centered(center, w as f64, h as f64)
+            })
+            .collect(),
+    )
+}
+
 pub fn generate_grid_polygons() -> (MultiPolygon<f64>, MultiPolygon<f64>) {
     let a = generate_grid(-15.0, 15.0, 0.4, 31);
     let b = generate_grid(-15.4, 15.4, 0.4, 31);
     (a, b)
 }
 
+/// Overlapping grids producing a result with a large number of contours and events.
+pub fn generate_large_grid_polygons() -> (MultiPolygon<f64>, MultiPolygon<f64>) {
+    let a = generate_grid(-50.0, 50.0, 0.4, 101);
+    let b = generate_grid(-50.4, 50.4, 0.4, 101);
+    (a, b)
+}
+
+/// Random triangles with many intersections, which split segments after their left
+/// events have been processed.
+pub fn generate_many_random_triangles_polygons() -> (MultiPolygon<f64>, MultiPolygon<f64>) {
+    let a = generate_random_triangles(100, 1);
+    let b = generate_random_triangles(100, 2);
+    (a, b)
+}
+
+/// A column of triangles with their tips on the y-axis, crossed by the edge of a triangle
+/// which is only the smallest subnormal away from vertical. Its intersection with the
+/// horizontal edge of the clipping operand is rounded onto the axis, below the start of the
+/// edge, after the events of the 1000 tips above it have been processed, which leaves the
+/// result events out of order by about 2000 positions.
+pub fn generate_nearly_vertical_crossing() -> (MultiPolygon<f64>, MultiPolygon<f64>) {
+    let num_tips = 4000;
+    let top = num_tips as f64 + 1.0;
+    let crossing = 3000.5;
+
+    let a = MultiPolygon(vec![Polygon::new(
+        LineString(vec![xy(0, top), xy(5e-324, 0), xy(1, top), xy(0, top)]),
+        vec![],
+    )]);
+    let mut b: Vec<_> = (1..=num_tips)
+        .map(|i| {
+            let y = i as f64;
+            Polygon::new(
+                LineString(vec![xy(0, y), xy(-1, y + 0.3), xy(-1, y + 0.6), xy(0, y)]),
+                vec![],
+            )
+        })
+        .collect();
+    b.push(generate_rect_centered(
+        xy(0, (crossing + top + 10.0) / 2.0),
+        4.0,
+        top + 10.0 - crossing,
+    ));
+    (a, MultiPolygon(b))
+}
+
 pub fn generate_circles_vs_rects() -> (MultiPolygon<f64>, MultiPolygon<f64>) {
     let a = generate_nested_circles(xy(0, 0), 1.0, 10.0, 30, 500);
     let b = generate_nested_rects(xy(1, 1), 2.0, 20.0, 30);
@@ -139,3 +204,7 @@ pub fn generate_random_triangles_polygons() -> (MultiPolygon<f64>, MultiPolygon<
     let b = generate_random_triangles(10, 2);
     (a, b)
 }
+
+pub fn generate_overlapping_grid() -> MultiPolygon<f64> {
+    generate_grid(-7.0, 7.0, 1.5, 15)
+}
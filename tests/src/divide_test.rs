@@ -0,0 +1,75 @@
+use glob::glob;
+use std::collections::HashMap;
+
+use super::data_generators::{generate_grid_polygons, generate_random_triangles_polygons};
+use super::helper::load_test_case;
+use geo::{Area, MultiPolygon};
+use geo_booleanop::boolean::{BooleanOp, BooleanOpError};
+
+type Edge = ((u64, u64), (u64, u64));
+
+/// Counts the undirected edges of all rings.
+fn count_edges(multi_polygons: &[&MultiPolygon<f64>]) -> HashMap<Edge, usize> {
+    let mut counts = HashMap::new();
+    let rings = multi_polygons
+        .iter()
+        .flat_map(|multi_polygon| multi_polygon.0.iter())
+        .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()));
+    for line in rings.flat_map(|ring| ring.lines()) {
+        let start = (line.start.x.to_bits(), line.start.y.to_bits());
+        let end = (line.end.x.to_bits(), line.end.y.to_bits());
+        *counts.entry((start.min(end), start.max(end))).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Asserts that the parts of the division tile the union exactly: every edge of the union
+/// is an edge of exactly one part, and every other edge is shared by exactly two parts.
+fn assert_tiles_union(p1: &MultiPolygon<f64>, p2: &MultiPolygon<f64>) {
+    let division = p1.divide(p2);
+    let union = p1.union(p2);
+
+    let area =
+        division.subject_only.unsigned_area() + division.both.unsigned_area() + division.clipping_only.unsigned_area();
+    assert!((area - union.unsigned_area()).abs() < 1e-9 * union.unsigned_area().max(1.0));
+
+    let union_edges = count_edges(&[&union]);
+    let part_edges = count_edges(&[&division.subject_only, &division.both, &division.clipping_only]);
+    for (edge, count) in &part_edges {
+        let expected = if union_edges.contains_key(edge) { 1 } else { 2 };
+        assert_eq!(*count, expected, "edge {:?}", edge);
+    }
+    for edge in union_edges.keys() {
+        assert!(part_edges.contains_key(edge), "edge {:?}", edge);
+    }
+}
+
+#[test]
+fn test_generic_test_cases() {
+    for entry in glob("./fixtures/generic_test_cases/*.geojson").expect("Failed to read glob pattern") {
+        let filename = entry.unwrap().to_str().unwrap().to_string();
+        println!("Running test case: {}", filename);
+        let (_, p1, p2) = load_test_case(&filename);
+
+        let division = p1.divide(&p2);
+        assert_eq!(division.subject_only, p1.difference(&p2));
+        assert_eq!(division.both, p1.intersection(&p2));
+    }
+}
+
+#[test]
+fn test_parts_tile_union() {
+    let (p1, p2) = generate_random_triangles_polygons();
+    assert_tiles_union(&p1, &p2);
+
+    let (p1, p2) = generate_grid_polygons();
+    assert_tiles_union(&p1, &p2);
+}
+
+#[test]
+fn test_non_finite_coordinates() {
+    let (mut p1, p2) = generate_random_triangles_polygons();
+    p1.0[0].exterior_mut(|ring| ring.0[1].y = f64::NAN);
+
+    assert_eq!(p1.try_divide(&p2), Err(BooleanOpError::NonFiniteCoordinate));
+}
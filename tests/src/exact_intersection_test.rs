@@ -0,0 +1,524 @@
+use super::helper::{extract_expected_result, load_test_case, operation_area, TestOperation, EXACT_RESULT_PROPERTY};
+use geo::{Area, Coord, CoordsIter, Line, MultiPolygon};
+use geo_booleanop::boolean::{Kernel, LineIntersection, RobustKernel};
+use geojson::Feature;
+use glob::glob;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+
+/// Exact value `mantissa * 2^exponent` of floating point numbers and of their sums, differences
+/// and products, with a mantissa of arbitrary size. It is independent of the expansions used
+/// by the library.
+#[derive(Clone, Debug)]
+struct Dyadic {
+    negative: bool,
+    /// Magnitude of the mantissa, least significant digit first.
+    digits: Vec<u32>,
+    exponent: i32,
+}
+
+impl Dyadic {
+    fn from_f64(value: f64) -> Dyadic {
+        let bits = value.to_bits();
+        let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+        // Subnormal numbers have no implicit leading bit and the exponent of the smallest normal one.
+        let (mantissa, exponent) = if biased_exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased_exponent - 1075)
+        };
+        Dyadic {
+            negative: value.is_sign_negative(),
+            digits: vec![mantissa as u32, (mantissa >> 32) as u32],
+            exponent,
+        }
+    }
+
+    fn signum(&self) -> Ordering {
+        match (self.digits.iter().all(|&d| d == 0), self.negative) {
+            (true, _) => Ordering::Equal,
+            (false, true) => Ordering::Less,
+            (false, false) => Ordering::Greater,
+        }
+    }
+
+    fn neg(&self) -> Dyadic {
+        Dyadic {
+            negative: !self.negative,
+            ..self.clone()
+        }
+    }
+
+    fn half(&self) -> Dyadic {
+        Dyadic {
+            exponent: self.exponent - 1,
+            ..self.clone()
+        }
+    }
+
+    fn add(&self, other: &Dyadic) -> Dyadic {
+        let exponent = self.exponent.min(other.exponent);
+        let a = shift_left(&self.digits, (self.exponent - exponent) as u32);
+        let b = shift_left(&other.digits, (other.exponent - exponent) as u32);
+        let (negative, digits) = if self.negative == other.negative {
+            (self.negative, add_digits(&a, &b))
+        } else if compare_digits(&a, &b) == Ordering::Less {
+            (other.negative, sub_digits(&b, &a))
+        } else {
+            (self.negative, sub_digits(&a, &b))
+        };
+        Dyadic {
+            negative,
+            digits,
+            exponent,
+        }
+    }
+
+    fn sub(&self, other: &Dyadic) -> Dyadic {
+        self.add(&other.neg())
+    }
+
+    fn mul(&self, other: &Dyadic) -> Dyadic {
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.digits.iter().enumerate() {
+                let value = digits[i + j] as u64 + a as u64 * b as u64 + carry;
+                digits[i + j] = value as u32;
+                carry = value >> 32;
+            }
+            digits[i + other.digits.len()] = carry as u32;
+        }
+        Dyadic {
+            negative: self.negative != other.negative,
+            digits,
+            exponent: self.exponent + other.exponent,
+        }
+    }
+}
+
+fn shift_left(digits: &[u32], bits: u32) -> Vec<u32> {
+    let mut shifted = vec![0u32; (bits / 32) as usize];
+    let mut carry = 0u32;
+    for &d in digits {
+        let value = ((d as u64) << (bits % 32)) | carry as u64;
+        shifted.push(value as u32);
+        carry = (value >> 32) as u32;
+    }
+    shifted.push(carry);
+    shifted
+}
+
+fn compare_digits(a: &[u32], b: &[u32]) -> Ordering {
+    let significant = |digits: &[u32]| digits.len() - digits.iter().rev().take_while(|&&d| d == 0).count();
+    let (len_a, len_b) = (significant(a), significant(b));
+    len_a
+        .cmp(&len_b)
+        .then_with(|| a[..len_a].iter().rev().cmp(b[..len_b].iter().rev()))
+}
+
+fn add_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let value = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(value as u32);
+        carry = value >> 32;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+/// Difference of the magnitudes `a >= b`.
+fn sub_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut borrow = 0i64;
+    a.iter()
+        .enumerate()
+        .map(|(i, &d)| {
+            let mut value = d as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = (value < 0) as i64;
+            if value < 0 {
+                value += 1 << 32;
+            }
+            value as u32
+        })
+        .collect()
+}
+
+/// Adjacent floating point number in the direction of `up`.
+fn next_float(value: f64, up: bool) -> f64 {
+    if value == 0. {
+        let smallest = f64::from_bits(1);
+        return if up { smallest } else { -smallest };
+    }
+    let bits = value.to_bits();
+    if (value > 0.) == up {
+        f64::from_bits(bits + 1)
+    } else {
+        f64::from_bits(bits - 1)
+    }
+}
+
+/// Whether `value` is the exact quotient `numerator / denominator` for a positive denominator,
+/// rounded to the nearest floating point number, with ties rounded to even.
+fn is_rounded_quotient(numerator: &Dyadic, denominator: &Dyadic, value: f64) -> bool {
+    let v = Dyadic::from_f64(value);
+    let position = |neighbor: f64| {
+        let midpoint = v.add(&Dyadic::from_f64(neighbor)).half();
+        numerator.sub(&midpoint.mul(denominator)).signum()
+    };
+    let is_even = value.to_bits() & 1 == 0;
+    let above_lower = match position(next_float(value, false)) {
+        Ordering::Greater => true,
+        Ordering::Equal => is_even,
+        Ordering::Less => false,
+    };
+    let below_upper = match position(next_float(value, true)) {
+        Ordering::Less => true,
+        Ordering::Equal => is_even,
+        Ordering::Greater => false,
+    };
+    above_lower && below_upper
+}
+
+/// Exact intersection point of two segments which cross in the interior of both, given by
+/// the numerators of its coordinates and their common, positive denominator.
+struct Crossing {
+    x: Dyadic,
+    y: Dyadic,
+    denominator: Dyadic,
+}
+
+impl Crossing {
+    fn of(a: Line<f64>, b: Line<f64>) -> Option<Crossing> {
+        let [a1x, a1y, a2x, a2y, b1x, b1y, b2x, b2y] = [
+            a.start.x, a.start.y, a.end.x, a.end.y, b.start.x, b.start.y, b.end.x, b.end.y,
+        ]
+        .map(Dyadic::from_f64);
+        let (dax, day) = (a2x.sub(&a1x), a2y.sub(&a1y));
+        let (dbx, dby) = (b2x.sub(&b1x), b2y.sub(&b1y));
+        let (dx, dy) = (b1x.sub(&a1x), b1y.sub(&a1y));
+        let cross = |ux: &Dyadic, uy: &Dyadic, vx: &Dyadic, vy: &Dyadic| ux.mul(vy).sub(&uy.mul(vx));
+
+        // The intersection is at `a1 + s / d * (a2 - a1)` and at `b1 + t / d * (b2 - b1)`.
+        let mut d = cross(&dax, &day, &dbx, &dby);
+        let mut s = cross(&dx, &dy, &dbx, &dby);
+        let mut t = cross(&dx, &dy, &dax, &day);
+        match d.signum() {
+            // Collinear segments only intersect at their endpoints.
+            Ordering::Equal => return None,
+            Ordering::Less => {
+                d = d.neg();
+                s = s.neg();
+                t = t.neg();
+            }
+            Ordering::Greater => {}
+        }
+        let is_interior = |p: &Dyadic| p.signum() == Ordering::Greater && p.sub(&d).signum() == Ordering::Less;
+        if !is_interior(&s) || !is_interior(&t) {
+            return None;
+        }
+        Some(Crossing {
+            x: a1x.mul(&d).add(&dax.mul(&s)),
+            y: a1y.mul(&d).add(&day.mul(&s)),
+            denominator: d,
+        })
+    }
+
+    fn is_rounded_to(&self, p: Coord<f64>) -> bool {
+        is_rounded_quotient(&self.x, &self.denominator, p.x) && is_rounded_quotient(&self.y, &self.denominator, p.y)
+    }
+}
+
+fn lines(multi_polygon: &MultiPolygon<f64>) -> Vec<Line<f64>> {
+    multi_polygon
+        .0
+        .iter()
+        .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+        .flat_map(|ring| ring.lines())
+        .collect()
+}
+
+/// Random floating point numbers of both signs over a range of magnitudes, in which their
+/// products and quotients neither overflow nor underflow.
+fn random_floats(rng: &mut StdRng) -> impl Iterator<Item = f64> + '_ {
+    std::iter::repeat_with(move || {
+        let magnitude = rng.gen_range(1.0..2.0) * 2f64.powi(rng.gen_range(-300..300));
+        if rng.gen() {
+            -magnitude
+        } else {
+            magnitude
+        }
+    })
+}
+
+fn is_equal(a: &Dyadic, b: &Dyadic) -> bool {
+    a.sub(b).signum() == Ordering::Equal
+}
+
+#[test]
+fn test_dyadic_arithmetic_matches_error_free_transformations() {
+    let mut rng: StdRng = SeedableRng::seed_from_u64(0);
+    let values: Vec<f64> = random_floats(&mut rng).take(2000).collect();
+    let subnormals = (0..100).map(|i| f64::from_bits(if i == 0 { 1 } else { rng.gen_range(1..1 << 52) }));
+
+    for pair in values.chunks(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (da, db) = (Dyadic::from_f64(a), Dyadic::from_f64(b));
+
+        // The rounding error of the product is exact with a fused multiply add.
+        let product = a * b;
+        let product_error = a.mul_add(b, -product);
+        assert!(is_equal(
+            &da.mul(&db),
+            &Dyadic::from_f64(product).add(&Dyadic::from_f64(product_error))
+        ));
+
+        // The rounding error of the sum is exact by Knuth's two-sum.
+        let sum = a + b;
+        let b_virtual = sum - a;
+        let sum_error = (a - (sum - b_virtual)) + (b - b_virtual);
+        assert!(is_equal(
+            &da.add(&db),
+            &Dyadic::from_f64(sum).add(&Dyadic::from_f64(sum_error))
+        ));
+        assert!(is_equal(&da.sub(&db), &da.add(&db.neg())));
+        assert_eq!(da.sub(&db).signum(), a.partial_cmp(&b).unwrap());
+    }
+    for value in subnormals {
+        let d = Dyadic::from_f64(value);
+        assert!(is_equal(&Dyadic::from_f64(2. * value).half(), &d));
+        assert!(is_equal(&d.add(&d), &Dyadic::from_f64(value + value)));
+        assert_eq!(d.neg().signum(), Ordering::Less);
+    }
+    assert_eq!(Dyadic::from_f64(0.).signum(), Ordering::Equal);
+    assert_eq!(Dyadic::from_f64(-0.).signum(), Ordering::Equal);
+}
+
+#[test]
+fn test_is_rounded_quotient_matches_division() {
+    // The division of floating point numbers is correctly rounded.
+    let mut rng: StdRng = SeedableRng::seed_from_u64(0);
+    let values: Vec<f64> = random_floats(&mut rng).take(2000).collect();
+    for pair in values.chunks(2) {
+        let (numerator, denominator) = (pair[0], pair[1].abs());
+        let quotient = numerator / denominator;
+        let (n, d) = (Dyadic::from_f64(numerator), Dyadic::from_f64(denominator));
+        assert!(is_rounded_quotient(&n, &d, quotient));
+        assert!(!is_rounded_quotient(&n, &d, next_float(quotient, true)));
+        assert!(!is_rounded_quotient(&n, &d, next_float(quotient, false)));
+    }
+
+    // Ties are rounded to even: 1 + 2^-53 is halfway between 1 and the next number.
+    let half_ulp = Dyadic::from_f64(1.).add(&Dyadic::from_f64(2f64.powi(-53)));
+    assert!(is_rounded_quotient(&half_ulp, &Dyadic::from_f64(1.), 1.));
+    let three_half_ulps = Dyadic::from_f64(1.).add(&Dyadic::from_f64(3. * 2f64.powi(-53)));
+    assert!(is_rounded_quotient(
+        &three_half_ulps,
+        &Dyadic::from_f64(1.),
+        1. + 2. * f64::EPSILON
+    ));
+}
+
+/// Test case whose result differs with the `exact-intersection` feature.
+struct ExactCase {
+    filename: String,
+    features: Vec<Feature>,
+    operands: (MultiPolygon<f64>, MultiPolygon<f64>),
+    /// The segments of both operands.
+    segments: Vec<Line<f64>>,
+}
+
+fn cases_with_exact_results() -> Vec<ExactCase> {
+    glob("./fixtures/generic_test_cases/*.geojson")
+        .expect("Failed to read glob pattern")
+        .map(|entry| entry.expect("Valid glob entry").to_str().unwrap().to_string())
+        .filter_map(|filename| {
+            let (features, p1, p2) = load_test_case(&filename);
+            let has_exact_result = features
+                .iter()
+                .any(|feature| feature.contains_property(EXACT_RESULT_PROPERTY));
+            let segments = lines(&p1).into_iter().chain(lines(&p2)).collect();
+            has_exact_result.then_some(ExactCase {
+                filename,
+                features,
+                operands: (p1, p2),
+                segments,
+            })
+        })
+        .collect()
+}
+
+/// Whether `p` is close enough to `segment` to be a rounded point on it.
+fn is_near(segment: Line<f64>, p: Coord<f64>) -> bool {
+    let delta = segment.delta();
+    let length = delta.x.hypot(delta.y);
+    let (dx, dy) = (p.x - segment.start.x, p.y - segment.start.y);
+    let tolerance = 1e-9 * length.max(1.);
+    let along = (dx * delta.x + dy * delta.y) / length;
+    (dx * delta.y - dy * delta.x).abs() / length <= tolerance && along >= -tolerance && along <= length + tolerance
+}
+
+/// Whether the segments `a` and `b` are divided at `p` at their crossing.
+///
+/// The crossing is rounded, unless the rounded point is exactly below the left endpoint of
+/// one of the segments. That would make the part before it vertical, but processed from top
+/// to bottom, so the point is moved to the right by one ulp instead, see `divide_segment`.
+fn is_division_point(a: Line<f64>, b: Line<f64>, p: Coord<f64>) -> bool {
+    let crossing = match Crossing::of(a, b) {
+        Some(crossing) => crossing,
+        None => return false,
+    };
+    let left_endpoint = |segment: Line<f64>| {
+        if (segment.start.x, segment.start.y) < (segment.end.x, segment.end.y) {
+            segment.start
+        } else {
+            segment.end
+        }
+    };
+    let rounded_left = Coord {
+        x: next_float(p.x, false),
+        y: p.y,
+    };
+    crossing.is_rounded_to(p)
+        || (crossing.is_rounded_to(rounded_left)
+            && [a, b].iter().any(|&segment| {
+                let left = left_endpoint(segment);
+                left.x == rounded_left.x && rounded_left.y < left.y
+            }))
+}
+
+/// The vertices of `result` which can't be derived from the input `segments`.
+///
+/// Starting from the input points, a vertex is derived as the correctly rounded crossing of
+/// two segments between derived points on different input segments. A segment which has been
+/// divided at a rounded point is no longer exactly on its input segment, so its intersections
+/// are those of the divided parts.
+fn underivable_vertices(segments: &[Line<f64>], result: &MultiPolygon<f64>) -> Vec<Coord<f64>> {
+    let mut known: Vec<Coord<f64>> = segments.iter().map(|segment| segment.start).collect();
+    let mut remaining: Vec<Coord<f64>> = Vec::new();
+    for p in result.coords_iter() {
+        if !known.contains(&p) && !remaining.contains(&p) {
+            remaining.push(p);
+        }
+    }
+
+    loop {
+        let parts = |segment: Line<f64>| -> Vec<Line<f64>> {
+            let points: Vec<Coord<f64>> = known.iter().copied().filter(|&p| is_near(segment, p)).collect();
+            points
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &a)| points[i + 1..].iter().map(move |&b| Line::new(a, b)))
+                .collect()
+        };
+        let is_derivable = |p: Coord<f64>| {
+            let near: Vec<Line<f64>> = segments
+                .iter()
+                .copied()
+                .filter(|&segment| is_near(segment, p))
+                .collect();
+            near.iter().enumerate().any(|(i, &a)| {
+                near[i + 1..].iter().any(|&b| {
+                    let parts_of_b = parts(b);
+                    parts(a).into_iter().any(|part_of_a| {
+                        parts_of_b
+                            .iter()
+                            .any(|&part_of_b| is_division_point(part_of_a, part_of_b, p))
+                    })
+                })
+            })
+        };
+        let derived: Vec<Coord<f64>> = remaining.iter().copied().filter(|&p| is_derivable(p)).collect();
+        if derived.is_empty() {
+            return remaining;
+        }
+        remaining.retain(|p| !derived.contains(p));
+        known.extend(derived);
+    }
+}
+
+#[test]
+fn test_exact_results_consist_of_rounded_intersections() {
+    let cases = cases_with_exact_results();
+    assert!(!cases.is_empty(), "Expected to find test cases with exact results");
+
+    for case in cases {
+        for feature in case.features.iter().skip(2) {
+            if !feature.contains_property(EXACT_RESULT_PROPERTY) {
+                continue;
+            }
+            let exact_result = extract_expected_result(feature).exact_result;
+            assert_eq!(
+                underivable_vertices(&case.segments, &exact_result),
+                vec![],
+                "{}: vertices which are neither input points nor rounded intersections",
+                case.filename
+            );
+        }
+    }
+}
+
+#[test]
+fn test_exact_results_cover_the_area_of_the_operation() {
+    for case in cases_with_exact_results() {
+        let (p1, p2) = &case.operands;
+        for feature in case.features.iter().skip(2) {
+            if !feature.contains_property(EXACT_RESULT_PROPERTY) {
+                continue;
+            }
+            let expected = extract_expected_result(feature);
+            let (subject, clipping) = match expected.op {
+                TestOperation::DifferenceBA => (p2, p1),
+                _ => (p1, p2),
+            };
+            // The operands use the even-odd rule.
+            let area = operation_area(&subject.0, &clipping.0, |subject_winding, clipping_winding| {
+                let (in_subject, in_clipping) = (subject_winding % 2 != 0, clipping_winding % 2 != 0);
+                match expected.op {
+                    TestOperation::Intersection => in_subject && in_clipping,
+                    TestOperation::Union => in_subject || in_clipping,
+                    TestOperation::Xor => in_subject != in_clipping,
+                    TestOperation::DifferenceAB | TestOperation::DifferenceBA => in_subject && !in_clipping,
+                }
+            });
+            // Rounding the intersection points changes the area by a few ulps of the coordinates.
+            let scale = p1.unsigned_area() + p2.unsigned_area();
+            assert!(
+                (expected.exact_result.unsigned_area() - area).abs() <= 1e-9 * scale,
+                "{}: exact {:?} result has area {}, expected {}",
+                case.filename,
+                expected.op,
+                expected.exact_result.unsigned_area(),
+                area
+            );
+        }
+    }
+}
+
+#[test]
+fn test_robust_kernel_rounds_intersections_correctly() {
+    for ExactCase { filename, segments, .. } in cases_with_exact_results() {
+        for (i, &a) in segments.iter().enumerate() {
+            for &b in &segments[i + 1..] {
+                let crossing = match Crossing::of(a, b) {
+                    Some(crossing) => crossing,
+                    None => continue,
+                };
+                match RobustKernel::intersection(a.start, a.end, b.start, b.end) {
+                    LineIntersection::Point(p) => assert!(
+                        crossing.is_rounded_to(p),
+                        "{}: {:?} is not the rounded intersection of {:?} and {:?}",
+                        filename,
+                        p,
+                        a,
+                        b
+                    ),
+                    other => panic!("{}: {:?} for crossing segments {:?} and {:?}", filename, other, a, b),
+                }
+            }
+        }
+    }
+}
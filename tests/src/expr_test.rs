@@ -0,0 +1,82 @@
+use glob::glob;
+
+use super::data_generators::generate_random_triangles;
+use super::helper::load_test_case;
+use geo::{polygon, Area, MultiPolygon};
+use geo_booleanop::boolean::{BooleanOp, BooleanOpError, Expr, FillRule, FillRules, Operation};
+
+fn assert_same_area(a: &MultiPolygon<f64>, b: &MultiPolygon<f64>) {
+    let (area_a, area_b) = (a.unsigned_area(), b.unsigned_area());
+    assert!(
+        (area_a - area_b).abs() <= 1e-9 * area_a.max(1.0),
+        "{} != {}",
+        area_a,
+        area_b
+    );
+}
+
+#[test]
+fn test_generic_test_cases() {
+    for entry in glob("./fixtures/generic_test_cases/*.geojson").expect("Failed to read glob pattern") {
+        let filename = entry.unwrap().to_str().unwrap().to_string();
+        println!("Running test case: {}", filename);
+        let (_, p1, p2) = load_test_case(&filename);
+
+        let (a, b) = (Expr::operand(&p1), Expr::operand(&p2));
+        assert_same_area(&a.clone().intersection(b.clone()).evaluate(), &p1.intersection(&p2));
+        assert_same_area(&a.clone().union(b.clone()).evaluate(), &p1.union(&p2));
+        assert_same_area(&a.clone().difference(b.clone()).evaluate(), &p1.difference(&p2));
+        assert_same_area(&a.xor(b).evaluate(), &p1.xor(&p2));
+    }
+}
+
+#[test]
+fn test_random_triangles() {
+    let operands: Vec<MultiPolygon<f64>> = (0..4).map(|seed| generate_random_triangles(10, seed)).collect();
+    let [zoning, overlay, wetlands, floodplain] = [&operands[0], &operands[1], &operands[2], &operands[3]];
+
+    let result = Expr::operand(zoning)
+        .union(Expr::operand(overlay))
+        .difference(Expr::operand(wetlands).intersection(Expr::operand(floodplain)))
+        .evaluate();
+
+    let chained = zoning.union(overlay).difference(&wetlands.intersection(floodplain));
+    assert_same_area(&result, &chained);
+}
+
+#[test]
+fn test_same_operand_twice() {
+    let p1 = polygon![(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.), (x: 0., y: 0.)];
+    let p2 = polygon![(x: 2., y: 1.), (x: 6., y: 1.), (x: 6., y: 5.), (x: 2., y: 5.), (x: 2., y: 1.)];
+
+    let result = Expr::polygon(&p1)
+        .xor(Expr::polygon(&p2))
+        .intersection(Expr::polygon(&p1))
+        .evaluate();
+
+    assert_eq!(result, p1.difference(&p2));
+}
+
+#[test]
+fn test_fill_rule() {
+    let p1 = generate_random_triangles(10, 1);
+    let p2 = generate_random_triangles(10, 2);
+
+    let result = Expr::operand(&p1)
+        .union(Expr::operand(&p2))
+        .with_fill_rule(FillRule::NonZero)
+        .evaluate();
+
+    let expected = p1.boolean_with_fill_rules(&p2, Operation::Union, FillRules::both(FillRule::NonZero));
+    assert_same_area(&result, &expected);
+}
+
+#[test]
+fn test_non_finite_coordinates() {
+    let mut p1 = generate_random_triangles(10, 1);
+    let p2 = generate_random_triangles(10, 2);
+    p1.0[3].exterior_mut(|ring| ring.0[0].x = f64::NEG_INFINITY);
+
+    let expr = Expr::operand(&p2).union(Expr::operand(&p1));
+    assert_eq!(expr.try_evaluate(), Err(BooleanOpError::NonFiniteCoordinate));
+}
@@ -1,6 +1,7 @@
 use super::helper::fixture_shapes;
 use geo::Coord;
 use geo_booleanop::boolean::fill_queue::fill_queue;
+use geo_booleanop::boolean::sweep_event::EventArena;
 use geo_booleanop::boolean::BoundingBox;
 use geo_booleanop::boolean::Operation;
 use num_traits::Float;
@@ -21,18 +22,18 @@ fn test_two_polygons() {
         },
     };
     let mut cbbox = sbbox;
-    let mut q = fill_queue(&[s], &[c], &mut sbbox, &mut cbbox, Operation::Intersection);
+    let mut events = EventArena::new();
+    let mut q = fill_queue(&[s], &[c], &mut sbbox, &mut cbbox, Operation::Intersection, &mut events);
 
     let mut sorted = Vec::new();
-    let mut keep_ref = Vec::new();
-    while let Some(e) = q.pop() {
-        keep_ref.push(e.clone());
+    while let Some(id) = q.pop(&events) {
+        let e = &events[id];
         sorted.push((
             e.is_left(),
             e.point.x,
             e.point.y,
-            e.get_other_event().unwrap().point.x,
-            e.get_other_event().unwrap().point.y,
+            events.other(id).unwrap().point.x,
+            events.other(id).unwrap().point.y,
         ));
     }
 
@@ -65,7 +66,8 @@ fn test_fill_event_queue() {
         max: xy(f64::neg_infinity(), f64::neg_infinity()),
     };
     let mut cbbox = sbbox;
-    let mut q = fill_queue(&[s], &[c], &mut sbbox, &mut cbbox, Operation::Intersection);
+    let mut events = EventArena::new();
+    let mut q = fill_queue(&[s], &[c], &mut sbbox, &mut cbbox, Operation::Intersection, &mut events);
 
     assert_eq!(
         sbbox,
@@ -83,16 +85,15 @@ fn test_fill_event_queue() {
     );
 
     let mut sorted = Vec::new();
-    let mut keep_ref = Vec::new();
-    while let Some(e) = q.pop() {
-        keep_ref.push(e.clone());
+    while let Some(id) = q.pop(&events) {
+        let e = &events[id];
         sorted.push((
             e.point.x,
             e.point.y,
             e.is_left(),
-            e.get_other_event().unwrap().point.x,
-            e.get_other_event().unwrap().point.y,
-            e.get_other_event().unwrap().is_left(),
+            events.other(id).unwrap().point.x,
+            events.other(id).unwrap().point.y,
+            events.other(id).unwrap().is_left(),
         ));
     }
     assert_eq!(
@@ -0,0 +1,225 @@
+use super::helper::{assert_no_crossings, load_test_case, operation_area, xy};
+use geo::{Area, LineString, MultiPolygon, Polygon};
+use geo_booleanop::boolean::{BooleanOp, FillRule, FillRules, Operation};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn rect(x1: f64, y1: f64, x2: f64, y2: f64) -> Polygon<f64> {
+    Polygon::new(
+        LineString(vec![xy(x1, y1), xy(x2, y1), xy(x2, y2), xy(x1, y2), xy(x1, y1)]),
+        vec![],
+    )
+}
+
+fn reversed(polygon: Polygon<f64>) -> Polygon<f64> {
+    let mut ring = polygon.exterior().clone();
+    ring.0.reverse();
+    Polygon::new(ring, vec![])
+}
+
+/// Resolves the polygons according to the fill rule by intersecting them with a large rect.
+fn resolve(polygons: &MultiPolygon<f64>, fill_rule: FillRule) -> MultiPolygon<f64> {
+    polygons.boolean_with_fill_rules(
+        &rect(-10.0, -10.0, 10.0, 10.0),
+        Operation::Intersection,
+        FillRules::new(fill_rule, FillRule::EvenOdd),
+    )
+}
+
+fn resolved_area(polygons: &MultiPolygon<f64>, fill_rule: FillRule) -> f64 {
+    resolve(polygons, fill_rule).unsigned_area()
+}
+
+#[test]
+fn test_overlapping_parts_of_multi_polygon() {
+    let polygons = MultiPolygon(vec![rect(0.0, 0.0, 2.0, 2.0), rect(1.0, 1.0, 3.0, 3.0)]);
+
+    assert_eq!(resolved_area(&polygons, FillRule::EvenOdd), 6.0);
+    assert_eq!(resolved_area(&polygons, FillRule::NonZero), 7.0);
+    assert_eq!(resolved_area(&polygons, FillRule::Positive), 7.0);
+    assert_eq!(resolved_area(&polygons, FillRule::Negative), 0.0);
+
+    let result = resolve(&polygons, FillRule::NonZero);
+    assert_eq!(result.0.len(), 1);
+    assert!(result.0[0].interiors().is_empty());
+}
+
+#[test]
+fn test_orientation_matters_for_signed_rules() {
+    let polygons = MultiPolygon(vec![reversed(rect(0.0, 0.0, 2.0, 2.0)), rect(1.0, 1.0, 3.0, 3.0)]);
+
+    assert_eq!(resolved_area(&polygons, FillRule::EvenOdd), 6.0);
+    assert_eq!(resolved_area(&polygons, FillRule::NonZero), 6.0);
+    assert_eq!(resolved_area(&polygons, FillRule::Positive), 3.0);
+    assert_eq!(resolved_area(&polygons, FillRule::Negative), 3.0);
+}
+
+#[test]
+fn test_ring_winding_twice() {
+    let ring = LineString(vec![
+        xy(0, 0),
+        xy(1, 0),
+        xy(1, 1),
+        xy(0, 1),
+        xy(0, 0),
+        xy(1, 0),
+        xy(1, 1),
+        xy(0, 1),
+        xy(0, 0),
+    ]);
+    let polygons = MultiPolygon(vec![Polygon::new(ring, vec![])]);
+
+    assert_eq!(resolved_area(&polygons, FillRule::EvenOdd), 0.0);
+    assert_eq!(resolved_area(&polygons, FillRule::NonZero), 1.0);
+}
+
+#[test]
+fn test_fill_rule_per_operand() {
+    let subject = MultiPolygon(vec![rect(0.0, 0.0, 2.0, 2.0), rect(1.0, 1.0, 3.0, 3.0)]);
+    let clipping = MultiPolygon(vec![rect(0.0, 0.0, 3.0, 3.0), rect(0.0, 0.0, 3.0, 3.0)]);
+
+    let intersection = |subject_rule, clipping_rule| {
+        subject
+            .boolean_with_fill_rules(
+                &clipping,
+                Operation::Intersection,
+                FillRules::new(subject_rule, clipping_rule),
+            )
+            .unsigned_area()
+    };
+
+    assert_eq!(intersection(FillRule::EvenOdd, FillRule::EvenOdd), 0.0);
+    assert_eq!(intersection(FillRule::NonZero, FillRule::EvenOdd), 0.0);
+    assert_eq!(intersection(FillRule::EvenOdd, FillRule::NonZero), 6.0);
+    assert_eq!(intersection(FillRule::NonZero, FillRule::NonZero), 7.0);
+}
+
+#[test]
+fn test_default_fill_rules_match_boolean() {
+    let a = MultiPolygon(vec![rect(0.0, 0.0, 2.0, 2.0), rect(1.0, 1.0, 3.0, 3.0)]);
+    let b = MultiPolygon(vec![rect(0.5, 0.5, 2.5, 1.5)]);
+
+    for operation in [
+        Operation::Intersection,
+        Operation::Union,
+        Operation::Difference,
+        Operation::Xor,
+    ] {
+        assert_eq!(
+            a.boolean_with_fill_rules(&b, operation, FillRules::default()),
+            a.boolean(&b, operation)
+        );
+    }
+}
+
+const FILL_RULES: [FillRule; 4] = [
+    FillRule::EvenOdd,
+    FillRule::NonZero,
+    FillRule::Positive,
+    FillRule::Negative,
+];
+
+const OPERATIONS: [Operation; 4] = [
+    Operation::Intersection,
+    Operation::Union,
+    Operation::Difference,
+    Operation::Xor,
+];
+
+/// Asserts that the operation succeeds and that the area of its result matches the area
+/// computed independently of the sweep.
+fn assert_matches_reference(
+    subject: &MultiPolygon<f64>,
+    clipping: &MultiPolygon<f64>,
+    operation: Operation,
+    fill_rules: FillRules,
+) {
+    let result = subject
+        .try_boolean_with_fill_rules(clipping, operation, fill_rules)
+        .unwrap_or_else(|err| {
+            panic!(
+                "{:?} for {:?} {:?} of {:?} and {:?}",
+                err, operation, fill_rules, subject, clipping
+            )
+        });
+    let expected = operation_area(&subject.0, &clipping.0, |subject_winding, clipping_winding| {
+        operation.is_in_result(
+            fill_rules.subject.is_inside(subject_winding),
+            fill_rules.clipping.is_inside(clipping_winding),
+        )
+    });
+
+    assert_no_crossings(&result);
+    assert!(
+        (result.unsigned_area() - expected).abs() <= 1e-9,
+        "area {} instead of {} for {:?} {:?} of {:?} and {:?}",
+        result.unsigned_area(),
+        expected,
+        operation,
+        fill_rules,
+        subject,
+        clipping
+    );
+}
+
+#[test]
+fn test_multi_polygons_with_shared_edges() {
+    for filename in [
+        "./fixtures/shared_edges.geojson",
+        "./fixtures/shared_edges_touching_vertex.geojson",
+    ] {
+        let (_, cells, other) = load_test_case(filename);
+        for subject_rule in FILL_RULES {
+            for clipping_rule in FILL_RULES {
+                let fill_rules = FillRules::new(subject_rule, clipping_rule);
+                for operation in OPERATIONS {
+                    assert_matches_reference(&cells, &other, operation, fill_rules);
+                    assert_matches_reference(&other, &cells, operation, fill_rules);
+                }
+            }
+        }
+    }
+
+    // The cell which does not overlap the triangle must not turn into a hole of the union.
+    let (_, cells, triangle) = load_test_case("./fixtures/shared_edges.geojson");
+    let union = cells.boolean_with_fill_rules(
+        &triangle,
+        Operation::Union,
+        FillRules::new(FillRule::NonZero, FillRule::NonZero),
+    );
+    assert_eq!(union.0.len(), 2);
+    assert!(union.0.iter().all(|polygon| polygon.interiors().is_empty()));
+}
+
+#[test]
+fn test_random_cells_match_reference_area() {
+    let mut rng: StdRng = SeedableRng::seed_from_u64(0);
+
+    for _ in 0..1000 {
+        // Cells of a grid share their edges with their neighbors.
+        let mut cells = Vec::new();
+        for i in 0..4 {
+            for j in 0..4 {
+                if rng.gen_bool(0.4) {
+                    let (x, y) = (i as f64 * 2.0 + 0.5, j as f64 * 2.0);
+                    let cell = rect(x, y, x + 2.0, y + 2.0);
+                    cells.push(if rng.gen_bool(0.3) { reversed(cell) } else { cell });
+                }
+            }
+        }
+        let cells = MultiPolygon(cells);
+        let mut ring: Vec<_> = (0..3)
+            .map(|_| xy(rng.gen_range(0..20) as f64 / 2.0, rng.gen_range(0..20) as f64 / 2.0))
+            .collect();
+        ring.push(ring[0]);
+        let triangle = MultiPolygon(vec![Polygon::new(LineString(ring), vec![])]);
+        if triangle.unsigned_area() == 0.0 {
+            continue;
+        }
+
+        let fill_rules = FillRules::new(FILL_RULES[rng.gen_range(0..4)], FILL_RULES[rng.gen_range(0..4)]);
+        let operation = OPERATIONS[rng.gen_range(0..4)];
+        assert_matches_reference(&cells, &triangle, operation, fill_rules);
+        assert_matches_reference(&triangle, &cells, operation, fill_rules);
+    }
+}
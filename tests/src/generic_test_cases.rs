@@ -3,9 +3,13 @@ use std::panic::catch_unwind;
 use std::thread::Result;
 
 use geo::MultiPolygon;
+use geojson::{Geometry, Value};
 
 use super::compact_geojson::write_compact_geojson;
-use super::helper::{apply_operation, convert_to_feature, extract_expected_result, load_test_case, TestOperation};
+use super::helper::{
+    apply_operation, convert_to_feature, extract_expected_result, extract_multi_polygon, load_test_case, TestOperation,
+    EXACT_RESULT_PROPERTY,
+};
 
 #[derive(Debug)]
 enum ResultTag {
@@ -27,6 +31,7 @@ fn compute_all_results(
     });
 
     let mut results = vec![(ResultTag::MainResult, main_result)];
+    #[allow(clippy::match_like_matches_macro)]
     let swappable_op = match op {
         TestOperation::DifferenceAB => false,
         TestOperation::DifferenceBA => false,
@@ -79,9 +84,24 @@ fn run_generic_test_case(filename: &str, regenerate: bool) -> Vec<String> {
 
         if regenerate {
             if let Result::Ok(result) = &all_results.first().expect("Need at least one result").1 {
-                let mut new_feature = convert_to_feature(result, Some(op));
-                new_feature.properties = feature.properties.clone(); // Copy existing properties to keep comments etc.
-                output_features.push(new_feature);
+                if cfg!(feature = "exact-intersection") {
+                    // Keep the result of the floating point arithmetic, and store the exact one
+                    // only if it differs.
+                    let mut new_feature = feature.clone();
+                    let properties = new_feature.properties.get_or_insert_with(Default::default);
+                    properties.remove(EXACT_RESULT_PROPERTY);
+                    if *result != extract_multi_polygon(feature) {
+                        properties.insert(
+                            EXACT_RESULT_PROPERTY.to_string(),
+                            serde_json::to_value(Geometry::new(Value::from(result))).unwrap(),
+                        );
+                    }
+                    output_features.push(new_feature);
+                } else {
+                    let mut new_feature = convert_to_feature(result, Some(op));
+                    new_feature.properties = feature.properties.clone(); // Copy existing properties to keep comments etc.
+                    output_features.push(new_feature);
+                }
             }
         }
     }
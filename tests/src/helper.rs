@@ -1,6 +1,7 @@
-use geo_booleanop::boolean::BooleanOp;
+use geo_booleanop::boolean::{BooleanOp, FillRule};
 
-use geo::{Coord, MultiPolygon, Polygon};
+use geo::line_intersection::{line_intersection, LineIntersection};
+use geo::{Coord, Line, MultiPolygon, Polygon};
 
 use geojson::{Feature, GeoJson, Geometry, Value};
 use serde_json::{json, Map};
@@ -23,6 +24,86 @@ pub fn xy<X: Into<f64>, Y: Into<f64>>(x: X, y: Y) -> Coord<f64> {
     }
 }
 
+/// Asserts that no two edges of the result cross or overlap. Touching in single points
+/// is allowed.
+pub fn assert_no_crossings(multi_polygon: &MultiPolygon<f64>) {
+    let lines: Vec<Line<f64>> = multi_polygon
+        .0
+        .iter()
+        .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+        .flat_map(|ring| ring.lines())
+        .collect();
+    for (i, a) in lines.iter().enumerate() {
+        for b in &lines[i + 1..] {
+            match line_intersection(*a, *b) {
+                None | Some(LineIntersection::SinglePoint { is_proper: false, .. }) => {}
+                Some(intersection) => panic!("{:?} and {:?} intersect in {:?}", a, b, intersection),
+            }
+        }
+    }
+}
+
+/// Area of the region which is inside of the rings according to the fill rule, computed
+/// independently of the sweep, see `operation_area`.
+pub fn fill_rule_area(polygons: &[Polygon<f64>], fill_rule: FillRule) -> f64 {
+    operation_area(polygons, &[], |winding, _| fill_rule.is_inside(winding))
+}
+
+/// Area of the region whose winding numbers with respect to the `subject` and `clipping`
+/// rings satisfy `is_inside`, computed independently of the sweep by decomposing the plane
+/// into vertical slabs without any vertex or crossing in their interior. Counter-clockwise
+/// rings have a positive winding.
+pub fn operation_area<I>(subject: &[Polygon<f64>], clipping: &[Polygon<f64>], is_inside: I) -> f64
+where
+    I: Fn(i32, i32) -> bool,
+{
+    let operand_lines = |polygons: &[Polygon<f64>], operand: usize| -> Vec<(Line<f64>, usize)> {
+        polygons
+            .iter()
+            .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+            .flat_map(|ring| ring.lines())
+            .filter(|line| line.start.x != line.end.x)
+            .map(|line| (line, operand))
+            .collect()
+    };
+    let mut lines = operand_lines(subject, 0);
+    lines.extend(operand_lines(clipping, 1));
+
+    let mut xs: Vec<f64> = lines.iter().flat_map(|(line, _)| [line.start.x, line.end.x]).collect();
+    for (i, (a, _)) in lines.iter().enumerate() {
+        for (b, _) in &lines[i + 1..] {
+            if let Some(LineIntersection::SinglePoint { intersection, .. }) = line_intersection(*a, *b) {
+                xs.push(intersection.x);
+            }
+        }
+    }
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    xs.dedup();
+
+    let y_at = |line: &Line<f64>, x: f64| line.start.y + (line.end.y - line.start.y) * (x - line.start.x) / line.dx();
+    let mut area = 0.0;
+    for slab in xs.windows(2) {
+        let (x0, x1) = (slab[0], slab[1]);
+        let x_mid = (x0 + x1) / 2.0;
+        let mut crossing: Vec<&(Line<f64>, usize)> = lines
+            .iter()
+            .filter(|(line, _)| line.start.x.min(line.end.x) <= x0 && line.start.x.max(line.end.x) >= x1)
+            .collect();
+        crossing.sort_by(|(a, _), (b, _)| y_at(a, x_mid).partial_cmp(&y_at(b, x_mid)).unwrap());
+
+        let mut windings = [0, 0];
+        for pair in crossing.windows(2) {
+            let (line, operand) = pair[0];
+            windings[*operand] += if line.dx() > 0.0 { 1 } else { -1 };
+            if is_inside(windings[0], windings[1]) {
+                let height = |x| y_at(&pair[1].0, x) - y_at(&pair[0].0, x);
+                area += (x1 - x0) * (height(x0) + height(x1)) / 2.0;
+            }
+        }
+    }
+    area
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum TestOperation {
     Intersection,
@@ -97,9 +178,16 @@ pub fn load_test_case(filename: &str) -> (Vec<Feature>, MultiPolygon<f64>, Multi
 // JSON <=> geo type conversion helpers
 // ----------------------------------------------------------------------------
 
+/// Property of a result feature holding the result with the `exact-intersection` feature, if it
+/// differs from the one computed in floating point arithmetic.
+/// Its vertices are derived independently of the library in `exact_intersection_test`.
+pub const EXACT_RESULT_PROPERTY: &str = "exact_intersection_result";
+
 #[derive(Debug)]
 pub struct ExpectedResult {
     pub result: MultiPolygon<f64>,
+    /// The result with exact intersections, independent of the `exact-intersection` feature.
+    pub exact_result: MultiPolygon<f64>,
     pub op: TestOperation,
     pub swap_ab_is_broken: bool,
 }
@@ -143,8 +231,22 @@ pub fn extract_expected_result(feature: &Feature) -> ExpectedResult {
         .map(|x| x.as_bool().expect("swap_ab_is_broken must be a boolean"))
         .unwrap_or(false);
 
+    let result = extract_multi_polygon(feature);
+    let exact_result = properties
+        .get(EXACT_RESULT_PROPERTY)
+        .map(|x| {
+            let geometry = Geometry::from_json_value(x.clone()).expect("exact result must be a geometry");
+            extract_multi_polygon(&Feature::from(geometry))
+        })
+        .unwrap_or_else(|| result.clone());
+
     ExpectedResult {
-        result: extract_multi_polygon(feature),
+        result: if cfg!(feature = "exact-intersection") {
+            exact_result.clone()
+        } else {
+            result
+        },
+        exact_result,
         op,
         swap_ab_is_broken,
     }
@@ -177,6 +279,7 @@ pub fn convert_to_feature(p: &MultiPolygon<f64>, operation: Option<TestOperation
 // ----------------------------------------------------------------------------
 
 /// Wrapper around the Python plotting script to visualize test cases.
+#[allow(clippy::zombie_processes)] // The plot window is meant to outlive the caller
 pub fn plot_generic_test_case(test_case_file: &str) {
     // Try to run Python plot
     let script_path = Path::new(file!()).to_path_buf()
@@ -0,0 +1,160 @@
+use super::data_generators::{generate_grid, generate_random_triangles};
+use super::helper::assert_no_crossings;
+use geo::{polygon, Area, Coord, CoordNum, LineString, MultiPolygon, Polygon};
+use geo_booleanop::boolean::{
+    BooleanOp, BooleanOpError, FillRules, IntegerCoord, Operation, PrecisionModel, MAX_INTEGER_COORD,
+};
+
+const OPERATIONS: [Operation; 4] = [
+    Operation::Intersection,
+    Operation::Union,
+    Operation::Difference,
+    Operation::Xor,
+];
+
+fn map_coords<A, B>(multi_polygon: &MultiPolygon<A>, f: impl Fn(A) -> B + Copy) -> MultiPolygon<B>
+where
+    A: CoordNum,
+    B: CoordNum,
+{
+    let map_ring = |ring: &LineString<A>| {
+        ring.0
+            .iter()
+            .map(|c| Coord { x: f(c.x), y: f(c.y) })
+            .collect::<LineString<B>>()
+    };
+    MultiPolygon(
+        multi_polygon
+            .0
+            .iter()
+            .map(|p| Polygon::new(map_ring(p.exterior()), p.interiors().iter().map(map_ring).collect()))
+            .collect(),
+    )
+}
+
+fn to_float<T: IntegerCoord>(multi_polygon: &MultiPolygon<T>) -> MultiPolygon<f64> {
+    map_coords(multi_polygon, |v| Into::<i64>::into(v) as f64)
+}
+
+fn scaled_to_integer(multi_polygon: &MultiPolygon<f64>, scale: f64) -> MultiPolygon<i64> {
+    map_coords(multi_polygon, |v| (v * scale).round() as i64)
+}
+
+#[test]
+fn test_grid_matches_float_operation() {
+    // All intersections of the grids are integer points, so snap rounding does not move them.
+    let subject = generate_grid(0.0, 36.0, 6.0, 7);
+    let clipping = generate_grid(-2.0, 38.0, 8.0, 5);
+    let int_subject = scaled_to_integer(&subject, 1.0);
+    let int_clipping = scaled_to_integer(&clipping, 1.0);
+    let small_subject = map_coords(&int_subject, |v| v as i32);
+    let small_clipping = map_coords(&int_clipping, |v| v as i32);
+
+    for operation in OPERATIONS {
+        let expected = subject.boolean(&clipping, operation);
+        assert_eq!(to_float(&int_subject.boolean(&int_clipping, operation)), expected);
+        assert_eq!(to_float(&small_subject.boolean(&small_clipping, operation)), expected);
+    }
+}
+
+#[test]
+fn test_random_triangles_are_valid() {
+    let scale = 1000.0;
+    let subject = scaled_to_integer(&generate_random_triangles(10, 1), scale);
+    let clipping = scaled_to_integer(&generate_random_triangles(10, 2), scale);
+
+    for operation in OPERATIONS {
+        let result = subject.boolean(&clipping, operation);
+        let float_result = to_float(&result);
+        assert_no_crossings(&float_result);
+
+        // Snap rounding moves every edge by less than one unit, which changes the area only
+        // slightly compared to the operation on the unrounded coordinates.
+        let exact = to_float(&subject)
+            .boolean(&to_float(&clipping), operation)
+            .unsigned_area();
+        let area = float_result.unsigned_area();
+        assert!((area - exact).abs() < 0.01 * exact, "{} vs {}", area, exact);
+    }
+}
+
+#[test]
+fn test_all_operations_and_division_agree() {
+    let subject = scaled_to_integer(&generate_random_triangles(6, 3), 100.0);
+    let clipping = scaled_to_integer(&generate_random_triangles(6, 4), 100.0);
+
+    let results = subject.boolean_all(&clipping);
+    assert_eq!(results.intersection, subject.intersection(&clipping));
+    assert_eq!(results.union, subject.union(&clipping));
+    assert_eq!(results.difference, subject.difference(&clipping));
+    assert_eq!(results.xor, subject.xor(&clipping));
+
+    let division = subject.divide(&clipping);
+    assert_eq!(division.subject_only, results.difference);
+    assert_eq!(division.both, results.intersection);
+}
+
+#[test]
+fn test_traced_sources_refer_to_input_edges() {
+    let subject = scaled_to_integer(&generate_random_triangles(6, 5), 100.0);
+    let clipping = scaled_to_integer(&generate_random_triangles(6, 6), 100.0);
+
+    for operation in OPERATIONS {
+        let traced = subject.boolean_traced(&clipping, operation);
+        assert_eq!(traced.multi_polygon, subject.boolean(&clipping, operation));
+        for (polygon, sources) in traced.multi_polygon.0.iter().zip(&traced.sources) {
+            let rings: Vec<_> = std::iter::once(polygon.exterior()).chain(polygon.interiors()).collect();
+            assert_eq!(rings.len(), sources.len());
+            for (ring, ring_sources) in rings.iter().zip(sources) {
+                assert_eq!(ring.lines().count(), ring_sources.len());
+                for source in ring_sources {
+                    let operand = if source.operand == 0 { &subject } else { &clipping };
+                    assert_eq!(source.ring, 0);
+                    assert!(source.edge < operand.0[source.polygon].exterior().lines().count());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_large_coordinates() {
+    let m = MAX_INTEGER_COORD;
+    let subject = polygon![(x: -m, y: -m), (x: m, y: m - 1), (x: -m, y: m), (x: -m, y: -m)];
+    let clipping = polygon![(x: m, y: -m), (x: m, y: m), (x: -m, y: m - 2), (x: m, y: -m)];
+
+    let result = subject.intersection(&clipping);
+    assert_eq!(result.0.len(), 1);
+    assert!(result.0[0]
+        .exterior()
+        .0
+        .iter()
+        .all(|c| c.x.abs() <= m && c.y.abs() <= m));
+    assert_no_crossings(&to_float(&subject.xor(&clipping)));
+
+    let outside = polygon![(x: 0, y: 0), (x: m + 1, y: 0), (x: 0, y: 1), (x: 0, y: 0)];
+    assert_eq!(subject.try_union(&outside), Err(BooleanOpError::CoordinateOutOfRange));
+}
+
+#[test]
+fn test_precision_model() {
+    let subject = polygon![(x: 0, y: 0), (x: 4, y: 0), (x: 4, y: 4), (x: 0, y: 0)];
+    let clipping = polygon![(x: 0, y: 3), (x: 3, y: 0), (x: 4, y: 3), (x: 0, y: 3)];
+    let expected = subject.union(&clipping);
+
+    for precision in [PrecisionModel::Floating, PrecisionModel::Fixed { scale: 10 }] {
+        assert_eq!(
+            subject.boolean_with_precision(&clipping, Operation::Union, FillRules::default(), precision),
+            expected
+        );
+    }
+    assert_eq!(
+        subject.try_boolean_with_precision(
+            &clipping,
+            Operation::Union,
+            FillRules::default(),
+            PrecisionModel::Fixed { scale: 0 }
+        ),
+        Err(BooleanOpError::InvalidPrecisionModel)
+    );
+}
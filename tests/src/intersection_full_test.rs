@@ -0,0 +1,55 @@
+use glob::glob;
+
+use super::helper::load_test_case;
+use geo::{line_string, point, polygon, Geometry, MultiPolygon, Polygon};
+use geo_booleanop::boolean::BooleanOp;
+
+#[test]
+fn test_generic_test_cases() {
+    // The polygons of the full intersection are the regular intersection.
+    for entry in glob("./fixtures/generic_test_cases/*.geojson").expect("Failed to read glob pattern") {
+        let filename = entry.unwrap().to_str().unwrap().to_string();
+        println!("Running test case: {}", filename);
+        let (_, p1, p2) = load_test_case(&filename);
+
+        let result = p1.intersection_full(&p2);
+        let polygons: Vec<Polygon<f64>> = result
+            .0
+            .iter()
+            .filter_map(|geometry| match geometry {
+                Geometry::Polygon(polygon) => Some(polygon.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(MultiPolygon(polygons), p1.intersection(&p2), "{}", filename);
+    }
+}
+
+#[test]
+fn test_adjacent_parcels() {
+    let parcel = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)];
+    let neighbors = MultiPolygon(vec![
+        polygon![(x: 2., y: 0.), (x: 4., y: 0.), (x: 4., y: 1.), (x: 2., y: 1.), (x: 2., y: 0.)],
+        polygon![(x: 2., y: 2.), (x: 3., y: 2.), (x: 3., y: 3.), (x: 2., y: 3.), (x: 2., y: 2.)],
+    ]);
+
+    assert!(parcel.intersection(&neighbors).0.is_empty());
+    assert_eq!(
+        parcel.intersection_full(&neighbors).0,
+        vec![
+            Geometry::LineString(line_string![(x: 2., y: 0.), (x: 2., y: 1.)]),
+            Geometry::Point(point!(x: 2., y: 2.)),
+        ]
+    );
+}
+
+#[test]
+fn test_integer_coordinates() {
+    let a: Polygon<i32> = polygon![(x: 0, y: 0), (x: 10, y: 0), (x: 10, y: 10), (x: 0, y: 10), (x: 0, y: 0)];
+    let b: Polygon<i32> = polygon![(x: 10, y: 5), (x: 20, y: 5), (x: 20, y: 20), (x: 10, y: 20), (x: 10, y: 5)];
+
+    assert_eq!(
+        a.intersection_full(&b).0,
+        vec![Geometry::LineString(line_string![(x: 10, y: 5), (x: 10, y: 10)])]
+    );
+}
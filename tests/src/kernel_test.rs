@@ -0,0 +1,117 @@
+use super::data_generators::{generate_grid, generate_random_triangles_polygons};
+use super::helper::{extract_expected_result, load_test_case, xy, TestOperation};
+use geo::{MultiPolygon, Polygon};
+use geo_booleanop::boolean::{
+    try_boolean_with_kernel, BooleanOp, BooleanOpError, DefaultKernel, FastKernel, FillRules, Kernel, Operation,
+    RobustKernel,
+};
+use glob::glob;
+
+const OPERATIONS: [Operation; 4] = [
+    Operation::Intersection,
+    Operation::Union,
+    Operation::Difference,
+    Operation::Xor,
+];
+
+fn apply_operation_with_kernel<K: Kernel<f64>>(
+    p1: &MultiPolygon<f64>,
+    p2: &MultiPolygon<f64>,
+    op: TestOperation,
+) -> MultiPolygon<f64> {
+    let (subject, clipping, operation) = match op {
+        TestOperation::Intersection => (p1, p2, Operation::Intersection),
+        TestOperation::Union => (p1, p2, Operation::Union),
+        TestOperation::Xor => (p1, p2, Operation::Xor),
+        TestOperation::DifferenceAB => (p1, p2, Operation::Difference),
+        TestOperation::DifferenceBA => (p2, p1, Operation::Difference),
+    };
+    try_boolean_with_kernel::<f64, K>(&subject.0, &clipping.0, operation, FillRules::default()).unwrap()
+}
+
+fn check_same_as_default<K: Kernel<f64>>(p1: &MultiPolygon<f64>, p2: &MultiPolygon<f64>, name: &str) {
+    for operation in OPERATIONS {
+        assert_eq!(
+            try_boolean_with_kernel::<f64, K>(&p1.0, &p2.0, operation, FillRules::default()),
+            p1.try_boolean(p2, operation),
+            "{} / {:?}",
+            name,
+            operation
+        );
+    }
+}
+
+#[test]
+fn test_robust_kernel_matches_exact_results() {
+    let test_cases: Vec<_> = glob("./fixtures/generic_test_cases/*.geojson")
+        .expect("Failed to read glob pattern")
+        .collect();
+    assert!(!test_cases.is_empty(), "Expected to find any test cases");
+
+    for entry in &test_cases {
+        let filename = entry.as_ref().expect("Valid glob entry").to_str().unwrap();
+        let (features, p1, p2) = load_test_case(filename);
+        for feature in features.iter().skip(2) {
+            let expected = extract_expected_result(feature);
+            let result = apply_operation_with_kernel::<RobustKernel>(&p1, &p2, expected.op);
+            assert_eq!(result, expected.exact_result, "{} / {:?}", filename, expected.op);
+        }
+    }
+}
+
+#[test]
+fn test_default_kernel_matches_boolean_op() {
+    let (p1, p2) = generate_random_triangles_polygons();
+    check_same_as_default::<DefaultKernel>(&p1, &p2, "random_triangles");
+}
+
+#[test]
+fn test_fast_kernel_on_exact_input() {
+    // The coordinates and intersections of the grids are small integers, so the floating
+    // point predicates of the fast kernel are exact.
+    let p1 = generate_grid(0.0, 36.0, 6.0, 7);
+    let p2 = generate_grid(-2.0, 38.0, 8.0, 5);
+    check_same_as_default::<FastKernel>(&p1, &p2, "grid");
+    check_same_as_default::<RobustKernel>(&p1, &p2, "grid");
+}
+
+#[test]
+fn test_fast_kernel_reports_contradicting_comparisons() {
+    // The inexact orientations of the fast kernel order two of the segments starting at
+    // (0, 2) below each other, which used to make walks along the sweep line loop forever.
+    let ring = |points: &[(f64, f64)]| Polygon::new(points.iter().map(|&(x, y)| xy(x, y)).collect(), vec![]);
+    let p1 = MultiPolygon(vec![
+        ring(&[
+            (0.999999999999999, 1.0),
+            (4.000000000000001, 1.000000000000001),
+            (2.999999999999999, -1e-15),
+            (6.000000000000001, 1.999999999999999),
+            (3.0, 7.0),
+            (5.999999999999999, 1.999999999999999),
+            (-1e-15, 2.0),
+            (0.999999999999999, 1.0),
+        ]),
+        ring(&[
+            (4.999999999999999, -1e-15),
+            (3.999999999999999, 1.000000000000001),
+            (0.999999999999999, 2.999999999999999),
+            (7.000000000000001, 1.999999999999999),
+            (0.0, 2.0),
+            (4.999999999999999, -1e-15),
+        ]),
+    ]);
+    let p2 = MultiPolygon(vec![ring(&[
+        (5.000000000000001, 1.000000000000001),
+        (3.0, 1.000000000000001),
+        (2.0, 4.000000000000001),
+        (1.0, 1.000000000000001),
+        (0.999999999999999, 1e-15),
+        (5.0, 5.999999999999999),
+        (5.000000000000001, 1.000000000000001),
+    ])]);
+
+    assert_eq!(
+        try_boolean_with_kernel::<f64, FastKernel>(&p1.0, &p2.0, Operation::Xor, FillRules::default()),
+        Err(BooleanOpError::SweepLineInconsistency)
+    );
+}
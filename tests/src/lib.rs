@@ -1,11 +1,51 @@
 #[cfg(test)]
+mod boolean_all_test;
+#[cfg(test)]
+mod custom_operation_test;
+#[cfg(test)]
+mod divide_test;
+#[cfg(test)]
+mod exact_intersection_test;
+#[cfg(test)]
+mod expr_test;
+#[cfg(test)]
 mod fill_queue_test;
 #[cfg(test)]
+mod fill_rule_test;
+#[cfg(test)]
 mod generic_test_cases;
 #[cfg(test)]
+mod integer_test;
+#[cfg(test)]
+mod intersection_full_test;
+#[cfg(test)]
+mod kernel_test;
+#[cfg(test)]
+mod line_clip_test;
+#[cfg(test)]
+mod make_valid_test;
+#[cfg(test)]
+mod parallel_test;
+#[cfg(test)]
+mod payload_test;
+#[cfg(test)]
 mod possible_intersection_test;
 #[cfg(test)]
+mod precision_test;
+#[cfg(test)]
+mod provenance_test;
+#[cfg(test)]
+mod rational_test;
+#[cfg(test)]
 mod subdivide_segments_test;
+#[cfg(test)]
+mod sweep_line_test;
+#[cfg(test)]
+mod try_boolean_test;
+#[cfg(test)]
+mod unary_union_test;
+#[cfg(test)]
+mod vertex_values_test;
 
 pub mod compact_geojson;
 pub mod data_generators;
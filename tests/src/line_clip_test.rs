@@ -0,0 +1,127 @@
+use super::data_generators::generate_nested_rects;
+use super::helper::xy;
+use geo::{EuclideanLength, LineString, MultiLineString, MultiPolygon, Polygon};
+use geo_booleanop::boolean::{BooleanOpError, BoundaryPolicy, LineClip};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn rect(x1: f64, y1: f64, x2: f64, y2: f64) -> Polygon<f64> {
+    Polygon::new(
+        LineString(vec![xy(x1, y1), xy(x2, y1), xy(x2, y2), xy(x1, y2), xy(x1, y1)]),
+        vec![],
+    )
+}
+
+fn assert_length_eq(a: f64, b: f64) {
+    assert!(
+        (a - b).abs() <= 1e-9 * a.abs().max(1.0),
+        "lengths differ: {} vs {}",
+        a,
+        b
+    );
+}
+
+#[test]
+fn test_clip_against_polygon_with_hole() {
+    let polygons = generate_nested_rects(xy(0, 0), 2.0, 4.0, 1);
+    let line = LineString(vec![xy(-3, 0), xy(3, 0)]);
+
+    let result = line.clip(&polygons, BoundaryPolicy::Inside);
+
+    assert_eq!(
+        result.inside,
+        MultiLineString(vec![
+            LineString(vec![xy(-2, 0), xy(-1, 0)]),
+            LineString(vec![xy(1, 0), xy(2, 0)]),
+        ])
+    );
+    assert_eq!(
+        result.outside,
+        MultiLineString(vec![
+            LineString(vec![xy(-3, 0), xy(-2, 0)]),
+            LineString(vec![xy(-1, 0), xy(1, 0)]),
+            LineString(vec![xy(2, 0), xy(3, 0)]),
+        ])
+    );
+}
+
+#[test]
+fn test_shared_edge_is_not_boundary() {
+    let polygons = MultiPolygon(vec![rect(0.0, 0.0, 1.0, 1.0), rect(1.0, 0.0, 2.0, 1.0)]);
+    let line = LineString(vec![xy(1, -1), xy(1, 2)]);
+
+    let result = line.clip(&polygons, BoundaryPolicy::Discard);
+
+    assert_eq!(
+        result.inside,
+        MultiLineString(vec![LineString(vec![xy(1, 0), xy(1, 1)])])
+    );
+    assert_eq!(
+        result.outside,
+        MultiLineString(vec![
+            LineString(vec![xy(1, -1), xy(1, 0)]),
+            LineString(vec![xy(1, 1), xy(1, 2)]),
+        ])
+    );
+}
+
+#[test]
+fn test_self_overlapping_lines() {
+    let polygon = rect(0.0, 0.0, 2.0, 2.0);
+    let lines = MultiLineString(vec![
+        LineString(vec![xy(-1, 1), xy(1, 1)]),
+        LineString(vec![xy(3, 1), xy(1, 1), xy(1, 0)]),
+    ]);
+
+    let result = lines.clip(&polygon, BoundaryPolicy::Outside);
+
+    assert_eq!(
+        result.inside,
+        MultiLineString(vec![
+            LineString(vec![xy(0, 1), xy(1, 1)]),
+            LineString(vec![xy(2, 1), xy(1, 1), xy(1, 0)]),
+        ])
+    );
+    assert_eq!(
+        result.outside,
+        MultiLineString(vec![
+            LineString(vec![xy(-1, 1), xy(0, 1)]),
+            LineString(vec![xy(3, 1), xy(2, 1)]),
+        ])
+    );
+}
+
+#[test]
+fn test_length_is_preserved() {
+    let polygons = generate_nested_rects(xy(0, 0), 0.5, 1.5, 2);
+    let mut rng: StdRng = SeedableRng::seed_from_u64(0);
+    let lines = MultiLineString(
+        (0..20)
+            .map(|_| {
+                LineString(
+                    (0..10)
+                        .map(|_| xy(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+                        .collect(),
+                )
+            })
+            .collect(),
+    );
+
+    let result = lines.clip(&polygons, BoundaryPolicy::Inside);
+
+    assert_length_eq(
+        result.inside.euclidean_length() + result.outside.euclidean_length(),
+        lines.euclidean_length(),
+    );
+}
+
+#[test]
+fn test_non_finite_coordinates() {
+    let line = LineString(vec![xy(0, 0), xy(f64::NAN, 1.0)]);
+
+    assert_eq!(
+        line.try_clip(&rect(0.0, 0.0, 1.0, 1.0), BoundaryPolicy::Inside),
+        Err(BooleanOpError::NonFiniteCoordinate)
+    );
+}
@@ -0,0 +1,125 @@
+use super::helper::{assert_no_crossings, fill_rule_area, load_test_case, xy};
+use geo::{Area, LineString, MultiPolygon, Polygon};
+use geo_booleanop::boolean::{BooleanOpError, FillRule, MakeValid};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn test_crossing_shell_and_hole() {
+    let (_, _, p2) = load_test_case("./fixtures/generic_test_cases/daef_cross_selfintersecting.geojson");
+
+    for (fill_rule, expected_area) in [
+        (FillRule::EvenOdd, 28125.0),
+        (FillRule::NonZero, 28125.0),
+        (FillRule::Positive, 19687.5),
+        (FillRule::Negative, 8437.5),
+    ] {
+        let result = p2.make_valid_with_fill_rule(fill_rule);
+
+        assert_no_crossings(&result);
+        assert_eq!(result.unsigned_area(), expected_area, "area for {:?}", fill_rule);
+    }
+}
+
+#[test]
+fn test_touching_rings() {
+    let (_, p1, _) = load_test_case("./fixtures/generic_test_cases/daef_cross_selfintersecting.geojson");
+
+    // The "holes" of the input touch the shell from the outside, so they turn into
+    // separate polygons.
+    let result = p1.make_valid();
+
+    assert_no_crossings(&result);
+    assert_eq!(result.0.len(), 4);
+    assert_eq!(result.unsigned_area(), 30000.0);
+}
+
+#[test]
+fn test_valid_polygon_is_unchanged() {
+    let polygon = Polygon::new(
+        LineString(vec![xy(0, 0), xy(4, 0), xy(4, 4), xy(0, 4), xy(0, 0)]),
+        vec![LineString(vec![xy(1, 1), xy(1, 3), xy(3, 3), xy(3, 1), xy(1, 1)])],
+    );
+
+    let result = polygon.make_valid();
+
+    assert_eq!(result.0.len(), 1);
+    assert_eq!(result.0[0].exterior(), polygon.exterior());
+    assert_eq!(result.0[0].interiors().len(), 1);
+    assert_eq!(result.unsigned_area(), polygon.unsigned_area());
+}
+
+#[test]
+fn test_non_finite_coordinates() {
+    let polygon = Polygon::new(LineString(vec![xy(0, 0), xy(1, 0), xy(0, f64::NAN), xy(0, 0)]), vec![]);
+
+    assert_eq!(polygon.try_make_valid(), Err(BooleanOpError::NonFiniteCoordinate));
+}
+
+#[test]
+fn test_self_overlapping_rectangles() {
+    let rect = |x1: i32, y1: i32, x2: i32, y2: i32| {
+        Polygon::new(
+            LineString(vec![xy(x1, y1), xy(x2, y1), xy(x2, y2), xy(x1, y2), xy(x1, y1)]),
+            vec![],
+        )
+    };
+    let rects = MultiPolygon(vec![rect(3, 3, 5, 5), rect(1, 3, 3, 6), rect(3, 4, 5, 5)]);
+
+    let result = rects.try_make_valid_with_fill_rule(FillRule::NonZero).unwrap();
+
+    assert_no_crossings(&result);
+    assert_eq!(result.unsigned_area(), 10.0);
+}
+
+#[test]
+fn test_self_intersecting_rings() {
+    for ring in [
+        vec![xy(2, 2), xy(0, 3), xy(4, 1), xy(3, 2), xy(1, 2), xy(4, 2), xy(2, 2)],
+        vec![xy(3, 5), xy(4, 1), xy(3, 5), xy(4, 4), xy(0, 0), xy(2, 1), xy(3, 5)],
+    ] {
+        let polygon = Polygon::new(LineString(ring), vec![]);
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            let result = polygon.try_make_valid_with_fill_rule(fill_rule).unwrap();
+
+            assert_no_crossings(&result);
+            assert_eq!(
+                result.unsigned_area(),
+                fill_rule_area(std::slice::from_ref(&polygon), fill_rule),
+                "area for {:?} of {:?}",
+                fill_rule,
+                polygon
+            );
+        }
+    }
+}
+
+#[test]
+fn test_random_rings_match_reference_area() {
+    let mut rng: StdRng = SeedableRng::seed_from_u64(0);
+
+    for _ in 0..2000 {
+        let num_vertices = rng.gen_range(4..=6);
+        let mut ring: Vec<_> = (0..num_vertices)
+            .map(|_| xy(rng.gen_range(0..6), rng.gen_range(0..6)))
+            .collect();
+        ring.push(ring[0]);
+        let polygon = Polygon::new(LineString(ring), vec![]);
+
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            let result = polygon
+                .try_make_valid_with_fill_rule(fill_rule)
+                .unwrap_or_else(|err| panic!("{:?} for {:?} of {:?}", err, fill_rule, polygon));
+            let expected = fill_rule_area(std::slice::from_ref(&polygon), fill_rule);
+
+            assert!(
+                (result.unsigned_area() - expected).abs() <= 1e-9,
+                "area {} instead of {} for {:?} of {:?}",
+                result.unsigned_area(),
+                expected,
+                fill_rule,
+                polygon
+            );
+        }
+    }
+}
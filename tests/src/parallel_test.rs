@@ -0,0 +1,83 @@
+use super::data_generators::{generate_grid, generate_random_triangles};
+use geo::{Area, Polygon};
+use geo_booleanop::boolean::{
+    par_boolean_many, par_intersection_many, par_unary_union, try_par_boolean_many, unary_union, BooleanOp, Operation,
+};
+
+fn assert_area_eq(a: f64, b: f64) {
+    assert!((a - b).abs() <= 1e-9 * a.abs().max(1.0), "areas differ: {} vs {}", a, b);
+}
+
+#[test]
+fn test_operands_and_results_are_shared_across_threads() {
+    let subject = generate_grid(0.0, 9.0, 1.5, 10);
+    let clips = generate_grid(-0.5, 10.5, 2.0, 5);
+
+    // The operands are borrowed by the threads, and the results are returned to this one.
+    let subject = &subject;
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = clips
+            .0
+            .iter()
+            .map(|clip| scope.spawn(move || subject.try_boolean_all(clip)))
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    for (result, clip) in results.iter().zip(&clips.0) {
+        let result = result.as_ref().unwrap();
+        // Results computed by another thread are read by several threads at once.
+        std::thread::scope(|scope| {
+            scope.spawn(|| assert_eq!(result.intersection, subject.intersection(clip)));
+            scope.spawn(|| assert_eq!(result.difference, subject.difference(clip)));
+        });
+    }
+}
+
+#[test]
+fn test_par_intersection_many_matches_sequential() {
+    let subject = generate_grid(0.0, 9.0, 1.5, 10);
+    let clips = generate_grid(-0.5, 10.5, 2.0, 5);
+
+    let results = par_intersection_many(&subject, &clips.0);
+
+    assert_eq!(results.len(), clips.0.len());
+    for (result, clip) in results.iter().zip(&clips.0) {
+        assert_eq!(result, &subject.intersection(clip));
+    }
+}
+
+#[test]
+fn test_par_boolean_many_with_polygon_subject() {
+    let subject: Polygon<f64> = generate_grid(0.0, 4.0, 4.0, 2).0.remove(0);
+    let clips = generate_grid(-2.0, 2.0, 3.0, 3);
+
+    let differences = par_boolean_many(&subject, &clips.0, Operation::Difference);
+    let fallible = try_par_boolean_many(&subject, &clips.0, Operation::Difference);
+
+    for ((difference, result), clip) in differences.iter().zip(fallible).zip(&clips.0) {
+        assert_eq!(difference, &subject.difference(clip));
+        assert_eq!(result.unwrap(), *difference);
+    }
+}
+
+#[test]
+fn test_par_unary_union_matches_single_sweep() {
+    let grid = generate_grid(0.0, 19.0, 1.5, 20);
+
+    let result = par_unary_union(&grid.0);
+
+    assert_eq!(result.0.len(), 1);
+    assert!(result.0[0].interiors().is_empty());
+    assert_area_eq(result.unsigned_area(), 20.5 * 20.5);
+    assert_area_eq(result.unsigned_area(), unary_union(&grid.0).unsigned_area());
+}
+
+#[test]
+fn test_par_unary_union_of_random_triangles() {
+    let triangles = generate_random_triangles(100, 0);
+
+    let result = par_unary_union(&triangles.0);
+
+    assert_area_eq(result.unsigned_area(), unary_union(&triangles.0).unsigned_area());
+}
@@ -0,0 +1,97 @@
+use glob::glob;
+
+use super::helper::load_test_case;
+use geo::{Coord, MultiPolygon};
+use geo_booleanop::boolean::{
+    try_boolean_with_payload, BooleanOp, FillRules, Operation, PayloadSegment, ValuedMultiPolygon, VertexPayload,
+};
+
+/// Payload recording the input vertex or the crossing a vertex comes from.
+#[derive(Clone, PartialEq, Debug)]
+enum Origin {
+    Input(Coord<f64>),
+    Crossing(Coord<f64>),
+}
+
+impl VertexPayload<f64> for Origin {
+    fn merge(
+        point: Coord<f64>,
+        _divided: &PayloadSegment<'_, f64, Origin>,
+        _crossing: &PayloadSegment<'_, f64, Origin>,
+    ) -> Origin {
+        Origin::Crossing(point)
+    }
+}
+
+fn with_origins(multi_polygon: &MultiPolygon<f64>) -> ValuedMultiPolygon<f64, Origin> {
+    let values = multi_polygon
+        .0
+        .iter()
+        .map(|p| {
+            std::iter::once(p.exterior())
+                .chain(p.interiors())
+                .map(|ring| ring.0.iter().map(|c| Origin::Input(*c)).collect())
+                .collect()
+        })
+        .collect();
+    ValuedMultiPolygon {
+        multi_polygon: multi_polygon.clone(),
+        values,
+    }
+}
+
+#[test]
+fn test_generic_test_cases() {
+    for entry in glob("./fixtures/generic_test_cases/*.geojson").expect("Failed to read glob pattern") {
+        let filename = entry.unwrap().to_str().unwrap().to_string();
+        println!("Running test case: {}", filename);
+        let (_, p1, p2) = load_test_case(&filename);
+        let is_input_vertex = |point: &Coord<f64>| {
+            [&p1, &p2].iter().any(|input| {
+                input.0.iter().any(|polygon| {
+                    std::iter::once(polygon.exterior())
+                        .chain(polygon.interiors())
+                        .any(|ring| ring.0.contains(point))
+                })
+            })
+        };
+        let (v1, v2) = (with_origins(&p1), with_origins(&p2));
+
+        for operation in [
+            Operation::Intersection,
+            Operation::Union,
+            Operation::Difference,
+            Operation::Xor,
+        ] {
+            let result = try_boolean_with_payload(&v1, &v2, operation, FillRules::default()).unwrap();
+            assert_eq!(
+                result.multi_polygon,
+                p1.boolean(&p2, operation),
+                "{} / {:?}",
+                filename,
+                operation
+            );
+            assert_eq!(result.values.len(), result.multi_polygon.0.len());
+
+            for (polygon, polygon_values) in result.multi_polygon.0.iter().zip(&result.values) {
+                let rings = std::iter::once(polygon.exterior()).chain(polygon.interiors());
+                assert_eq!(polygon_values.len(), polygon.interiors().len() + 1);
+
+                for (ring, ring_values) in rings.zip(polygon_values) {
+                    assert_eq!(ring_values.len(), ring.0.len());
+                    assert_eq!(ring_values.first(), ring_values.last());
+                    for (point, value) in ring.0.iter().zip(ring_values) {
+                        // An input vertex touching an edge of the other operand may be reported
+                        // with the payload of the crossing, but the payload never moves.
+                        match value {
+                            Origin::Input(c) | Origin::Crossing(c) => assert_eq!(c, point, "{}", filename),
+                        }
+                        if !is_input_vertex(point) {
+                            assert!(matches!(value, Origin::Crossing(_)), "{} / {:?}", filename, point);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
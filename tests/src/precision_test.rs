@@ -0,0 +1,93 @@
+use super::data_generators::{generate_grid, generate_random_triangles};
+use super::helper::assert_no_crossings;
+use geo::{Area, MultiPolygon};
+use geo_booleanop::boolean::{BooleanOp, BooleanOpError, FillRules, Operation, PrecisionModel};
+
+fn assert_on_grid(multi_polygon: &MultiPolygon<f64>, precision: PrecisionModel<f64>) {
+    for polygon in &multi_polygon.0 {
+        for c in std::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .flat_map(|ring| &ring.0)
+        {
+            assert_eq!(precision.make_precise_coord(*c), *c, "vertex not on grid");
+        }
+    }
+}
+
+#[test]
+fn test_random_triangles_are_valid_at_precision() {
+    let subject = generate_random_triangles(10, 1);
+    let clipping = generate_random_triangles(10, 2);
+
+    for precision in [
+        PrecisionModel::decimals(1),
+        PrecisionModel::decimals(2),
+        PrecisionModel::grid_size(0.25),
+    ] {
+        for operation in [
+            Operation::Intersection,
+            Operation::Union,
+            Operation::Difference,
+            Operation::Xor,
+        ] {
+            let result = subject
+                .try_boolean_with_precision(&clipping, operation, FillRules::default(), precision)
+                .unwrap();
+
+            assert_on_grid(&result, precision);
+            assert_no_crossings(&result);
+        }
+    }
+}
+
+#[test]
+fn test_area_converges_with_precision() {
+    let subject = generate_random_triangles(10, 3);
+    let clipping = generate_random_triangles(10, 4);
+    let exact = subject.xor(&clipping).unsigned_area();
+
+    for (decimals, tolerance) in [(2, 0.1), (4, 0.001), (6, 0.00001)] {
+        let precision = PrecisionModel::decimals(decimals);
+        let area = subject
+            .boolean_with_precision(&clipping, Operation::Xor, FillRules::default(), precision)
+            .unsigned_area();
+        assert!(
+            (area - exact).abs() < tolerance,
+            "{} vs {} at {} decimals",
+            area,
+            exact,
+            decimals
+        );
+    }
+}
+
+#[test]
+fn test_input_on_grid_is_not_modified() {
+    let subject = generate_grid(0.0, 9.0, 1.5, 10);
+    let clipping = generate_grid(-0.5, 10.5, 2.0, 5);
+
+    let result = subject.boolean_with_precision(
+        &clipping,
+        Operation::Intersection,
+        FillRules::default(),
+        PrecisionModel::grid_size(0.25),
+    );
+
+    assert_eq!(result, subject.intersection(&clipping));
+}
+
+#[test]
+fn test_invalid_scale() {
+    let subject = generate_grid(0.0, 1.0, 0.5, 2);
+    for scale in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert_eq!(
+            subject.try_boolean_with_precision(
+                &subject,
+                Operation::Union,
+                FillRules::default(),
+                PrecisionModel::Fixed { scale }
+            ),
+            Err(BooleanOpError::InvalidPrecisionModel)
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use glob::glob;
+
+use super::helper::load_test_case;
+use geo::{Coord, Line, MultiPolygon, Polygon};
+use geo_booleanop::boolean::{BooleanOp, EdgeSource, Operation};
+
+fn source_line(operands: &[&MultiPolygon<f64>], source: &EdgeSource) -> Line<f64> {
+    let polygon: &Polygon<f64> = &operands[source.operand].0[source.polygon];
+    let ring = if source.ring == 0 {
+        polygon.exterior()
+    } else {
+        &polygon.interiors()[source.ring - 1]
+    };
+    Line::new(ring.0[source.edge], ring.0[source.edge + 1])
+}
+
+fn distance_to_line(point: Coord<f64>, line: Line<f64>) -> f64 {
+    let (d, p) = (line.delta(), point - line.start);
+    let t = ((p.x * d.x + p.y * d.y) / (d.x * d.x + d.y * d.y)).clamp(0.0, 1.0);
+    let (dx, dy) = (p.x - t * d.x, p.y - t * d.y);
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[test]
+fn test_generic_test_cases() {
+    for entry in glob("./fixtures/generic_test_cases/*.geojson").expect("Failed to read glob pattern") {
+        let filename = entry.unwrap().to_str().unwrap().to_string();
+        println!("Running test case: {}", filename);
+        let (_, p1, p2) = load_test_case(&filename);
+
+        for operation in [
+            Operation::Intersection,
+            Operation::Union,
+            Operation::Difference,
+            Operation::Xor,
+        ] {
+            let traced = p1.boolean_traced(&p2, operation);
+            assert_eq!(traced.multi_polygon, p1.boolean(&p2, operation));
+            assert_eq!(traced.sources.len(), traced.multi_polygon.0.len());
+
+            for (polygon, polygon_sources) in traced.multi_polygon.0.iter().zip(&traced.sources) {
+                let rings = std::iter::once(polygon.exterior()).chain(polygon.interiors());
+                assert_eq!(polygon_sources.len(), polygon.interiors().len() + 1);
+
+                for (ring, ring_sources) in rings.zip(polygon_sources) {
+                    assert_eq!(ring_sources.len(), ring.lines().count());
+
+                    for (line, source) in ring.lines().zip(ring_sources) {
+                        // Every output edge is a part of its source edge, up to the rounding
+                        // of intersection points.
+                        let input_line = source_line(&[&p1, &p2], source);
+                        let tolerance = 1e-9 * input_line.start.x.abs().max(input_line.start.y.abs()).max(1.0);
+                        assert!(distance_to_line(line.start, input_line) <= tolerance);
+                        assert!(distance_to_line(line.end, input_line) <= tolerance);
+                    }
+                }
+            }
+        }
+    }
+}
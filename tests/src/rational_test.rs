@@ -0,0 +1,242 @@
+use super::data_generators::generate_grid;
+use geo::{polygon, Coord, LineString, MultiPolygon, Polygon};
+use geo_booleanop::boolean::{
+    try_boolean_with_kernel, BooleanOp, FastKernel, FillRule, FillRules, Operation, OrderedField,
+};
+use num_traits::{Num, NumCast, One, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+const OPERATIONS: [Operation; 4] = [
+    Operation::Intersection,
+    Operation::Union,
+    Operation::Difference,
+    Operation::Xor,
+];
+
+/// A minimal rational number type with exact arithmetic, as long as the values fit in `i128`.
+#[derive(Clone, Copy, Debug)]
+struct Rational {
+    num: i128,
+    den: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Rational {
+    fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "Division by zero");
+        let divisor = gcd(num, den) * den.signum();
+        Rational {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+}
+
+impl PartialEq for Rational {
+    fn eq(&self, other: &Rational) -> bool {
+        // Both values are normalized.
+        self.num == other.num && self.den == other.den
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        // The denominators are positive.
+        (self.num * other.den).partial_cmp(&(other.num * self.den))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    fn add(self, other: Rational) -> Rational {
+        Rational::new(self.num * other.den + other.num * self.den, self.den * other.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, other: Rational) -> Rational {
+        Rational::new(self.num * other.den - other.num * self.den, self.den * other.den)
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    fn mul(self, other: Rational) -> Rational {
+        Rational::new(self.num * other.num, self.den * other.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+    fn div(self, other: Rational) -> Rational {
+        Rational::new(self.num * other.den, self.den * other.num)
+    }
+}
+
+impl Rem for Rational {
+    type Output = Rational;
+    fn rem(self, other: Rational) -> Rational {
+        let quotient = self / other;
+        self - other * Rational::new(quotient.num / quotient.den, 1)
+    }
+}
+
+impl Zero for Rational {
+    fn zero() -> Rational {
+        Rational::new(0, 1)
+    }
+    fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+}
+
+impl One for Rational {
+    fn one() -> Rational {
+        Rational::new(1, 1)
+    }
+}
+
+impl Num for Rational {
+    type FromStrRadixErr = std::num::ParseIntError;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Rational, Self::FromStrRadixErr> {
+        i128::from_str_radix(s, radix).map(|num| Rational::new(num, 1))
+    }
+}
+
+impl ToPrimitive for Rational {
+    fn to_i64(&self) -> Option<i64> {
+        (self.num / self.den).to_i64()
+    }
+    fn to_u64(&self) -> Option<u64> {
+        (self.num / self.den).to_u64()
+    }
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.num as f64 / self.den as f64)
+    }
+}
+
+impl NumCast for Rational {
+    fn from<T: ToPrimitive>(n: T) -> Option<Rational> {
+        n.to_i128().map(|num| Rational::new(num, 1))
+    }
+}
+
+impl OrderedField for Rational {}
+
+fn r(num: i128, den: i128) -> Rational {
+    Rational::new(num, den)
+}
+
+fn map_coords<A, B>(multi_polygon: &MultiPolygon<A>, f: impl Fn(A) -> B + Copy) -> MultiPolygon<B>
+where
+    A: geo::CoordNum,
+    B: geo::CoordNum,
+{
+    let map_ring = |ring: &LineString<A>| {
+        ring.0
+            .iter()
+            .map(|c| Coord { x: f(c.x), y: f(c.y) })
+            .collect::<LineString<B>>()
+    };
+    MultiPolygon(
+        multi_polygon
+            .0
+            .iter()
+            .map(|p| Polygon::new(map_ring(p.exterior()), p.interiors().iter().map(map_ring).collect()))
+            .collect(),
+    )
+}
+
+fn boolean(
+    subject: &[Polygon<Rational>],
+    clipping: &[Polygon<Rational>],
+    operation: Operation,
+) -> MultiPolygon<Rational> {
+    try_boolean_with_kernel::<Rational, FastKernel>(subject, clipping, operation, FillRules::default()).unwrap()
+}
+
+fn signed_area(ring: &LineString<Rational>) -> Rational {
+    ring.lines().fold(Rational::zero(), |sum, line| {
+        sum + line.start.x * line.end.y - line.end.x * line.start.y
+    }) / r(2, 1)
+}
+
+#[test]
+fn test_grid_matches_float_operation() {
+    // All coordinates and intersections of the grids are integers, which are exact in both types.
+    let subject = generate_grid(0.0, 36.0, 6.0, 7);
+    let clipping = generate_grid(-2.0, 38.0, 8.0, 5);
+    let to_rational = |v: f64| r(v as i128, 1);
+    let rational_subject = map_coords(&subject, to_rational);
+    let rational_clipping = map_coords(&clipping, to_rational);
+
+    for operation in OPERATIONS {
+        let result = boolean(&rational_subject.0, &rational_clipping.0, operation);
+        let float_result = map_coords(&result, |v| v.to_f64().unwrap());
+        assert_eq!(float_result, subject.boolean(&clipping, operation), "{:?}", operation);
+    }
+}
+
+#[test]
+fn test_exact_intersection_point() {
+    // The edge from (0, 0) to (1, 2) crosses the hypotenuse of the subject at (1/3, 2/3),
+    // which has no exact floating point representation.
+    let subject = [polygon![
+        (x: r(0, 1), y: r(0, 1)),
+        (x: r(1, 1), y: r(0, 1)),
+        (x: r(0, 1), y: r(1, 1)),
+        (x: r(0, 1), y: r(0, 1)),
+    ]];
+    let clipping = [polygon![
+        (x: r(0, 1), y: r(0, 1)),
+        (x: r(1, 1), y: r(2, 1)),
+        (x: r(0, 1), y: r(2, 1)),
+        (x: r(0, 1), y: r(0, 1)),
+    ]];
+
+    let result = boolean(&subject, &clipping, Operation::Intersection);
+    assert_eq!(result.0.len(), 1);
+    let exterior = result.0[0].exterior();
+    assert!(exterior.0.contains(&Coord { x: r(1, 3), y: r(2, 3) }));
+    assert_eq!(signed_area(exterior), r(1, 6));
+
+    // The parts of the operands add up to the union exactly.
+    let difference = boolean(&subject, &clipping, Operation::Difference);
+    let union = boolean(&subject, &clipping, Operation::Union);
+    let total_area = |multi_polygon: &MultiPolygon<Rational>| {
+        multi_polygon
+            .0
+            .iter()
+            .fold(Rational::zero(), |sum, p| sum + signed_area(p.exterior()))
+    };
+    assert_eq!(total_area(&difference), r(1, 2) - r(1, 6));
+    assert_eq!(total_area(&union), r(1, 2) + r(1, 1) - r(1, 6));
+}
+
+#[test]
+fn test_empty_operand() {
+    let subject = [polygon![
+        (x: r(0, 1), y: r(0, 1)),
+        (x: r(1, 2), y: r(0, 1)),
+        (x: r(0, 1), y: r(1, 2)),
+        (x: r(0, 1), y: r(0, 1)),
+    ]];
+    for rules in [FillRules::default(), FillRules::both(FillRule::NonZero)] {
+        let union = try_boolean_with_kernel::<Rational, FastKernel>(&subject, &[], Operation::Union, rules);
+        assert_eq!(union.unwrap().0.len(), 1);
+        let intersection =
+            try_boolean_with_kernel::<Rational, FastKernel>(&[], &subject, Operation::Intersection, rules);
+        assert!(intersection.unwrap().0.is_empty());
+        let empty = try_boolean_with_kernel::<Rational, FastKernel>(&[], &[], Operation::Union, rules);
+        assert!(empty.unwrap().0.is_empty());
+    }
+}
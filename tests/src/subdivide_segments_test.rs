@@ -1,38 +1,38 @@
 use super::helper::fixture_shapes;
 use geo_booleanop::boolean::compare_segments::compare_segments;
-use geo_booleanop::boolean::sweep_event::SweepEvent;
+use geo_booleanop::boolean::sweep_event::{EventArena, EventId, SweepEvent};
 use geo_booleanop::splay::SplaySet;
-use std::rc::{Rc, Weak};
 
 #[test]
 fn test_sweep_line() {
     let (s, c) = fixture_shapes("two_triangles.geojson");
+    let mut events = EventArena::new();
 
-    let ef_other = SweepEvent::new_rc(0, s.exterior().0[2], false, Weak::new(), true, true);
-    let ef = SweepEvent::new_rc(0, s.exterior().0[0], true, Rc::downgrade(&ef_other), true, true);
-    let eg_other = SweepEvent::new_rc(0, s.exterior().0[1], false, Weak::new(), false, true);
-    let eg = SweepEvent::new_rc(0, s.exterior().0[0], true, Rc::downgrade(&eg_other), false, true);
+    let ef_other = events.push(SweepEvent::new(0, s.exterior().0[2], false, None, true, true));
+    let ef = events.push(SweepEvent::new(0, s.exterior().0[0], true, Some(ef_other), true, true));
+    let eg_other = events.push(SweepEvent::new(0, s.exterior().0[1], false, None, false, true));
+    let eg = events.push(SweepEvent::new(0, s.exterior().0[0], true, Some(eg_other), false, true));
 
-    let mut tree = SplaySet::new(compare_segments);
-    tree.insert(ef.clone());
-    tree.insert(eg.clone());
+    let da_other = events.push(SweepEvent::new(0, c.exterior().0[2], false, None, true, true));
+    let da = events.push(SweepEvent::new(0, c.exterior().0[0], true, Some(da_other), true, true));
+    let dc_other = events.push(SweepEvent::new(0, c.exterior().0[1], false, None, false, true));
+    let dc = events.push(SweepEvent::new(0, c.exterior().0[0], true, Some(dc_other), false, true));
 
-    assert!(Rc::ptr_eq(tree.find(&ef).unwrap(), &ef));
-    assert!(Rc::ptr_eq(tree.min().unwrap(), &ef));
-    assert!(Rc::ptr_eq(tree.max().unwrap(), &eg));
-    assert!(Rc::ptr_eq(tree.next(&ef).unwrap(), &eg));
-    assert!(Rc::ptr_eq(tree.prev(&eg).unwrap(), &ef));
+    let mut tree = SplaySet::new(|a: &EventId, b: &EventId| compare_segments(&events, *a, *b).unwrap());
+    tree.insert(ef);
+    tree.insert(eg);
 
-    let da_other = SweepEvent::new_rc(0, c.exterior().0[2], false, Weak::new(), true, true);
-    let da = SweepEvent::new_rc(0, c.exterior().0[0], true, Rc::downgrade(&da_other), true, true);
-    let dc_other = SweepEvent::new_rc(0, c.exterior().0[1], false, Weak::new(), false, true);
-    let dc = SweepEvent::new_rc(0, c.exterior().0[0], true, Rc::downgrade(&dc_other), false, true);
+    assert_eq!(tree.find(&ef), Some(&ef));
+    assert_eq!(tree.min(), Some(&ef));
+    assert_eq!(tree.max(), Some(&eg));
+    assert_eq!(tree.next(&ef), Some(&eg));
+    assert_eq!(tree.prev(&eg), Some(&ef));
 
-    tree.insert(da.clone());
-    tree.insert(dc.clone());
+    tree.insert(da);
+    tree.insert(dc);
 
-    assert!(Rc::ptr_eq(tree.min().unwrap(), &da));
-    assert!(Rc::ptr_eq(tree.next(&da).unwrap(), &dc));
-    assert!(Rc::ptr_eq(tree.next(&dc).unwrap(), &ef));
-    assert!(Rc::ptr_eq(tree.next(&ef).unwrap(), &eg));
+    assert_eq!(tree.min(), Some(&da));
+    assert_eq!(tree.next(&da), Some(&dc));
+    assert_eq!(tree.next(&dc), Some(&ef));
+    assert_eq!(tree.next(&ef), Some(&eg));
 }
@@ -0,0 +1,79 @@
+use super::data_generators::{generate_grid_polygons, generate_random_triangles_polygons};
+use super::helper::{extract_expected_result, load_test_case, TestOperation};
+use geo::MultiPolygon;
+use geo_booleanop::boolean::{
+    BTreeSweepLine, BooleanOp, FillRules, Operation, SkipListSweepLine, SplaySweepLine, SweepLineStatus,
+};
+use glob::glob;
+
+const OPERATIONS: [Operation; 4] = [
+    Operation::Intersection,
+    Operation::Union,
+    Operation::Difference,
+    Operation::Xor,
+];
+
+fn check_same_results<S: SweepLineStatus>(p1: &MultiPolygon<f64>, p2: &MultiPolygon<f64>, name: &str) {
+    for operation in OPERATIONS {
+        let expected = p1.try_boolean_with_sweep_line::<SplaySweepLine>(p2, operation, FillRules::default());
+        let result = p1.try_boolean_with_sweep_line::<S>(p2, operation, FillRules::default());
+        assert_eq!(result, expected, "{} / {:?}", name, operation);
+    }
+}
+
+/// Compares the results of all test cases.
+fn check_all_cases<S: SweepLineStatus>() {
+    let test_cases: Vec<_> = glob("./fixtures/generic_test_cases/*.geojson")
+        .expect("Failed to read glob pattern")
+        .collect();
+    assert!(!test_cases.is_empty(), "Expected to find any test cases");
+
+    for entry in &test_cases {
+        let filename = entry.as_ref().expect("Valid glob entry").to_str().unwrap();
+        let (_, p1, p2) = load_test_case(filename);
+        check_same_results::<S>(&p1, &p2, filename);
+    }
+
+    let (p1, p2) = generate_grid_polygons();
+    check_same_results::<S>(&p1, &p2, "grid");
+    let (p1, p2) = generate_random_triangles_polygons();
+    check_same_results::<S>(&p1, &p2, "random_triangles");
+}
+
+#[test]
+fn test_btree_sweep_line_matches_splay_tree() {
+    check_all_cases::<BTreeSweepLine>();
+}
+
+#[test]
+fn test_skip_list_sweep_line_matches_splay_tree() {
+    check_all_cases::<SkipListSweepLine>();
+}
+
+fn check_expected_results<S: SweepLineStatus>(filename: &str) {
+    let (features, p1, p2) = load_test_case(filename);
+    for feature in features.iter().skip(2) {
+        let expected = extract_expected_result(feature);
+        let (subject, clipping, operation) = match expected.op {
+            TestOperation::Intersection => (&p1, &p2, Operation::Intersection),
+            TestOperation::Union => (&p1, &p2, Operation::Union),
+            TestOperation::Xor => (&p1, &p2, Operation::Xor),
+            TestOperation::DifferenceAB => (&p1, &p2, Operation::Difference),
+            TestOperation::DifferenceBA => (&p2, &p1, Operation::Difference),
+        };
+        let result = subject.try_boolean_with_sweep_line::<S>(clipping, operation, FillRules::default());
+        assert_eq!(result, Ok(expected.result), "{} / {:?}", filename, expected.op);
+    }
+}
+
+#[test]
+fn test_segments_divided_at_the_sweep_point_stay_ordered() {
+    // With exact intersections, the clipping edge from (-0.565625, 11.0828125) passing the
+    // lower left corner of the subject by less than an ulp is divided at the corner, while
+    // the corner is processed. Its part ending at the corner is ordered below the bottom edge
+    // afterwards, so it has to be moved in the sweep line to be found again by any structure.
+    let filename = "./fixtures/generic_test_cases/fatal4.geojson";
+    check_expected_results::<SplaySweepLine>(filename);
+    check_expected_results::<BTreeSweepLine>(filename);
+    check_expected_results::<SkipListSweepLine>(filename);
+}
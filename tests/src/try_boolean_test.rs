@@ -0,0 +1,120 @@
+use super::helper::{fixture_shapes, operation_area, xy};
+use geo::{Area, LineString, MultiPolygon, Polygon};
+use geo_booleanop::boolean::{BooleanOp, BooleanOpError, Operation};
+use std::time::{Duration, Instant};
+
+fn square(x: f64, y: f64, size: f64) -> Polygon<f64> {
+    Polygon::new(
+        LineString(vec![
+            xy(x, y),
+            xy(x + size, y),
+            xy(x + size, y + size),
+            xy(x, y + size),
+            xy(x, y),
+        ]),
+        vec![],
+    )
+}
+
+#[test]
+fn test_try_boolean_matches_boolean() {
+    let (s, c) = fixture_shapes("two_shapes.geojson");
+
+    assert_eq!(s.try_intersection(&c), Ok(s.intersection(&c)));
+    assert_eq!(s.try_union(&c), Ok(s.union(&c)));
+    assert_eq!(s.try_difference(&c), Ok(s.difference(&c)));
+    assert_eq!(s.try_xor(&c), Ok(s.xor(&c)));
+}
+
+#[test]
+fn test_try_boolean_non_finite_coordinates() {
+    let valid = square(0.0, 0.0, 2.0);
+    let mut invalid = square(1.0, 1.0, 2.0);
+    invalid.exterior_mut(|ring| ring.0[1].x = f64::NAN);
+
+    assert_eq!(valid.try_union(&invalid), Err(BooleanOpError::NonFiniteCoordinate));
+    assert_eq!(invalid.try_union(&valid), Err(BooleanOpError::NonFiniteCoordinate));
+
+    let mut infinite = square(1.0, 1.0, 2.0);
+    infinite.exterior_mut(|ring| ring.0[2].y = f64::INFINITY);
+    let multi = MultiPolygon(vec![valid, infinite]);
+
+    assert_eq!(
+        multi.try_intersection(&square(5.0, 5.0, 1.0)),
+        Err(BooleanOpError::NonFiniteCoordinate)
+    );
+}
+
+#[test]
+#[should_panic(expected = "non-finite")]
+fn test_boolean_panics_on_error() {
+    let mut invalid = square(0.0, 0.0, 1.0);
+    invalid.exterior_mut(|ring| ring.0[0].y = f64::NAN);
+
+    invalid.union(&square(0.5, 0.5, 1.0));
+}
+
+#[test]
+fn test_intersections_of_rounded_points_terminate() {
+    // Rounded intersection points used to divide the segments over and over again.
+    let a = MultiPolygon(vec![Polygon::new(
+        LineString(vec![xy(4, 8), xy(8, 4), xy(3, 9), xy(7, 8), xy(4, 8)]),
+        vec![],
+    )]);
+    let b = MultiPolygon(vec![Polygon::new(
+        LineString(vec![xy(7, 8), xy(7, 0), xy(5, 9), xy(0, 8), xy(7, 8)]),
+        vec![],
+    )]);
+
+    let result = a.try_boolean(&b, Operation::Intersection).unwrap();
+    let expected = operation_area(&a.0, &b.0, |subject_winding, clipping_winding| {
+        subject_winding % 2 != 0 && clipping_winding % 2 != 0
+    });
+
+    assert!((result.unsigned_area() - expected).abs() <= 1e-9);
+}
+
+#[test]
+fn test_runaway_divisions_are_reported_quickly() {
+    // The rounded intersection points of these triangles keep dividing the same segments
+    // more than a thousand times.
+    let triangle = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| {
+        Polygon::new(
+            LineString(vec![xy(a.0, a.1), xy(b.0, b.1), xy(c.0, c.1), xy(a.0, a.1)]),
+            vec![],
+        )
+    };
+    let a = MultiPolygon(vec![
+        triangle((6.0, -1e-12), (3.000000000001, 1.0), (2.999999999999, 5.999999999999)),
+        triangle(
+            (1.999999999999, 6.999999999999),
+            (2.999999999999, 2.000000000001),
+            (4.0, 1e-12),
+        ),
+    ]);
+    let b = MultiPolygon(vec![triangle((5.999999999999, 1.0), (0.0, 6.0), (3.0, 3.999999999999))]);
+
+    let start = Instant::now();
+    assert_eq!(a.try_union(&b), Err(BooleanOpError::EventLimitExceeded));
+    assert!(start.elapsed() < Duration::from_millis(100));
+}
+
+/// Operand of an external crate, which implements `BooleanOp` like before the fallible and
+/// fill rule variants were added.
+struct Tile(Polygon<f64>);
+
+impl BooleanOp<f64> for Tile {
+    fn boolean(&self, rhs: &Tile, operation: Operation) -> MultiPolygon<f64> {
+        self.0.boolean(&rhs.0, operation)
+    }
+}
+
+#[test]
+fn test_implementing_boolean_op_only_requires_boolean() {
+    let (a, b) = (Tile(square(0.0, 0.0, 2.0)), Tile(square(1.0, 1.0, 2.0)));
+
+    assert_eq!(a.intersection(&b), MultiPolygon(vec![square(1.0, 1.0, 1.0)]));
+    assert_eq!(a.union(&b).unsigned_area(), 7.0);
+    assert_eq!(a.difference(&b).unsigned_area(), 3.0);
+    assert_eq!(a.xor(&b).unsigned_area(), 6.0);
+}
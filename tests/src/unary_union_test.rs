@@ -0,0 +1,107 @@
+use super::data_generators::{generate_grid, generate_random_grid_rects, generate_random_triangles};
+use super::helper::xy;
+use geo::{Area, LineString, MultiPolygon, Polygon};
+use geo_booleanop::boolean::{try_unary_union, unary_union, unary_union_iter, BooleanOp};
+
+fn square(x: f64, y: f64) -> Polygon<f64> {
+    Polygon::new(
+        LineString(vec![
+            xy(x, y),
+            xy(x + 1.0, y),
+            xy(x + 1.0, y + 1.0),
+            xy(x, y + 1.0),
+            xy(x, y),
+        ]),
+        vec![],
+    )
+}
+
+fn assert_area_eq(a: f64, b: f64) {
+    assert!((a - b).abs() <= 1e-9 * a.abs().max(1.0), "areas differ: {} vs {}", a, b);
+}
+
+#[test]
+fn test_adjacent_tiles_are_dissolved() {
+    let grid = generate_grid(0.0, 9.0, 1.0, 10);
+
+    let result = unary_union(&grid.0);
+
+    assert_eq!(result.0.len(), 1);
+    assert!(result.0[0].interiors().is_empty());
+    assert_area_eq(result.unsigned_area(), 100.0);
+}
+
+#[test]
+fn test_overlapping_tiles_are_dissolved() {
+    let grid = generate_grid(0.0, 9.0, 1.5, 10);
+
+    let result = unary_union(&grid.0);
+
+    assert_eq!(result.0.len(), 1);
+    assert!(result.0[0].interiors().is_empty());
+    assert_area_eq(result.unsigned_area(), 10.5 * 10.5);
+}
+
+#[test]
+fn test_ring_of_tiles_has_hole() {
+    let tiles: Vec<_> = (0..3)
+        .flat_map(|x| (0..3).map(move |y| (x, y)))
+        .filter(|&(x, y)| (x, y) != (1, 1))
+        .map(|(x, y)| square(x as f64, y as f64))
+        .collect();
+
+    let result = unary_union_iter(tiles.iter());
+
+    assert_eq!(result.0.len(), 1);
+    assert_eq!(result.0[0].interiors().len(), 1);
+    assert_area_eq(result.unsigned_area(), 8.0);
+}
+
+#[test]
+fn test_duplicates_are_merged() {
+    let polygon = square(0.0, 0.0);
+
+    let result = unary_union(&[polygon.clone(), polygon.clone(), polygon.clone()]);
+
+    assert_eq!(result, MultiPolygon(vec![polygon]));
+}
+
+#[test]
+fn test_matches_pairwise_union() {
+    let triangles = generate_random_triangles(20, 3);
+
+    let pairwise = triangles
+        .0
+        .iter()
+        .fold(MultiPolygon(vec![]), |acc, triangle| acc.union(triangle));
+    let result = unary_union_iter(triangles.0);
+
+    assert_area_eq(result.unsigned_area(), pairwise.unsigned_area());
+}
+
+#[test]
+fn test_three_overlapping_edges_of_same_operand() {
+    let rect = |x1: f64, y1: f64, x2: f64, y2: f64| {
+        Polygon::new(
+            LineString(vec![xy(x1, y1), xy(x2, y1), xy(x2, y2), xy(x1, y2), xy(x1, y1)]),
+            vec![],
+        )
+    };
+    let rects = [rect(3., 3., 5., 5.), rect(1., 3., 3., 6.), rect(3., 4., 5., 5.)];
+
+    let result = try_unary_union(&rects).unwrap();
+
+    assert_area_eq(result.unsigned_area(), 10.0);
+}
+
+#[test]
+fn test_matches_pairwise_union_of_grid_rects() {
+    for seed in 0..500 {
+        let rects = generate_random_grid_rects(3 + seed as usize % 4, 6, seed);
+
+        let pairwise = rects.0.iter().fold(MultiPolygon(vec![]), |acc, rect| acc.union(rect));
+        let result = try_unary_union(&rects.0).unwrap_or_else(|err| panic!("seed {}: {:?}", seed, err));
+
+        assert_area_eq(result.unsigned_area(), pairwise.unsigned_area());
+    }
+}
@@ -0,0 +1,104 @@
+use glob::glob;
+
+use super::helper::load_test_case;
+use geo::{Coord, MultiPolygon};
+use geo_booleanop::boolean::{try_boolean_with_values, BooleanOp, FillRules, Operation, ValuedMultiPolygon};
+
+fn with_values<Z>(multi_polygon: &MultiPolygon<f64>, f: impl Fn(Coord<f64>) -> Z) -> ValuedMultiPolygon<f64, Z> {
+    let values = multi_polygon
+        .0
+        .iter()
+        .map(|p| {
+            std::iter::once(p.exterior())
+                .chain(p.interiors())
+                .map(|ring| ring.0.iter().map(|c| f(*c)).collect())
+                .collect()
+        })
+        .collect();
+    ValuedMultiPolygon {
+        multi_polygon: multi_polygon.clone(),
+        values,
+    }
+}
+
+#[test]
+fn test_generic_test_cases() {
+    // The values are an affine function of the coordinates, which linear interpolation
+    // along the edges reproduces up to rounding.
+    let plane = |c: Coord<f64>| 3.0 * c.x - 2.0 * c.y + 5.0;
+
+    for entry in glob("./fixtures/generic_test_cases/*.geojson").expect("Failed to read glob pattern") {
+        let filename = entry.unwrap().to_str().unwrap().to_string();
+        println!("Running test case: {}", filename);
+        let (_, p1, p2) = load_test_case(&filename);
+        let (v1, v2) = (with_values(&p1, plane), with_values(&p2, plane));
+
+        for operation in [
+            Operation::Intersection,
+            Operation::Union,
+            Operation::Difference,
+            Operation::Xor,
+        ] {
+            let result = try_boolean_with_values(&v1, &v2, operation, FillRules::default()).unwrap();
+            assert_eq!(result.multi_polygon, p1.boolean(&p2, operation));
+            assert_eq!(result.values.len(), result.multi_polygon.0.len());
+
+            for (polygon, polygon_values) in result.multi_polygon.0.iter().zip(&result.values) {
+                let rings = std::iter::once(polygon.exterior()).chain(polygon.interiors());
+                assert_eq!(polygon_values.len(), polygon.interiors().len() + 1);
+
+                for (ring, ring_values) in rings.zip(polygon_values) {
+                    assert_eq!(ring_values.len(), ring.0.len());
+                    for (point, value) in ring.0.iter().zip(ring_values) {
+                        let expected = plane(*point);
+                        let tolerance = 1e-9 * expected.abs().max(point.x.abs()).max(point.y.abs()).max(1.0);
+                        assert!(
+                            (value - expected).abs() <= tolerance,
+                            "{} / {:?}: {} vs {} at {:?}",
+                            filename,
+                            operation,
+                            value,
+                            expected,
+                            point
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_input_vertices_keep_their_values() {
+    // The values are unrelated to the coordinates, so only input vertices have known values.
+    let bits = |c: Coord<f64>| [c.x.to_bits() as f64, c.y.to_bits() as f64];
+
+    for entry in glob("./fixtures/generic_test_cases/*.geojson").expect("Failed to read glob pattern") {
+        let filename = entry.unwrap().to_str().unwrap().to_string();
+        let (_, p1, p2) = load_test_case(&filename);
+        let is_input_vertex = |point: &Coord<f64>| {
+            [&p1, &p2].iter().any(|input| {
+                input.0.iter().any(|polygon| {
+                    std::iter::once(polygon.exterior())
+                        .chain(polygon.interiors())
+                        .any(|ring| ring.0.contains(point))
+                })
+            })
+        };
+        let (v1, v2) = (with_values(&p1, bits), with_values(&p2, bits));
+
+        for operation in [Operation::Union, Operation::Xor] {
+            let result = try_boolean_with_values(&v1, &v2, operation, FillRules::default()).unwrap();
+            for (polygon, polygon_values) in result.multi_polygon.0.iter().zip(&result.values) {
+                let rings = std::iter::once(polygon.exterior()).chain(polygon.interiors());
+                for (ring, ring_values) in rings.zip(polygon_values) {
+                    for (point, value) in ring.0.iter().zip(ring_values) {
+                        if is_input_vertex(point) {
+                            assert_eq!(*value, bits(*point), "{} / {:?}: {:?}", filename, operation, point);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}